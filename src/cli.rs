@@ -71,15 +71,22 @@ pub enum Commands {
         /// Use user-level paths instead of auto-detecting the scope.
         #[arg(long, conflicts_with = "global")]
         user: bool,
-        /// Use system-level paths instead of auto-detecting the scope.
+        /// Use system-level paths instead of auto-detecting the scope; installs there obey
+        /// `/etc/za/tool-policy.toml` when present.
         #[arg(long, conflicts_with = "user")]
         global: bool,
+        /// Activate tools for the repository owning the nearest `za.tools.toml`.
+        #[arg(long, conflicts_with_all = ["user", "global"])]
+        project: bool,
         #[command(subcommand)]
         cmd: ToolCommands,
     },
     /// Run a tool with normalized proxy settings
+    ///
+    /// A project pin that is not installed fails the run; set `ZA_RUN_PIN_FALLBACK=1` to use the
+    /// user or global tool instead.
     Run {
-        /// Tool name, e.g. `codex`
+        /// Tool name, e.g. `codex`, or `name@version` to run that version without activating it
         tool: String,
         /// Arguments passed through to the tool
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
@@ -185,10 +192,12 @@ pub enum ToolCommands {
     /// Install one or more tools and make them active in this scope
     #[command(alias = "pull")]
     Install {
-        /// Tool names, e.g. `codex just`
+        /// Tool names, e.g. `codex just`, or GitHub repositories such as `owner/repo` whose
+        /// release asset for this host is matched by name
         #[arg(required = true, num_args = 1.., value_name = "TOOL")]
         tools: Vec<String>,
-        /// Install a specific version instead of the latest release. Requires exactly one tool.
+        /// Install a specific version or semver range (e.g. `^1.30`) instead of the latest release.
+        /// Requires exactly one tool.
         #[arg(long, value_name = "VERSION")]
         version: Option<String>,
         /// Adopt an existing unmanaged binary already present in this scope. Requires exactly one tool.
@@ -200,6 +209,14 @@ pub enum ToolCommands {
         /// Print per-tool resolution and stage details.
         #[arg(long)]
         verbose: bool,
+        /// Install release assets for another target, e.g. `aarch64-unknown-linux-musl` or `linux/arm64`.
+        /// Builds for another architecture go to a separate store and are not activated.
+        #[arg(long, value_name = "TARGET", conflicts_with = "adopt")]
+        target: Option<String>,
+        /// Refuse `owner/repo` release assets without a published sha256 digest or checksum file
+        /// instead of installing them unverified. Later updates of the tool keep the requirement.
+        #[arg(long, conflicts_with = "adopt")]
+        require_digest: bool,
     },
     /// Diagnose za-managed tool state and repair hints
     Doctor {
@@ -208,6 +225,13 @@ pub enum ToolCommands {
         /// Print JSON output for scripting.
         #[arg(long)]
         json: bool,
+        /// Apply safe repairs: re-link active entries, restore missing or mismatched manifests, and
+        /// remove leftovers.
+        #[arg(long)]
+        fix: bool,
+        /// Show what `--fix` would change without touching anything.
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
     },
     /// List za-managed tools and availability in this scope
     #[command(name = "ls", alias = "list")]
@@ -237,7 +261,7 @@ pub enum ToolCommands {
         all: bool,
         /// Tool names. Omit to update all za-managed tools in this scope.
         tools: Vec<String>,
-        /// Pin the update target to a specific version. Requires exactly one tool.
+        /// Pin the update target to a specific version or semver range. Requires exactly one tool.
         #[arg(long, value_name = "VERSION")]
         version: Option<String>,
         /// Update Codex to the newest alpha pre-release.
@@ -273,6 +297,16 @@ pub enum ToolCommands {
         /// Print per-tool resolution and stage details.
         #[arg(long)]
         verbose: bool,
+        /// Install release assets for another target, e.g. `aarch64-unknown-linux-musl` or `linux/arm64`.
+        /// Builds for another architecture go to a separate store and are not activated.
+        #[arg(long, value_name = "TARGET")]
+        target: Option<String>,
+        /// Install exactly the versions and assets recorded in the lockfile next to the manifest.
+        #[arg(long)]
+        locked: bool,
+        /// Uninstall za-managed tools that the manifest no longer lists.
+        #[arg(long)]
+        prune: bool,
     },
     /// Remove one or all installed tool versions
     #[command(alias = "rm")]
@@ -304,6 +338,9 @@ pub enum ToolCommands {
         /// Print JSON output for scripting.
         #[arg(long)]
         json: bool,
+        /// Fetch the release notes between the installed and the newer version.
+        #[arg(long)]
+        changelog: bool,
         /// Return non-zero when updates are available.
         #[arg(long)]
         fail_on_updates: bool,
@@ -311,6 +348,51 @@ pub enum ToolCommands {
         #[arg(long)]
         fail_on_check_errors: bool,
     },
+    /// Re-activate a retained older version of a tool. Activation keeps the newest 2 versions;
+    /// change that with `za config set tool-retain-versions <N>`, or `all` to never prune.
+    Rollback {
+        /// Tool name, e.g. `codex`
+        tool: String,
+        /// Version to re-activate. Defaults to the most recently installed non-active version.
+        #[arg(long, value_name = "VERSION")]
+        to: Option<String>,
+    },
+    /// Show when tools were installed, updated, adopted, rolled back or removed in this scope
+    History {
+        /// Tool name. Omit to show every recorded tool.
+        tool: Option<String>,
+        /// Only show events from the last this many days.
+        #[arg(long, value_name = "DAYS")]
+        days: Option<u64>,
+        /// Print JSON output for scripting.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Inspect or prune the download cache shared by every scope
+    Cache {
+        #[command(subcommand)]
+        cmd: ToolCacheCommands,
+    },
+    /// Export the active tools as a reproducible Dockerfile, install script or sync manifest
+    Export {
+        /// Tool names. Omit to export every active za-managed tool in this scope.
+        tools: Vec<String>,
+        /// Artifact to emit.
+        #[arg(long, value_enum, default_value = "sh")]
+        format: ToolExportFormat,
+        /// Write the artifact to this file instead of stdout. A sync manifest also gets a lockfile.
+        #[arg(long, short = 'o', value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Emit a software bill of materials of the active tools in this scope
+    Sbom {
+        /// Document format.
+        #[arg(long, value_enum, default_value = "cyclonedx")]
+        format: ToolSbomFormat,
+        /// Write the SBOM to this file instead of stdout.
+        #[arg(long, short = 'o', value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
     /// Adopt an existing unmanaged binary already present in this scope
     #[command(hide = true)]
     Adopt {
@@ -319,6 +401,34 @@ pub enum ToolCommands {
     },
 }
 
+/// `za tool cache` sub-commands
+#[derive(Subcommand)]
+pub enum ToolCacheCommands {
+    /// List cached release assets, most recently used first
+    #[command(name = "ls", alias = "list")]
+    Ls {
+        /// Print JSON output for scripting.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove cached release assets. Without options, trims the cache to its size cap
+    /// (`za config set tool-cache-max-size`, default 2G).
+    Prune {
+        /// Remove every cached asset and interrupted download.
+        #[arg(long, conflicts_with_all = ["older_than", "max_size"])]
+        all: bool,
+        /// Remove assets and interrupted downloads unused this long, e.g. `30d` or `2weeks`.
+        #[arg(long, value_name = "DURATION")]
+        older_than: Option<String>,
+        /// Evict least recently used assets until the cache fits, e.g. `512M` or `2G`.
+        #[arg(long, value_name = "SIZE")]
+        max_size: Option<String>,
+        /// Preview which assets would be removed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 /// `za port` sub-commands
 #[derive(Subcommand)]
 pub enum PortCommands {
@@ -698,6 +808,24 @@ pub enum CompletionShell {
     Powershell,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ToolExportFormat {
+    /// Build stage that installs the tools under `/opt/za/bin`.
+    Dockerfile,
+    /// POSIX shell script that downloads, verifies and installs the tools.
+    Sh,
+    /// `za.tools.toml` pinned to the exact versions.
+    SyncManifest,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ToolSbomFormat {
+    /// CycloneDX 1.5 JSON.
+    Cyclonedx,
+    /// SPDX 2.3 JSON.
+    Spdx,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
 pub enum AiShell {
     Bash,
@@ -721,6 +849,9 @@ pub enum ConfigCommands {
         #[arg(value_enum)]
         key: ConfigKey,
         value: String,
+        /// Per-tool override of `tool-min-release-age-days`, e.g. `--tool rg`
+        #[arg(long, value_name = "NAME")]
+        tool: Option<String>,
     },
     /// Get a config value
     Get {
@@ -729,11 +860,17 @@ pub enum ConfigCommands {
         /// Print raw value (for scripting). Use with care.
         #[arg(long)]
         raw: bool,
+        /// Per-tool override of `tool-min-release-age-days`, e.g. `--tool rg`
+        #[arg(long, value_name = "NAME")]
+        tool: Option<String>,
     },
     /// Remove a config value
     Unset {
         #[arg(value_enum)]
         key: ConfigKey,
+        /// Per-tool override of `tool-min-release-age-days`, e.g. `--tool rg`
+        #[arg(long, value_name = "NAME")]
+        tool: Option<String>,
     },
 }
 
@@ -1044,6 +1181,10 @@ pub enum GitAuthCommands {
 pub enum ConfigKey {
     #[value(name = "github-token")]
     GithubToken,
+    #[value(name = "github-api-base")]
+    GithubApiBase,
+    #[value(name = "github-download-mirror")]
+    GithubDownloadMirror,
     #[value(name = "proxy-http")]
     ProxyHttp,
     #[value(name = "proxy-https")]
@@ -1068,6 +1209,12 @@ pub enum ConfigKey {
     ToolAll,
     #[value(name = "tool-no-proxy")]
     ToolNoProxy,
+    #[value(name = "tool-retain-versions")]
+    ToolRetainVersions,
+    #[value(name = "tool-min-release-age-days")]
+    ToolMinReleaseAgeDays,
+    #[value(name = "tool-cache-max-size")]
+    ToolCacheMaxSize,
     #[value(name = "update-http")]
     UpdateHttp,
     #[value(name = "update-https")]
@@ -1109,7 +1256,8 @@ mod tests {
         AiCommands, AiGitCommands, AiShell, CiCommands, Cli, CodexCommands, ColorWhen, Commands,
         CompletionCommands, CompletionShell, DepsCommands, DiffArgs, DiffCommands, DiffKindFilter,
         DiffRiskFilter, GhCommands, GitAuthCommands, IdeAgentCommands, IdeCommands, PinCommands,
-        PortCommands, PortSignal, ToolCommands,
+        PortCommands, PortSignal, ToolCacheCommands, ToolCommands, ToolExportFormat,
+        ToolSbomFormat,
     };
     use clap::Parser;
     use std::path::PathBuf;
@@ -1123,6 +1271,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn run_accepts_versioned_tool_and_passthrough_args() {
+        let cli =
+            Cli::try_parse_from(["za", "run", "rg@13.0.0", "--", "--version"]).expect("must parse");
+        match cli.cmd {
+            Commands::Run { tool, args } => {
+                assert_eq!(tool, "rg@13.0.0");
+                assert_eq!(args, ["--version"]);
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
     #[test]
     fn ai_shell_parses_shell_enum() {
         let cli = Cli::try_parse_from(["za", "ai", "shell", "bash"]).expect("must parse");
@@ -1234,7 +1395,9 @@ mod tests {
         let cli = Cli::try_parse_from(["za", "tool", "install", "codex", "--version", "0.105.0"])
             .expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(!user);
                 assert!(!global);
                 assert!(matches!(
@@ -1245,6 +1408,8 @@ mod tests {
                         adopt: false,
                         dry_run: false,
                         verbose: false,
+                        target: None,
+                        require_digest: false,
                     } if tools == vec!["codex"] && version == "0.105.0"
                 ));
             }
@@ -1257,7 +1422,9 @@ mod tests {
         let cli =
             Cli::try_parse_from(["za", "tool", "install", "just", "cross"]).expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(!user);
                 assert!(!global);
                 assert!(matches!(
@@ -1268,6 +1435,8 @@ mod tests {
                         adopt: false,
                         dry_run: false,
                         verbose: false,
+                        target: None,
+                        require_digest: false,
                     } if tools == vec!["just", "cross"]
                 ));
             }
@@ -1280,7 +1449,9 @@ mod tests {
         let cli = Cli::try_parse_from(["za", "tool", "--user", "update", "codex", "rg"])
             .expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(user);
                 assert!(!global);
                 assert!(matches!(
@@ -1304,7 +1475,9 @@ mod tests {
         let cli =
             Cli::try_parse_from(["za", "tool", "--global", "update", "codex"]).expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(!user);
                 assert!(global);
                 assert!(matches!(
@@ -1409,6 +1582,8 @@ mod tests {
                         adopt: true,
                         dry_run: false,
                         verbose: false,
+                        target: None,
+                        require_digest: false,
                     } if tools == vec!["codex"]
                 ));
             }
@@ -1430,6 +1605,8 @@ mod tests {
                         adopt: false,
                         dry_run: true,
                         verbose: false,
+                        target: None,
+                        require_digest: false,
                     } if tools == vec!["ble.sh"]
                 ));
             }
@@ -1451,6 +1628,8 @@ mod tests {
                         adopt: false,
                         dry_run: false,
                         verbose: true,
+                        target: None,
+                        require_digest: false,
                     } if tools == vec!["just", "cross"]
                 ));
             }
@@ -1506,7 +1685,9 @@ mod tests {
     fn tool_update_parses_all_flag() {
         let cli = Cli::try_parse_from(["za", "tool", "update", "--all"]).expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(!user);
                 assert!(!global);
                 assert!(matches!(
@@ -1530,7 +1711,9 @@ mod tests {
         let cli = Cli::try_parse_from(["za", "tool", "update", "--all", "--verbose"])
             .expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(!user);
                 assert!(!global);
                 assert!(matches!(
@@ -1549,6 +1732,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tool_install_parses_target_and_rejects_adopt() {
+        let cli = Cli::try_parse_from([
+            "za",
+            "tool",
+            "install",
+            "rg",
+            "--target",
+            "aarch64-unknown-linux-musl",
+        ])
+        .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Install {
+                        tools,
+                        target: Some(target),
+                        require_digest: false,
+                        ..
+                    } if tools == vec!["rg"] && target == "aarch64-unknown-linux-musl"
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+
+        assert!(
+            Cli::try_parse_from([
+                "za",
+                "tool",
+                "install",
+                "rg",
+                "--adopt",
+                "--target",
+                "linux/arm64",
+            ])
+            .is_err()
+        );
+    }
+
     #[test]
     fn tool_sync_parses_flags() {
         let cli = Cli::try_parse_from([
@@ -1562,7 +1785,9 @@ mod tests {
         ])
         .expect("must parse");
         match cli.cmd {
-            Commands::Tool { user, global, cmd } => {
+            Commands::Tool {
+                user, global, cmd, ..
+            } => {
                 assert!(!user);
                 assert!(!global);
                 assert!(matches!(
@@ -1571,6 +1796,9 @@ mod tests {
                         file,
                         dry_run: true,
                         verbose: true,
+                        target: None,
+                        locked: false,
+                        prune: false,
                     } if file == std::path::Path::new("za.tools.toml")
                 ));
             }
@@ -1578,6 +1806,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tool_sync_parses_locked_and_prune_flags() {
+        let cli =
+            Cli::try_parse_from(["za", "tool", "sync", "--locked", "--prune"]).expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Sync {
+                        locked: true,
+                        prune: true,
+                        dry_run: false,
+                        ..
+                    }
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn tool_parses_project_scope_flag() {
+        let cli = Cli::try_parse_from(["za", "tool", "--project", "sync"]).expect("must parse");
+        match cli.cmd {
+            Commands::Tool {
+                user,
+                global,
+                project,
+                cmd,
+            } => {
+                assert!(project);
+                assert!(!user);
+                assert!(!global);
+                assert!(matches!(cmd, ToolCommands::Sync { .. }));
+            }
+            _ => panic!("unexpected command"),
+        }
+        assert!(Cli::try_parse_from(["za", "tool", "--project", "--user", "list"]).is_err());
+    }
+
+    #[test]
+    fn tool_cache_prune_parses_limits() {
+        let cli = Cli::try_parse_from([
+            "za",
+            "tool",
+            "cache",
+            "prune",
+            "--older-than",
+            "30d",
+            "--max-size",
+            "1G",
+            "--dry-run",
+        ])
+        .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Cache {
+                        cmd: ToolCacheCommands::Prune {
+                            all: false,
+                            older_than: Some(older_than),
+                            max_size: Some(max_size),
+                            dry_run: true,
+                        }
+                    } if older_than == "30d" && max_size == "1G"
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+        assert!(
+            Cli::try_parse_from(["za", "tool", "cache", "prune", "--all", "--max-size", "1G"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn tool_rollback_parses_target_version() {
+        let cli = Cli::try_parse_from(["za", "tool", "rollback", "codex", "--to", "0.104.0"])
+            .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Rollback { tool, to: Some(to) }
+                        if tool == "codex" && to == "0.104.0"
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
     #[test]
     fn deps_parses_verbose_flag() {
         let cli = Cli::try_parse_from(["za", "deps", "--verbose"]).expect("must parse");
@@ -1801,13 +2121,101 @@ mod tests {
             Commands::Tool { cmd, .. } => {
                 assert!(matches!(
                     cmd,
-                    ToolCommands::Doctor { tools, json: true } if tools == vec!["codex"]
+                    ToolCommands::Doctor { tools, json: true, fix: false, dry_run: false }
+                        if tools == vec!["codex"]
                 ));
             }
             _ => panic!("unexpected command"),
         }
     }
 
+    #[test]
+    fn tool_history_parses_tool_days_and_json() {
+        let cli = Cli::try_parse_from(["za", "tool", "history", "rg", "--days", "7", "--json"])
+            .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::History {
+                        tool: Some(tool),
+                        days: Some(7),
+                        json: true,
+                    } if tool == "rg"
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn tool_export_parses_format_and_output() {
+        let cli = Cli::try_parse_from([
+            "za",
+            "tool",
+            "export",
+            "rg",
+            "--format",
+            "sync-manifest",
+            "-o",
+            "za.tools.toml",
+        ])
+        .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Export {
+                        tools,
+                        format: ToolExportFormat::SyncManifest,
+                        output: Some(output),
+                    } if tools == vec!["rg"] && output == std::path::Path::new("za.tools.toml")
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn tool_sbom_parses_format_and_output() {
+        let cli =
+            Cli::try_parse_from(["za", "tool", "sbom", "--format", "spdx", "-o", "sbom.json"])
+                .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Sbom {
+                        format: ToolSbomFormat::Spdx,
+                        output: Some(output),
+                    } if output == std::path::Path::new("sbom.json")
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+    }
+
+    #[test]
+    fn tool_doctor_dry_run_requires_fix() {
+        let cli = Cli::try_parse_from(["za", "tool", "doctor", "--fix", "--dry-run"])
+            .expect("must parse");
+        match cli.cmd {
+            Commands::Tool { cmd, .. } => {
+                assert!(matches!(
+                    cmd,
+                    ToolCommands::Doctor {
+                        fix: true,
+                        dry_run: true,
+                        ..
+                    }
+                ));
+            }
+            _ => panic!("unexpected command"),
+        }
+
+        assert!(Cli::try_parse_from(["za", "tool", "doctor", "--dry-run"]).is_err());
+    }
+
     #[test]
     fn port_wait_parses_timeout_and_interval() {
         let cli = Cli::try_parse_from([
@@ -30,7 +30,6 @@ use std::{
 use self::model::*;
 use self::render::*;
 
-const GITHUB_API_BASE: &str = "https://api.github.com";
 const GITHUB_API_VERSION: &str = "2022-11-28";
 const HTTP_USER_AGENT: &str = "za-ci/0.1";
 const CI_CONFIG_FILE_NAME: &str = "ci.toml";
@@ -110,7 +109,7 @@ struct GitHubClient {
 impl GitHubClient {
     fn new(github_token_override: Option<String>, cache_mode: CiCacheMode) -> Result<Self> {
         Ok(Self {
-            http: build_http_client(GITHUB_API_BASE)?,
+            http: build_http_client(&za_config::load_github_api_base()?)?,
             github_token: resolve_github_token(github_token_override)?,
             cache: matches!(cache_mode, CiCacheMode::ReadWrite)
                 .then(|| Mutex::new(CiApiCacheState::load())),
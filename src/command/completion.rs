@@ -193,6 +193,33 @@ fn uninstall_completion(shell: CompletionShell, path_override: Option<PathBuf>)
     Ok(0)
 }
 
+/// Installs another program's completion script with the same activation as `za completion install`.
+pub(crate) fn install_program_completion(
+    shell: ShellcompShell,
+    program_name: &str,
+    script: &[u8],
+) -> Result<InstallReport> {
+    shellcomp::install(InstallRequest {
+        shell,
+        program_name,
+        script,
+        path_override: None,
+    })
+    .with_context(|| format!("install {program_name} completion"))
+}
+
+pub(crate) fn uninstall_program_completion(
+    shell: ShellcompShell,
+    program_name: &str,
+) -> Result<RemoveReport> {
+    shellcomp::uninstall(UninstallRequest {
+        shell,
+        program_name,
+        path_override: None,
+    })
+    .with_context(|| format!("uninstall {program_name} completion"))
+}
+
 fn render_completion(shell: CompletionShell) -> Result<Vec<u8>> {
     let generator_shell: shellcomp::clap_complete::Shell = shell.into();
     shellcomp::render_clap_completion::<crate::cli::Cli>(generator_shell, PROGRAM_NAME)
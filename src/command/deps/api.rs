@@ -176,8 +176,8 @@ impl ApiClient {
     pub(super) fn new(github_token_override: Option<String>) -> Result<Self> {
         let crates_http =
             build_http_client("https://crates.io").context("build crates.io HTTP client")?;
-        let github_http =
-            build_http_client("https://api.github.com").context("build GitHub HTTP client")?;
+        let github_http = build_http_client(&za_config::load_github_api_base()?)
+            .context("build GitHub HTTP client")?;
         let github_token = resolve_github_token(github_token_override)?;
         Ok(Self {
             crates_http,
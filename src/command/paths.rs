@@ -1,11 +1,18 @@
 //! Shared filesystem path helpers for za-managed state.
 
 use anyhow::{Result, anyhow};
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 pub(crate) const GLOBAL_TOOL_STORE_DIR: &str = "/var/lib/za/tools/store";
 pub(crate) const GLOBAL_TOOL_CURRENT_DIR: &str = "/var/lib/za/tools/current";
 pub(crate) const GLOBAL_BIN_DIR: &str = "/usr/local/bin";
+pub(crate) const GLOBAL_DOCKER_CLI_PLUGINS_DIR: &str = "/usr/local/lib/docker/cli-plugins";
+/// Repository-level tool declaration file that marks a project root.
+pub(crate) const PROJECT_TOOLS_FILE: &str = "za.tools.toml";
+const PROJECT_STATE_DIR: &str = ".za";
 
 pub(crate) fn data_home() -> Result<PathBuf> {
     if let Some(path) = env::var_os("XDG_DATA_HOME").map(PathBuf::from) {
@@ -30,6 +37,18 @@ pub(crate) fn state_home() -> Result<PathBuf> {
     Ok(home_dir()?.join(".local/state"))
 }
 
+pub(crate) fn cache_home() -> Result<PathBuf> {
+    if let Some(path) = env::var_os("XDG_CACHE_HOME").map(PathBuf::from) {
+        return Ok(path);
+    }
+    Ok(home_dir()?.join(".cache"))
+}
+
+/// Content-addressed release asset cache shared by every tool scope of the current user.
+pub(crate) fn tool_artifact_cache_dir() -> Result<PathBuf> {
+    Ok(cache_home()?.join("za/tools/artifacts"))
+}
+
 pub(crate) fn user_tool_store_dir() -> Result<PathBuf> {
     Ok(data_home()?.join("za/tools/store"))
 }
@@ -38,6 +57,32 @@ pub(crate) fn user_tool_current_dir() -> Result<PathBuf> {
     Ok(state_home()?.join("za/tools/current"))
 }
 
+/// Version store shared by every project scope; projects only keep activation state locally.
+pub(crate) fn shared_tool_store_dir() -> Result<PathBuf> {
+    Ok(data_home()?.join("za/tools/shared-store"))
+}
+
+/// Tool history of project scopes, kept per user so it never lands in a repository.
+pub(crate) fn project_tool_history_dir() -> Result<PathBuf> {
+    Ok(state_home()?.join("za/tools/project-history"))
+}
+
+/// Nearest ancestor of `start` (inclusive) that contains a `za.tools.toml`.
+pub(crate) fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(PROJECT_TOOLS_FILE).is_file())
+        .map(Path::to_path_buf)
+}
+
+pub(crate) fn project_tool_current_dir(root: &Path) -> PathBuf {
+    root.join(PROJECT_STATE_DIR).join("tools/current")
+}
+
+pub(crate) fn project_bin_dir(root: &Path) -> PathBuf {
+    root.join(PROJECT_STATE_DIR).join("bin")
+}
+
 pub(crate) fn user_bin_dir() -> Result<PathBuf> {
     user_bin_dir_from_env(
         env::var_os("HOME").map(PathBuf::from),
@@ -53,6 +98,27 @@ fn user_bin_dir_from_env(home: Option<PathBuf>, za_bin_dir: Option<PathBuf>) ->
         .ok_or_else(|| anyhow!("cannot resolve user bin directory: set `HOME` or `ZA_BIN_DIR`"))
 }
 
+/// Docker reads user CLI plugins from `$DOCKER_CONFIG/cli-plugins`, defaulting to `~/.docker`.
+pub(crate) fn user_docker_cli_plugins_dir() -> Result<PathBuf> {
+    user_docker_cli_plugins_dir_from_env(
+        env::var_os("HOME").map(PathBuf::from),
+        env::var_os("DOCKER_CONFIG").map(PathBuf::from),
+    )
+}
+
+fn user_docker_cli_plugins_dir_from_env(
+    home: Option<PathBuf>,
+    docker_config: Option<PathBuf>,
+) -> Result<PathBuf> {
+    if let Some(path) = docker_config.filter(|path| !path.as_os_str().is_empty()) {
+        return Ok(path.join("cli-plugins"));
+    }
+    home.map(|home| home.join(".docker/cli-plugins"))
+        .ok_or_else(|| {
+            anyhow!("cannot resolve Docker CLI plugin directory: set `HOME` or `DOCKER_CONFIG`")
+        })
+}
+
 pub(crate) fn jetbrains_agent_shim_bin_dir() -> Result<PathBuf> {
     user_bin_dir()
 }
@@ -63,8 +129,25 @@ pub(crate) fn legacy_jetbrains_agent_shim_bin_dir() -> Result<PathBuf> {
 
 #[cfg(test)]
 mod tests {
-    use super::user_bin_dir_from_env;
-    use std::path::PathBuf;
+    use super::{
+        PROJECT_TOOLS_FILE, find_project_root, user_bin_dir_from_env,
+        user_docker_cli_plugins_dir_from_env,
+    };
+    use std::{fs, path::PathBuf};
+
+    #[test]
+    fn docker_cli_plugins_dir_follows_docker_config() {
+        let home = Some(PathBuf::from("/home/alice"));
+        assert_eq!(
+            user_docker_cli_plugins_dir_from_env(home.clone(), None).expect("default dir"),
+            PathBuf::from("/home/alice/.docker/cli-plugins")
+        );
+        assert_eq!(
+            user_docker_cli_plugins_dir_from_env(home, Some(PathBuf::from("/etc/docker-alice")))
+                .expect("DOCKER_CONFIG dir"),
+            PathBuf::from("/etc/docker-alice/cli-plugins")
+        );
+    }
 
     #[test]
     fn user_bin_dir_defaults_to_local_bin() {
@@ -92,4 +175,25 @@ mod tests {
 
         assert_eq!(resolved, PathBuf::from("/opt/za/bin"));
     }
+
+    #[test]
+    fn find_project_root_walks_up_to_the_tools_file() {
+        let root = std::env::temp_dir().join(format!(
+            "za-test-project-root-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("time")
+                .as_nanos()
+        ));
+        let nested = root.join("crates/app/src");
+        fs::create_dir_all(&nested).expect("create nested dir");
+        assert_eq!(find_project_root(&nested), None);
+
+        fs::write(root.join(PROJECT_TOOLS_FILE), "tools = [\"rg\"]\n").expect("write tools");
+        assert_eq!(find_project_root(&nested), Some(root.clone()));
+        assert_eq!(find_project_root(&root), Some(root.clone()));
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
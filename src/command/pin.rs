@@ -17,7 +17,6 @@ const HTTP_TIMEOUT_SECS: u64 = 30;
 const HTTP_USER_AGENT: &str = "za-pin/0.1";
 const NPM_REGISTRY_BASE: &str = "https://registry.npmjs.org";
 const CRATES_API_BASE: &str = "https://crates.io";
-const GITHUB_API_BASE: &str = "https://api.github.com";
 const GITHUB_API_VERSION: &str = "2022-11-28";
 const DEFAULT_NPM_TAG: &str = "latest";
 
@@ -234,7 +233,7 @@ fn resolve_action_pin(spec: &ActionSpec, github_token: Option<String>) -> Result
     }
 
     let token = resolve_github_token(github_token)?;
-    let client = build_http_client(GITHUB_API_BASE)?;
+    let client = build_http_client(&za_config::load_github_api_base()?)?;
     let ref_path = percent_encode_path_segment(&spec.ref_name);
     let path = format!(
         "/repos/{}/{}/commits/{}",
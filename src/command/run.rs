@@ -12,10 +12,16 @@ use std::{
 use std::os::unix::fs::PermissionsExt;
 
 const IDE_AGENT_SHIM_MANAGED_MARKER_PREFIX: &str = "# za-managed: ide-agent-shim";
+const RUN_PIN_FALLBACK_ENV: &str = "ZA_RUN_PIN_FALLBACK";
 
 pub fn run(tool: &str, args: &[String]) -> Result<i32> {
-    let canonical = crate::command::tool::canonical_tool_name(tool);
-    let executable = resolve_executable_path(&canonical)?;
+    let (canonical, executable) = if tool.contains('@') && !has_path_component(tool) {
+        crate::command::tool::materialize_tool_version(tool)?
+    } else {
+        let canonical = crate::command::tool::canonical_tool_name(tool);
+        let executable = resolve_executable_path(&canonical)?;
+        (canonical, executable)
+    };
 
     let mut cmd = Command::new(&executable);
     cmd.args(args)
@@ -48,6 +54,9 @@ pub(crate) fn resolve_executable_path(name: &str) -> Result<PathBuf> {
         bail!("`za run` expected an executable path or tool name, but `{name}` is not executable");
     }
 
+    if let Some(path) = resolve_project_managed_active(name)? {
+        return Ok(path);
+    }
     if let Some(path) = resolve_user_managed_active(name)? {
         return Ok(path);
     }
@@ -67,6 +76,61 @@ fn has_path_component(name: &str) -> bool {
     Path::new(name).components().count() > 1
 }
 
+/// Tools pinned by the enclosing project's `za.tools.toml` win over user and global installs.
+///
+/// A pinned version that is not installed fails the run so the project never silently runs a
+/// different version; setting `ZA_RUN_PIN_FALLBACK=1` opts into using the user or global tool
+/// with a warning instead.
+fn resolve_project_managed_active(name: &str) -> Result<Option<PathBuf>> {
+    let Ok(cwd) = env::current_dir() else {
+        return Ok(None);
+    };
+    let Some(root) = crate::command::paths::find_project_root(&cwd) else {
+        return Ok(None);
+    };
+    let Ok(store_dir) = crate::command::paths::shared_tool_store_dir() else {
+        return Ok(None);
+    };
+    let allow_fallback = env::var(RUN_PIN_FALLBACK_ENV).is_ok_and(|value| value.trim() == "1");
+    resolve_project_pinned(name, &root, &store_dir, allow_fallback)
+}
+
+fn resolve_project_pinned(
+    name: &str,
+    root: &Path,
+    store_dir: &Path,
+    allow_fallback: bool,
+) -> Result<Option<PathBuf>> {
+    let current_dir = crate::command::paths::project_tool_current_dir(root);
+    let Some(version) = read_active_version(name, &current_dir)? else {
+        return Ok(None);
+    };
+    let executable = crate::command::tool::managed_executable_path(name, &version, store_dir);
+    if is_executable_file(&executable) {
+        return Ok(Some(executable));
+    }
+
+    let fallback = if allow_fallback {
+        match resolve_user_managed_active(name)? {
+            Some(path) => Some(path),
+            None => resolve_global_managed_active(name)?,
+        }
+    } else {
+        None
+    };
+    let Some(fallback) = fallback else {
+        bail!(
+            "project pins `{name}` version `{version}`, which is not installed; run `za tool sync` in {}",
+            root.display()
+        );
+    };
+    eprintln!(
+        "warning: project pins `{name}` version `{version}`, which is not installed; using {} because {RUN_PIN_FALLBACK_ENV}=1 (run `za tool sync` to install the pinned version)",
+        fallback.display()
+    );
+    Ok(Some(fallback))
+}
+
 fn resolve_user_managed_active(name: &str) -> Result<Option<PathBuf>> {
     let Ok(store_dir) = crate::command::paths::user_tool_store_dir() else {
         return Ok(None);
@@ -90,6 +154,23 @@ fn resolve_managed_active(
     store_dir: &Path,
     current_dir: &Path,
 ) -> Result<Option<PathBuf>> {
+    let Some(version) = read_active_version(name, current_dir)? else {
+        return Ok(None);
+    };
+
+    let executable = crate::command::tool::managed_executable_path(name, &version, store_dir);
+    if !is_executable_file(&executable) {
+        bail!(
+            "active `{name}` version `{version}` points to missing executable `{}`; repair with `za tool update {name}`",
+            executable.display()
+        );
+    }
+    Ok(Some(executable))
+}
+
+/// Reads the active version marker, rejecting anything that would escape the tool's store
+/// directory once joined into a path.
+fn read_active_version(name: &str, current_dir: &Path) -> Result<Option<String>> {
     let current_file = current_dir.join(name);
     if !current_file.exists() {
         return Ok(None);
@@ -101,15 +182,13 @@ fn resolve_managed_active(
     if version.is_empty() {
         return Ok(None);
     }
-
-    let executable = crate::command::tool::managed_executable_path(name, version, store_dir);
-    if !is_executable_file(&executable) {
-        bail!(
-            "active `{name}` version `{version}` points to missing executable `{}`; repair with `za tool update {name}`",
-            executable.display()
-        );
-    }
-    Ok(Some(executable))
+    crate::command::tool::validate_version_segment(version).with_context(|| {
+        format!(
+            "active version file {} names an invalid `{name}` version",
+            current_file.display()
+        )
+    })?;
+    Ok(Some(version.to_string()))
 }
 
 fn find_in_path(name: &str) -> Option<PathBuf> {
@@ -223,7 +302,10 @@ fn non_empty_env_value<'a>(vars: &'a HashMap<String, String>, key: &str) -> Opti
 
 #[cfg(test)]
 mod tests {
-    use super::{normalized_proxy_env, resolve_executable_path, resolve_managed_active};
+    use super::{
+        normalized_proxy_env, resolve_executable_path, resolve_managed_active,
+        resolve_project_pinned,
+    };
     use crate::command::za_config::RunProxyOverrides;
     use anyhow::Result;
     use std::{
@@ -419,6 +501,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn resolve_managed_active_rejects_versions_escaping_the_store() -> Result<()> {
+        let root = temp_executable_path("managed-traversal-root");
+        let store = root.join("store");
+        let current = root.join("current");
+        fs::create_dir_all(&store)?;
+        fs::create_dir_all(&current)?;
+
+        for version in ["../../../usr", "..", "1.0/../../x", "1.0\\..\\x"] {
+            fs::write(current.join("rg"), format!("{version}\n"))?;
+            let err = resolve_managed_active("rg", &store, &current)
+                .expect_err("traversing version must be rejected");
+            assert!(
+                format!("{err:#}").contains("invalid `rg` version"),
+                "unexpected error for `{version}`: {err:#}"
+            );
+        }
+
+        let _ = fs::remove_dir_all(root);
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_project_pinned_fails_when_pin_is_not_installed() -> Result<()> {
+        let root = temp_executable_path("project-pin-root");
+        let store = root.join("store");
+        let current = crate::command::paths::project_tool_current_dir(&root);
+        fs::create_dir_all(&store)?;
+        fs::create_dir_all(&current)?;
+        fs::write(current.join("rg"), "14.1.1\n")?;
+
+        let err = resolve_project_pinned("rg", &root, &store, false)
+            .expect_err("missing pin must not fall back silently");
+        assert!(err.to_string().contains("run `za tool sync`"), "{err:#}");
+        assert_eq!(resolve_project_pinned("fd", &root, &store, false)?, None);
+
+        let _ = fs::remove_dir_all(root);
+        Ok(())
+    }
+
     fn temp_executable_path(name: &str) -> PathBuf {
         let unique = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -1,12 +1,23 @@
 //! Tool manager for versioned executables.
 
+mod adhoc;
+mod admin;
 mod batch;
+mod cache;
+mod completions;
 mod doctor;
+mod export;
+mod history;
 mod integrations;
 mod listing;
+mod lockfile;
 mod policy;
+mod registry;
+mod sbom;
 mod source;
 mod state;
+mod target;
+mod verify;
 
 use anyhow::{Context, Result, anyhow, bail};
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
@@ -16,6 +27,7 @@ use reqx::{
     blocking::{Client, ClientBuilder},
     prelude::RetryPolicy,
 };
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 use signal_hook::{consts::signal::SIGINT, flag as signal_flag};
 #[cfg(unix)]
@@ -36,7 +48,7 @@ use std::{
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use self::doctor::run_doctor;
+use self::doctor::{DoctorFixMode, run_doctor};
 use self::listing::{
     LatestCheck, LatestResolutionMode, UnmanagedBinary, list_installed, list_outdated,
     resolve_latest_checks_for_names_with_mode, show_catalog, show_tool,
@@ -46,11 +58,12 @@ use self::listing::{
     latest_check_progress_message, list_update_status, tool_update_cache_entry_is_fresh,
 };
 use self::policy::{
-    GithubReleasePolicy, PackagePolicy, ToolLayout, ToolPolicy,
-    canonical_tool_name as canonical_tool_name_impl, find_tool_policy, supported_tool_names_csv,
-    tool_policies,
+    EntryPlacement, ExposedEntry, PackagePolicy, ReleasePolicy, ToolLayout, ToolPolicy,
+    canonical_tool_name as canonical_tool_name_impl, ensure_tool_registry_loaded, find_tool_policy,
+    supported_tool_names_csv, tool_policies,
 };
 use self::source::{resolve_install_source, resolve_requested_version};
+use self::target::ToolTarget;
 use self::{batch::*, integrations::*, state::*};
 use crate::{
     cli::ToolCommands,
@@ -58,7 +71,6 @@ use crate::{
 };
 
 const HTTP_TIMEOUT_SECS: u64 = 300;
-const GITHUB_API_BASE: &str = "https://api.github.com";
 const HTTP_USER_AGENT: &str = "za-tool-manager/0.1";
 const MANIFEST_FILE: &str = "manifest.json";
 const LOCK_FILE: &str = ".tool.lock";
@@ -71,12 +83,7 @@ const SOURCE_KIND_ADOPTED: &str = "adopted";
 const SOURCE_KIND_SYNTHESIZED: &str = "synthesized";
 const IDE_TERMINAL_BASH_HELPER_START_MARKER: &str = "# >>> za ide-terminal (bash) >>>";
 const IDE_TERMINAL_BASH_HELPER_END_MARKER: &str = "# <<< za ide-terminal (bash) <<<";
-const STARSHIP_BASH_INIT_START_MARKER: &str = "# >>> za starship (bash) >>>";
-const STARSHIP_BASH_INIT_END_MARKER: &str = "# <<< za starship (bash) <<<";
-const BLESH_BASH_INIT_TOP_START_MARKER: &str = "# >>> za ble.sh (bash top) >>>";
-const BLESH_BASH_INIT_TOP_END_MARKER: &str = "# <<< za ble.sh (bash top) <<<";
 const BLESH_BASH_INIT_BOTTOM_START_MARKER: &str = "# >>> za ble.sh (bash bottom) >>>";
-const BLESH_BASH_INIT_BOTTOM_END_MARKER: &str = "# <<< za ble.sh (bash bottom) <<<";
 const PROXY_HINT: &str =
     "if your network requires a proxy, set HTTPS_PROXY/HTTP_PROXY (and optional NO_PROXY)";
 const TOOL_UPDATE_CACHE_SCHEMA_VERSION: u32 = 3;
@@ -108,10 +115,7 @@ static SIGNAL_HANDLER_REGISTRATION: LazyLock<Result<(), String>> = LazyLock::new
 });
 
 fn prepare_interruptible_tool_operation() -> Result<()> {
-    if let Err(err) = &*SIGNAL_HANDLER_REGISTRATION {
-        bail!("failed to initialize interrupt handlers: {err}");
-    }
-    INTERRUPT_REQUESTED.store(false, Ordering::SeqCst);
+    reset_interrupt_state()?;
     let removed = source::cleanup_stale_temp_dirs();
     if removed > 0 {
         eprintln!("🧹 Cleaned {removed} stale temp dir(s) from previous interrupted runs");
@@ -119,6 +123,14 @@ fn prepare_interruptible_tool_operation() -> Result<()> {
     Ok(())
 }
 
+fn reset_interrupt_state() -> Result<()> {
+    if let Err(err) = &*SIGNAL_HANDLER_REGISTRATION {
+        bail!("failed to initialize interrupt handlers: {err}");
+    }
+    INTERRUPT_REQUESTED.store(false, Ordering::SeqCst);
+    Ok(())
+}
+
 fn print_tool_stage(stage: &str, message: impl AsRef<str>) {
     let stage_label = format!("{stage:<8}");
     let styled_stage = style_tool_stage_token(stage, &stage_label);
@@ -146,6 +158,7 @@ fn tool_stage_icon(stage: &str) -> &'static str {
         "install" => "📥",
         "activate" => "✅",
         "prune" => "🧹",
+        "hold" => "⏳",
         "next" => "ℹ️",
         "done" => "✅",
         "fail" => "❌",
@@ -271,6 +284,9 @@ pub(super) fn ensure_not_interrupted() -> Result<()> {
 enum ToolScope {
     Global,
     User,
+    /// Activation state lives in the nearest repository with a `za.tools.toml`; versions are
+    /// kept in a store shared by all projects.
+    Project,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -278,11 +294,14 @@ pub enum ToolScopeRequest {
     Auto,
     Global,
     User,
+    Project,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub(super) enum ToolUpdateChannel {
     Stable,
+    #[serde(rename = "alpha")]
     CodexAlpha,
 }
 
@@ -295,6 +314,16 @@ impl ToolScopeRequest {
             (false, false) => Ok(Self::Auto),
         }
     }
+
+    pub fn from_tool_flags(user: bool, global: bool, project: bool) -> Result<Self> {
+        if project {
+            if user || global {
+                bail!("`--project` cannot be combined with `--user` or `--global`");
+            }
+            return Ok(Self::Project);
+        }
+        Self::from_flags(user, global)
+    }
 }
 
 impl ToolScope {
@@ -302,6 +331,7 @@ impl ToolScope {
         match request {
             ToolScopeRequest::Global => Self::Global,
             ToolScopeRequest::User => Self::User,
+            ToolScopeRequest::Project => Self::Project,
             ToolScopeRequest::Auto => default_tool_scope(),
         }
     }
@@ -310,6 +340,7 @@ impl ToolScope {
         match self {
             Self::Global => "global",
             Self::User => "user",
+            Self::Project => "project",
         }
     }
 }
@@ -416,7 +447,14 @@ fn run_ls_command(
         return show_catalog(json);
     }
     if outdated {
-        return list_outdated(home, tools, json, fail_on_updates, fail_on_check_errors);
+        return list_outdated(
+            home,
+            tools,
+            json,
+            false,
+            fail_on_updates,
+            fail_on_check_errors,
+        );
     }
     if !tools.is_empty() {
         bail!("`za tool ls` does not accept tool names; use `za tool show <tool>`");
@@ -424,11 +462,25 @@ fn run_ls_command(
     list_installed(home, json)
 }
 
+fn resolve_tool_target(raw: Option<&str>) -> Result<ToolTarget> {
+    raw.map_or_else(|| Ok(ToolTarget::host()), ToolTarget::parse)
+}
+
 pub fn run(cmd: ToolCommands, scope_request: ToolScopeRequest) -> Result<i32> {
-    prepare_interruptible_tool_operation()?;
+    // `doctor --fix` sweeps leftovers itself so it can report (or only preview) them.
+    let sweep_leftovers = !matches!(cmd, ToolCommands::Doctor { fix: true, .. });
+    if sweep_leftovers {
+        prepare_interruptible_tool_operation()?;
+    } else {
+        reset_interrupt_state()?;
+    }
+    ensure_tool_registry_loaded()?;
 
     let home = ToolHome::detect(scope_request)?;
-    cleanup_legacy_current_dir_artifacts(&home)?;
+    if sweep_leftovers {
+        cleanup_legacy_current_dir_artifacts(&home)?;
+    }
+    adhoc::register_installed_adhoc_tools(&home)?;
 
     match cmd {
         ToolCommands::Ls {
@@ -461,30 +513,35 @@ pub fn run(cmd: ToolCommands, scope_request: ToolScopeRequest) -> Result<i32> {
             adopt,
             dry_run,
             verbose,
+            target,
+            require_digest,
         } => {
+            let target = resolve_tool_target(target.as_deref())?;
             if dry_run {
-                install_tools(
-                    &home,
-                    &tools,
-                    version.as_deref(),
+                install_tools(ToolInstallRun {
+                    home: &home,
+                    tools: &tools,
+                    version: version.as_deref(),
                     adopt,
-                    ToolAction::Install,
-                    true,
+                    require_digest,
+                    target,
+                    dry_run: true,
                     verbose,
-                )?;
+                })?;
                 Ok(0)
             } else {
                 let home_for_action = home.clone();
                 run_mutating_tool_command(&home, move || {
-                    install_tools(
-                        &home_for_action,
-                        &tools,
-                        version.as_deref(),
+                    install_tools(ToolInstallRun {
+                        home: &home_for_action,
+                        tools: &tools,
+                        version: version.as_deref(),
                         adopt,
-                        ToolAction::Install,
-                        false,
+                        require_digest,
+                        target,
+                        dry_run: false,
                         verbose,
-                    )
+                    })
                 })
             }
         }
@@ -518,18 +575,47 @@ pub fn run(cmd: ToolCommands, scope_request: ToolScopeRequest) -> Result<i32> {
             file,
             dry_run,
             verbose,
+            target,
+            locked,
+            prune,
         } => {
+            let target = resolve_tool_target(target.as_deref())?;
+            let request = |dry_run| ToolSyncRequest {
+                file: &file,
+                target,
+                locked,
+                prune,
+                dry_run,
+                verbose,
+            };
             if dry_run {
-                sync_manifest(&home, &file, true, verbose)?;
+                sync_manifest(&home, request(true))?;
                 Ok(0)
             } else {
                 let home_for_action = home.clone();
                 run_mutating_tool_command(&home, move || {
-                    sync_manifest(&home_for_action, &file, false, verbose)
+                    sync_manifest(&home_for_action, request(false))
                 })
             }
         }
-        ToolCommands::Doctor { tools, json } => run_doctor(&home, &tools, json),
+        ToolCommands::Doctor {
+            tools,
+            json,
+            fix,
+            dry_run,
+        } => {
+            let fix = fix.then_some(if dry_run {
+                DoctorFixMode::DryRun
+            } else {
+                DoctorFixMode::Apply
+            });
+            if fix == Some(DoctorFixMode::Apply) {
+                let _lock = ensure_tool_home_ready(&home)?;
+                run_doctor(&home, &tools, json, fix)
+            } else {
+                run_doctor(&home, &tools, json, fix)
+            }
+        }
         ToolCommands::Uninstall { tool, version } => {
             let home_for_action = home.clone();
             run_mutating_tool_command(&home, move || {
@@ -547,26 +633,145 @@ pub fn run(cmd: ToolCommands, scope_request: ToolScopeRequest) -> Result<i32> {
         ToolCommands::Outdated {
             tools,
             json,
+            changelog,
+            fail_on_updates,
+            fail_on_check_errors,
+        } => list_outdated(
+            &home,
+            &tools,
+            json,
+            changelog,
             fail_on_updates,
             fail_on_check_errors,
-        } => list_outdated(&home, &tools, json, fail_on_updates, fail_on_check_errors),
+        ),
+        ToolCommands::Rollback { tool, to } => {
+            let home_for_action = home.clone();
+            run_mutating_tool_command(&home, move || {
+                rollback_tool(&home_for_action, &tool, to.as_deref())
+            })
+        }
+        ToolCommands::History { tool, days, json } => {
+            history::run_history(&home, tool.as_deref(), days, json)
+        }
+        ToolCommands::Cache { cmd } => cache::run_cache_command(cmd),
+        ToolCommands::Export {
+            tools,
+            format,
+            output,
+        } => export::run_export(&home, &tools, format, output.as_deref()),
+        ToolCommands::Sbom { format, output } => sbom::run_sbom(&home, format, output.as_deref()),
         ToolCommands::Adopt { tool } => {
             let home_for_action = home.clone();
             run_mutating_tool_command(&home, move || {
-                install_tools(
-                    &home_for_action,
-                    std::slice::from_ref(&tool),
-                    None,
-                    true,
-                    ToolAction::Install,
-                    false,
-                    false,
-                )
+                install_tools(ToolInstallRun {
+                    home: &home_for_action,
+                    tools: std::slice::from_ref(&tool),
+                    version: None,
+                    adopt: true,
+                    require_digest: false,
+                    target: ToolTarget::host(),
+                    dry_run: false,
+                    verbose: false,
+                })
             })
         }
     }
 }
 
+/// Resolves `name@version` for `za run` to an executable in a tool store, fetching it into the
+/// default scope's store when no store has it yet. Active versions and bin entries are untouched.
+pub(crate) fn materialize_tool_version(spec: &str) -> Result<(String, PathBuf)> {
+    prepare_interruptible_tool_operation()?;
+    ensure_tool_registry_loaded()?;
+
+    let mut requested = ToolSpec::parse(spec)?;
+    if requested.version.is_none() && requested.requirement.is_none() {
+        bail!("invalid tool spec `{spec}`: expected `name@version`");
+    }
+    requested.name = canonical_supported_tool_name(&requested.name)?;
+    let store_dirs = tool_store_dirs();
+    if let Some(version) = requested.version.as_deref()
+        && let Some(path) = find_stored_executable(&store_dirs, &requested.name, version)
+    {
+        ensure_stored_version_allowed(&requested.name, version, &path)?;
+        return Ok((requested.name, path));
+    }
+
+    let home = ToolHome::detect(ToolScopeRequest::Auto)?;
+    let options = InstallOptions::install(za_config::ProxyScope::Tool)
+        .with_prune(false)
+        .emit_stages(false)
+        .download_display(source::DownloadDisplay::Compact);
+    let plan = plan_install(&home, requested, options)?;
+    let tool = plan.tool.clone();
+    if let Some(path) = find_stored_executable(&store_dirs, &tool.name, &tool.version) {
+        ensure_stored_version_allowed(&tool.name, &tool.version, &path)?;
+        return Ok((tool.name, path));
+    }
+
+    let _lock = ensure_tool_home_ready(&home)?;
+    materialize_install_plan(&home, &plan, options, None)?;
+    eprintln!(
+        "📦 Fetched {} into the {} store; the active version is unchanged",
+        tool.image(),
+        home.scope.label()
+    );
+    let path = managed_executable_path(&tool.name, &tool.version, &home.store_dir);
+    Ok((tool.name, path))
+}
+
+/// A hit in the global store skips `plan_install`, so the admin policy is checked here instead.
+fn ensure_stored_version_allowed(name: &str, version: &str, path: &Path) -> Result<()> {
+    if !path.starts_with(paths::GLOBAL_TOOL_STORE_DIR) {
+        return Ok(());
+    }
+    let home = ToolHome::for_scope(ToolScope::Global)?;
+    let Some(admin_policy) = admin::load_admin_policy(&home)? else {
+        return Ok(());
+    };
+    let tool = ToolRef {
+        name: name.to_string(),
+        version: normalize_version(version),
+    };
+    admin_policy.check_installed(&home, &tool)
+}
+
+/// Stores searched for an already downloaded version, nearest scope first.
+fn tool_store_dirs() -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    if let Ok(dir) = paths::shared_tool_store_dir() {
+        out.push(dir);
+    }
+    if let Ok(dir) = paths::user_tool_store_dir() {
+        out.push(dir);
+    }
+    out.push(PathBuf::from(paths::GLOBAL_TOOL_STORE_DIR));
+    out
+}
+
+fn find_stored_executable(store_dirs: &[PathBuf], name: &str, version: &str) -> Option<PathBuf> {
+    let version = normalize_version(version);
+    store_dirs
+        .iter()
+        .filter(|store_dir| stored_version_runs_on_host(store_dir, name, &version))
+        .map(|store_dir| managed_executable_path(name, &version, store_dir))
+        .find(|path| path.is_file())
+}
+
+/// Entries without a recorded target predate `--target` and were always host builds.
+fn stored_version_runs_on_host(store_dir: &Path, name: &str, version: &str) -> bool {
+    let manifest_path = store_dir.join(name).join(version).join(MANIFEST_FILE);
+    let Ok(raw) = fs::read_to_string(&manifest_path) else {
+        return true;
+    };
+    match serde_json::from_str::<ToolManifest>(&raw) {
+        Ok(manifest) => manifest.target.as_deref().is_none_or(|target| {
+            ToolTarget::parse(target).is_ok_and(|target| target.runs_on_host())
+        }),
+        Err(_) => false,
+    }
+}
+
 pub fn update_self(
     scope_request: ToolScopeRequest,
     check: bool,
@@ -610,7 +815,9 @@ pub fn update_self(
     let installed = install(
         &home,
         ToolSpec::parse(&target_spec)?,
-        InstallOptions::update(za_config::ProxyScope::Update).with_prune(false),
+        InstallOptions::update(za_config::ProxyScope::Update)
+            .with_prune(false)
+            .record_history(false),
     )?;
     if let Err(err) = verify_self_update(&home, &installed.tool) {
         let rollback_res =
@@ -629,7 +836,21 @@ pub fn update_self(
     if let Some(path) = backup.as_ref() {
         let _ = fs::remove_file(path);
     }
-    let removed = prune_non_active_versions(&home, &installed.tool)?;
+    if installed.outcome != InstallOutcome::Unchanged {
+        history::record_tool_event(
+            &home,
+            history::ToolHistoryEvent::new(
+                history::ToolHistoryAction::SelfUpdate,
+                "za",
+                Some(&installed.tool.version),
+            )
+            .previous_version(previous_active.as_deref()),
+        );
+    }
+    let removed = match za_config::load_tool_retain_versions()? {
+        Some(retain) => prune_non_active_versions(&home, &installed.tool, retain)?,
+        None => Vec::new(),
+    };
     if !removed.is_empty() {
         print_tool_stage(
             "prune",
@@ -800,6 +1021,8 @@ struct InstallOptions {
     emit_stages: bool,
     emit_plan_stage: bool,
     download_display: source::DownloadDisplay,
+    target: ToolTarget,
+    record_history: bool,
 }
 
 impl InstallOptions {
@@ -813,6 +1036,8 @@ impl InstallOptions {
             emit_stages: true,
             emit_plan_stage: false,
             download_display: source::DownloadDisplay::Detailed,
+            target: ToolTarget::host(),
+            record_history: true,
         }
     }
 
@@ -826,6 +1051,8 @@ impl InstallOptions {
             emit_stages: true,
             emit_plan_stage: false,
             download_display: source::DownloadDisplay::Detailed,
+            target: ToolTarget::host(),
+            record_history: true,
         }
     }
 
@@ -839,6 +1066,8 @@ impl InstallOptions {
             emit_stages: true,
             emit_plan_stage: false,
             download_display: source::DownloadDisplay::Detailed,
+            target: ToolTarget::host(),
+            record_history: true,
         }
     }
 
@@ -866,6 +1095,16 @@ impl InstallOptions {
         self.download_display = download_display;
         self
     }
+
+    fn target(mut self, target: ToolTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    fn record_history(mut self, record_history: bool) -> Self {
+        self.record_history = record_history;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -890,6 +1129,9 @@ struct InstallPlan {
     already_installed: bool,
     planned_outcome: InstallOutcome,
     current_matches_target: bool,
+    requirement: Option<VersionReq>,
+    locked: Option<ReleaseAsset>,
+    integrations: bool,
 }
 
 #[derive(Debug)]
@@ -904,6 +1146,9 @@ struct PullSource {
     artifact: PullArtifactKind,
     path: PathBuf,
     resolved_by: String,
+    asset: Option<ReleaseAsset>,
+    /// Downloaded without any digest or signature check.
+    unverified: bool,
     cleanup_root: Option<PathBuf>,
 }
 
@@ -920,9 +1165,21 @@ impl PullSource {
             artifact,
             path,
             resolved_by,
+            asset: None,
+            unverified: false,
             cleanup_root: Some(cleanup_root),
         }
     }
+
+    fn with_asset(mut self, asset: ReleaseAsset) -> Self {
+        self.asset = Some(asset);
+        self
+    }
+
+    fn with_unverified(mut self, unverified: bool) -> Self {
+        self.unverified = unverified;
+        self
+    }
 }
 
 impl Drop for PullSource {
@@ -938,6 +1195,15 @@ impl Drop for PullSource {
 struct InstallSource {
     kind: &'static str,
     detail: String,
+    asset: Option<ReleaseAsset>,
+    unverified: bool,
+}
+
+/// Downloaded release asset URL and the SHA-256 of the bytes fetched from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReleaseAsset {
+    url: String,
+    sha256: String,
 }
 
 #[derive(Debug, Clone)]
@@ -956,11 +1222,42 @@ struct ToolManifest {
     source_detail: String,
     sha256: String,
     size_bytes: u64,
-}
-
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    requirement: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    asset_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    asset_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    adhoc: Option<adhoc::AdhocToolSource>,
+    /// The release asset was installed without a digest or signature check.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    unverified: bool,
+}
+
+/// `tools` is either a list of specs or a table keyed by tool name.
 #[derive(Debug, Deserialize)]
 struct ToolSyncManifest {
-    tools: Vec<String>,
+    tools: toml::Value,
+}
+
+/// Table form of one sync manifest entry, e.g. `just = { version = "^1.30", hold = true }`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolSyncManifestTable {
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    channel: Option<ToolUpdateChannel>,
+    /// Keep the active version once installed instead of following `version` or `channel`.
+    #[serde(default)]
+    hold: bool,
+    /// Run post-install shell integrations: the starship/ble.sh bashrc blocks, declared shell
+    /// init snippets and shell completions.
+    #[serde(default)]
+    integrations: bool,
 }
 
 #[derive(Debug)]
@@ -978,6 +1275,13 @@ struct ToolRef {
 struct ToolSpec {
     name: String,
     version: Option<String>,
+    /// Semver range such as `^1.30`; the newest stable release inside it is installed.
+    requirement: Option<VersionReq>,
+    locked: Option<ReleaseAsset>,
+    /// Whether post-activation shell integrations run for this tool.
+    integrations: bool,
+    /// Target recorded by an earlier install; overrides the batch target when set.
+    target: Option<ToolTarget>,
 }
 
 impl ToolSpec {
@@ -987,16 +1291,40 @@ impl ToolSpec {
             bail!("tool name must not be empty");
         }
         validate_name(trimmed_name)?;
-        let version = version
-            .map(str::trim)
-            .filter(|v| !v.is_empty())
-            .map(normalize_version);
+        let (version, requirement) = match version.map(str::trim).filter(|v| !v.is_empty()) {
+            Some(raw) if is_version_requirement(raw) => {
+                let requirement = VersionReq::parse(raw).with_context(|| {
+                    format!("invalid version requirement `{raw}` for `{trimmed_name}`")
+                })?;
+                (None, Some(requirement))
+            }
+            Some(raw) => {
+                let version = normalize_version(raw);
+                validate_version_segment(&version)
+                    .with_context(|| format!("invalid version for `{trimmed_name}`"))?;
+                (Some(version), None)
+            }
+            None => (None, None),
+        };
         Ok(Self {
             name: trimmed_name.to_string(),
             version,
+            requirement,
+            locked: None,
+            integrations: true,
+            target: None,
         })
     }
 
+    /// Spec text as written back to sync manifests and lockfiles.
+    fn display_spec(&self) -> String {
+        match (&self.version, &self.requirement) {
+            (Some(version), _) => format!("{}:{version}", self.name),
+            (None, Some(requirement)) => format!("{}:{requirement}", self.name),
+            (None, None) => self.name.clone(),
+        }
+    }
+
     fn parse(input: &str) -> Result<Self> {
         let trimmed = input.trim();
         if trimmed.is_empty() {
@@ -1050,6 +1378,12 @@ impl ToolRef {
     }
 }
 
+/// Treats operator-prefixed, wildcard and comma-separated versions as semver ranges; anything
+/// else stays an exact version.
+fn is_version_requirement(raw: &str) -> bool {
+    raw.starts_with(['^', '~', '<', '>', '=', '*']) || raw.contains([',', '*'])
+}
+
 fn validate_name(name: &str) -> Result<()> {
     if name.trim().is_empty() {
         bail!("tool name must not be empty");
@@ -1066,6 +1400,26 @@ fn validate_name(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Versions name a store directory, so they must stay a single plain path segment.
+pub(crate) fn validate_version_segment(version: &str) -> Result<()> {
+    if version.is_empty() {
+        bail!("version must not be empty");
+    }
+    if version.contains('/') || version.contains('\\') {
+        bail!("version `{version}` must not contain path separators");
+    }
+    if version == "." || version == ".." {
+        bail!("version `{version}` is not a valid version");
+    }
+    if !version
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+    {
+        bail!("version `{version}` contains unsupported characters");
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 struct ToolHome {
     scope: ToolScope,
@@ -1085,6 +1439,7 @@ impl ToolHome {
             ToolScopeRequest::Auto => detect_current_za_scope().unwrap_or_else(default_tool_scope),
             ToolScopeRequest::Global => ToolScope::Global,
             ToolScopeRequest::User => ToolScope::User,
+            ToolScopeRequest::Project => bail!("`za update` does not support project scope"),
         };
         Self::for_scope(scope)
     }
@@ -1103,9 +1458,46 @@ impl ToolHome {
                 current_dir: paths::user_tool_current_dir()?,
                 bin_dir: paths::user_bin_dir()?,
             }),
+            ToolScope::Project => {
+                let cwd = env::current_dir().context("read current working directory")?;
+                let Some(root) = paths::find_project_root(&cwd) else {
+                    bail!(
+                        "no {} found in {} or its parents; create one to use `za tool --project`",
+                        paths::PROJECT_TOOLS_FILE,
+                        cwd.display()
+                    );
+                };
+                Ok(Self {
+                    scope,
+                    store_dir: paths::shared_tool_store_dir()?,
+                    current_dir: paths::project_tool_current_dir(&root),
+                    bin_dir: paths::project_bin_dir(&root),
+                })
+            }
         }
     }
 
+    /// Builds for another architecture live in a sibling store such as `store-aarch64-linux`, so
+    /// they never shadow or overwrite the host build of the same version.
+    fn for_target(&self, target: ToolTarget) -> Self {
+        let mut home = self.clone();
+        if !target.runs_on_host() {
+            let mut store_name = self
+                .store_dir
+                .file_name()
+                .unwrap_or_default()
+                .to_os_string();
+            store_name.push(format!("-{target}"));
+            home.store_dir = self.store_dir.with_file_name(store_name);
+        }
+        home
+    }
+
+    /// Project scopes share their version store, so they must never delete versions from it.
+    fn shares_store(&self) -> bool {
+        self.scope == ToolScope::Project
+    }
+
     fn ensure_layout(&self) -> Result<()> {
         create_dir_all_with_context(&self.store_dir, "store")?;
         create_dir_all_with_context(&self.current_dir, "current")?;
@@ -1152,6 +1544,21 @@ impl ToolHome {
         self.bin_dir.join(name)
     }
 
+    /// Project scopes have no Docker CLI plugin directory; Docker only reads user and system ones.
+    fn exposed_entry_path(&self, placement: EntryPlacement) -> Result<Option<PathBuf>> {
+        let dir = match (placement, self.scope) {
+            (EntryPlacement::Bin(_), _) => self.bin_dir.clone(),
+            (EntryPlacement::DockerCliPlugin(_), ToolScope::Global) => {
+                PathBuf::from(paths::GLOBAL_DOCKER_CLI_PLUGINS_DIR)
+            }
+            (EntryPlacement::DockerCliPlugin(_), ToolScope::User) => {
+                paths::user_docker_cli_plugins_dir()?
+            }
+            (EntryPlacement::DockerCliPlugin(_), ToolScope::Project) => return Ok(None),
+        };
+        Ok(Some(dir.join(placement.file_name())))
+    }
+
     fn active_path(&self, name: &str) -> PathBuf {
         match package_policy_for_name(name) {
             Some(package) => self.current_package_path(name).join(package.entry_relpath),
@@ -1166,6 +1573,18 @@ impl ToolHome {
     fn self_update_backup_dir(&self) -> PathBuf {
         self.current_dir.join(SELF_UPDATE_BACKUP_DIR)
     }
+
+    /// Sits next to `current/` for global and user scopes. Project history lives in the user's
+    /// state directory, keyed by project, so the database stays out of the repository.
+    fn history_db_path(&self) -> Result<PathBuf> {
+        match self.scope {
+            ToolScope::Global | ToolScope::User => {
+                Ok(self.current_dir.with_file_name(history::HISTORY_DB_FILE))
+            }
+            ToolScope::Project => Ok(paths::project_tool_history_dir()?
+                .join(history::project_history_db_file(&self.current_dir))),
+        }
+    }
 }
 
 impl ToolLock {
@@ -1202,7 +1621,8 @@ fn materialize_pulled_tool(home: &ToolHome, tool: &ToolRef, source: &PullSource)
                     tool.name
                 );
             }
-            copy_executable(&source.path, &home.install_path(tool))
+            copy_executable(&source.path, &home.install_path(tool))?;
+            completions::stage_archived_completions(home, tool, source)
         }
         ToolLayout::Package => stage_package_payload(home, tool, source),
     }
@@ -1328,19 +1748,71 @@ fn plan_install(
     ensure_not_interrupted()?;
 
     requested.name = canonical_tool_name(&requested.name);
+    let admin_policy = admin::load_admin_policy(home)?;
+    if let Some(admin_policy) = admin_policy.as_ref() {
+        let source = if options.adoption == AdoptionMode::Require {
+            Some(admin::AdminToolSource::Adopted)
+        } else {
+            admin::AdminToolSource::of_tool(&requested.name)
+        };
+        admin_policy.check_tool(&requested.name, source)?;
+    }
     let adoption = if options.adoption == AdoptionMode::Require {
         detect_adoption_candidate(home, &requested)?
     } else {
         None
     };
+    let resolution_requirement = admin::resolution_requirement(
+        requested.requirement.as_ref(),
+        admin_policy
+            .as_ref()
+            .and_then(|admin_policy| admin_policy.version_requirement(&requested.name)),
+    );
     let version = if let Some(v) = requested.version.as_deref() {
         let v = normalize_version(v);
         if v.is_empty() {
             bail!("version must not be empty");
         }
+        if let Some(requirement) = requested.requirement.as_ref() {
+            source::ensure_version_satisfies(&requested.name, &v, requirement)?;
+        }
         v
     } else if let Some(adopted) = adoption.as_ref() {
         adopted.version.clone()
+    } else if let Some(requirement) = resolution_requirement.as_ref() {
+        print_tool_stage_if(
+            options.emit_stages,
+            "resolve",
+            format!(
+                "latest version for `{}` matching `{requirement}`",
+                requested.name
+            ),
+        );
+        let latest =
+            source::resolve_version_in_range(&requested.name, requirement, options.proxy_scope)?;
+        if options.action == ToolAction::Update {
+            let installed = read_current_version(home, &requested.name)?;
+            let (version, held_back) = source::apply_min_release_age(
+                &requested.name,
+                installed.as_deref(),
+                &latest,
+                Some(requirement),
+                options.proxy_scope,
+            )?;
+            if let Some(skip) = held_back {
+                print_tool_stage_if(
+                    options.emit_stages,
+                    "hold",
+                    format!(
+                        "`{}` {} is newer than the minimum release age; eligible on {}",
+                        requested.name, skip.version, skip.eligible_on
+                    ),
+                );
+            }
+            version
+        } else {
+            latest
+        }
     } else {
         print_tool_stage_if(
             options.emit_stages,
@@ -1349,6 +1821,13 @@ fn plan_install(
         );
         resolve_requested_version(&requested.name, None, options.proxy_scope)?
     };
+    if adoption.is_some() && !options.target.is_host() {
+        bail!(
+            "cannot adopt `{}` for target {}; adopted binaries always match the host",
+            requested.name,
+            options.target
+        );
+    }
     if options.adoption == AdoptionMode::Require && adoption.is_none() {
         bail!(
             "no unmanaged `{}` binary found in {} scope to adopt",
@@ -1356,19 +1835,38 @@ fn plan_install(
             home.scope.label()
         );
     }
+    let locked = requested.locked.take();
+    let requirement = requested.requirement.take();
+    let integrations = requested.integrations;
     let tool = requested.resolve(version);
+    if let Some(admin_policy) = admin_policy.as_ref() {
+        admin_policy.check_version(&tool.name, &tool.version)?;
+    }
     ensure_not_interrupted()?;
 
-    let previous_active = read_current_version(home, &tool.name)?;
+    // Foreign-target installs are never activated, so the host's active version is irrelevant.
+    let activates = options.target.runs_on_host();
+    let previous_active = if activates {
+        read_current_version(home, &tool.name)?
+    } else {
+        None
+    };
     let dst = home.install_path(&tool);
     let already_installed = dst.exists();
+    if already_installed {
+        ensure_installed_target_matches(home, &tool, options.target)?;
+        if let Some(locked) = locked.as_ref() {
+            ensure_installed_asset_matches_lock(home, &tool, locked)?;
+        }
+    }
     let manifest_exists = home.manifest_path(&tool).exists();
     let active_exists = home.active_path(&tool.name).exists();
     let current_matches_target = previous_active
         .as_deref()
         .is_some_and(|current| normalize_version(current) == normalize_version(&tool.version));
-    let update_target_is_healthy =
-        current_matches_target && already_installed && manifest_exists && active_exists;
+    let update_target_is_healthy = already_installed
+        && manifest_exists
+        && (!activates || (current_matches_target && active_exists));
     let planned_outcome = match options.action {
         ToolAction::Install => {
             if update_target_is_healthy {
@@ -1399,6 +1897,9 @@ fn plan_install(
         already_installed,
         planned_outcome,
         current_matches_target,
+        requirement,
+        locked,
+        integrations,
     })
 }
 
@@ -1419,7 +1920,7 @@ fn materialize_install_plan(
             fs::create_dir_all(parent)?;
         }
 
-        let source =
+        let (source, target) =
             if let Some(adopted) = plan.adoption.as_ref().filter(|a| a.version == tool.version) {
                 print_tool_stage_if(
                     options.emit_stages,
@@ -1427,10 +1928,15 @@ fn materialize_install_plan(
                     format!("adopting existing binary {}", adopted.path.display()),
                 );
                 copy_executable(&adopted.path, &dst)?;
-                InstallSource {
-                    kind: SOURCE_KIND_ADOPTED,
-                    detail: format!("existing binary {}", adopted.path.display()),
-                }
+                (
+                    InstallSource {
+                        kind: SOURCE_KIND_ADOPTED,
+                        detail: format!("existing binary {}", adopted.path.display()),
+                        asset: None,
+                        unverified: false,
+                    },
+                    ToolTarget::host(),
+                )
             } else {
                 ensure_not_interrupted()?;
                 print_tool_stage_if(
@@ -1438,12 +1944,27 @@ fn materialize_install_plan(
                     "source",
                     format!("fetching `{}` {}", tool.name, tool.version),
                 );
-                let src = match resolve_install_source(
-                    tool,
-                    options.proxy_scope,
-                    options.download_display,
-                    download_progress_sink,
-                ) {
+                let recorded = match plan.locked {
+                    Some(_) => None,
+                    None => cached_recorded_asset(home, tool, options.target),
+                };
+                let resolved = match plan.locked.as_ref().or(recorded.as_ref()) {
+                    Some(locked) => source::resolve_locked_install_source(
+                        tool,
+                        locked,
+                        options.proxy_scope,
+                        options.download_display,
+                        download_progress_sink,
+                    ),
+                    None => resolve_install_source(
+                        tool,
+                        options.proxy_scope,
+                        options.target,
+                        options.download_display,
+                        download_progress_sink,
+                    ),
+                };
+                let src = match resolved {
                     Ok(src) => src,
                     Err(err) => {
                         return Err(match install_source_failure_guidance(&err) {
@@ -1454,12 +1975,18 @@ fn materialize_install_plan(
                 };
                 ensure_not_interrupted()?;
                 materialize_pulled_tool(home, tool, &src)?;
-                InstallSource {
-                    kind: src.kind,
-                    detail: src.resolved_by.clone(),
-                }
+                (
+                    InstallSource {
+                        kind: src.kind,
+                        detail: src.resolved_by.clone(),
+                        asset: src.asset.clone(),
+                        unverified: src.unverified,
+                    },
+                    options.target,
+                )
             };
-        write_manifest(home, tool, &source)?;
+        write_manifest(home, tool, &source, Some(target), plan.requirement.as_ref())?;
+        record_cached_install(tool, &source, target);
         print_tool_stage_if(
             options.emit_stages,
             "install",
@@ -1467,6 +1994,9 @@ fn materialize_install_plan(
         );
     } else {
         ensure_manifest(home, tool)?;
+        if let Some(requirement) = plan.requirement.as_ref() {
+            record_manifest_requirement(home, tool, requirement)?;
+        }
         print_tool_stage_if(
             options.emit_stages,
             "install",
@@ -1482,7 +2012,23 @@ fn activate_install_plan(
     options: InstallOptions,
 ) -> Result<()> {
     let tool = &plan.tool;
+    if !options.target.runs_on_host() {
+        print_tool_stage_if(
+            options.emit_stages,
+            "install",
+            format!(
+                "{} for {} stored at {}; not activated on this host",
+                tool.image(),
+                options.target,
+                home.install_path(tool).display()
+            ),
+        );
+        return Ok(());
+    }
     activate_tool(home, tool)?;
+    if options.record_history {
+        history::record_install_plan_event(home, plan);
+    }
     print_tool_stage_if(
         options.emit_stages,
         "activate",
@@ -1493,9 +2039,14 @@ fn activate_install_plan(
         ),
     );
     emit_user_path_hint(home, tool, options);
-    ensure_post_activation_integrations(home, tool, options.emit_stages)?;
-    if options.prune_after_activation {
-        let removed = prune_non_active_versions(home, tool)?;
+    if plan.integrations {
+        ensure_post_activation_integrations(home, tool, options.emit_stages)?;
+    }
+    if options.prune_after_activation
+        && !home.shares_store()
+        && let Some(retain) = za_config::load_tool_retain_versions()?
+    {
+        let removed = prune_non_active_versions(home, tool, retain)?;
         if !removed.is_empty() {
             print_tool_stage_if(
                 options.emit_stages,
@@ -1512,7 +2063,9 @@ fn activate_install_plan(
 }
 
 fn emit_user_path_hint(home: &ToolHome, tool: &ToolRef, options: InstallOptions) {
-    if home.scope != ToolScope::User || tool_layout_for_name(&tool.name) != ToolLayout::Binary {
+    if !matches!(home.scope, ToolScope::User | ToolScope::Project)
+        || tool_layout_for_name(&tool.name) != ToolLayout::Binary
+    {
         return;
     }
     if path_env_contains_dir(&home.bin_dir) {
@@ -1642,7 +2195,11 @@ fn preview_install(home: &ToolHome, plan: &InstallPlan, options: InstallOptions)
                 format!("would adopt existing binary {}", adopted.path.display()),
             );
         } else {
-            let source = match source::preview_install_source(tool, options.proxy_scope) {
+            let preview = match plan.locked.as_ref() {
+                Some(locked) => Ok(source::preview_locked_install_source(locked)),
+                None => source::preview_install_source(tool, options.proxy_scope, options.target),
+            };
+            let source = match preview {
                 Ok(source) => source,
                 Err(err) => {
                     return Err(match install_source_failure_guidance(&err) {
@@ -1673,6 +2230,19 @@ fn preview_install(home: &ToolHome, plan: &InstallPlan, options: InstallOptions)
         );
     }
 
+    if !options.target.runs_on_host() {
+        print_tool_stage_if(
+            options.emit_stages,
+            "next",
+            format!(
+                "would store {} for {} at {} without activating it; dry-run only",
+                tool.image(),
+                options.target,
+                home.install_path(tool).display()
+            ),
+        );
+        return Ok(());
+    }
     print_tool_stage_if(
         options.emit_stages,
         "activate",
@@ -1682,9 +2252,14 @@ fn preview_install(home: &ToolHome, plan: &InstallPlan, options: InstallOptions)
             home.active_path(&tool.name).display()
         ),
     );
-    preview_post_activation_integrations(home, tool, options.emit_stages)?;
-    if options.prune_after_activation {
-        let removed = stale_versions_to_prune(home, tool)?;
+    if plan.integrations {
+        preview_post_activation_integrations(home, tool, options.emit_stages)?;
+    }
+    if options.prune_after_activation
+        && !home.shares_store()
+        && let Some(retain) = za_config::load_tool_retain_versions()?
+    {
+        let removed = stale_versions_to_prune(home, tool, retain)?;
         if !removed.is_empty() {
             print_tool_stage_if(
                 options.emit_stages,
@@ -1816,6 +2391,10 @@ fn package_policy_for_name(name: &str) -> Option<PackagePolicy> {
     find_tool_policy(name).and_then(|policy| policy.package)
 }
 
+fn exposed_entries_for_name(name: &str) -> &'static [ExposedEntry] {
+    find_tool_policy(name).map_or(&[], |policy| policy.exposed)
+}
+
 fn detect_adoption_candidate(
     home: &ToolHome,
     requested: &ToolSpec,
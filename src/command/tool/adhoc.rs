@@ -0,0 +1,195 @@
+//! Ad hoc GitHub tools installed as `owner/repo` without a catalog or registry entry.
+//!
+//! The repository is recorded in every manifest of such a tool and registered again at the start
+//! of each `za tool` run, so `update`, `outdated` and `show` resolve it like a catalog tool.
+
+use super::policy::{
+    ExpectedAssetName, ReleaseBackend, ReleasePolicy, ReleaseTrack, ReleaseVerification,
+    ToolOrigin, register_adhoc_policy,
+};
+use super::registry::leak_str;
+use super::*;
+
+/// GitHub repository an ad hoc tool was installed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct AdhocToolSource {
+    pub(super) owner: String,
+    pub(super) repo: String,
+    #[serde(default)]
+    pub(super) tag_prefix: String,
+    /// Installed with `--require-digest`, so updates refuse unverifiable assets too.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(super) require_digest: bool,
+}
+
+/// Resolves `owner/repo` to the tool name it installs as. Repositories already covered by the
+/// catalog, a registry or an earlier ad hoc install reuse that entry; anything else gets an ad
+/// hoc policy whose tag prefix is learned from the latest release.
+pub(super) fn prepare_adhoc_tool(
+    raw: &str,
+    require_digest: bool,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<String> {
+    let (owner, repo) = parse_adhoc_repo(raw)?;
+    let name = repo.to_ascii_lowercase();
+    validate_name(&name).with_context(|| format!("cannot install `{raw}`"))?;
+
+    if let Some(policy) = find_tool_policy(&name) {
+        let same_repo = policy.release.is_some_and(|release| {
+            release.backend == ReleaseBackend::Github
+                && release.owner.eq_ignore_ascii_case(&owner)
+                && release.repo.eq_ignore_ascii_case(&repo)
+        });
+        if !same_repo {
+            bail!(
+                "`{raw}` would install as `{name}`, which is already provided by {}",
+                policy.source_label
+            );
+        }
+        if require_digest
+            && let Some(source) = adhoc_source(&name)
+            && !source.require_digest
+        {
+            register_adhoc_policy(adhoc_policy(
+                &name,
+                AdhocToolSource {
+                    require_digest,
+                    ..source
+                },
+            ));
+        }
+        return Ok(policy.canonical_name.to_string());
+    }
+
+    let release = adhoc_release_policy(
+        leak_str(name.clone()),
+        AdhocToolSource {
+            owner: owner.clone(),
+            repo: repo.clone(),
+            tag_prefix: String::new(),
+            require_digest,
+        },
+    );
+    let tag = source::fetch_latest_release_tag(release, proxy_scope)
+        .with_context(|| format!("find the latest GitHub release of `{raw}`"))?;
+    let tag_prefix = release_tag_prefix(&tag)?.to_string();
+    register_adhoc_policy(adhoc_policy(
+        &name,
+        AdhocToolSource {
+            owner,
+            repo,
+            tag_prefix,
+            require_digest,
+        },
+    ));
+    Ok(name)
+}
+
+/// Registers the ad hoc tools installed in `home` so this run can resolve them.
+pub(super) fn register_installed_adhoc_tools(home: &ToolHome) -> Result<()> {
+    for name in collect_managed_tool_names(home)? {
+        if find_tool_policy(&name).is_some() {
+            continue;
+        }
+        let Ok(Some(version)) = read_current_version(home, &name) else {
+            continue;
+        };
+        let tool = ToolRef { name, version };
+        // An unreadable manifest only loses ad hoc resolution; `doctor` reports the manifest.
+        let Ok(Some(manifest)) = read_manifest(home, &tool) else {
+            continue;
+        };
+        if let Some(source) = manifest.adhoc {
+            register_adhoc_policy(adhoc_policy(&tool.name, source));
+        }
+    }
+    Ok(())
+}
+
+/// Repository recorded in the manifest when `name` resolves to an ad hoc policy.
+pub(super) fn adhoc_source(name: &str) -> Option<AdhocToolSource> {
+    let release = find_tool_policy(name)?.release?;
+    matches!(
+        release.expected_asset_name,
+        Some(ExpectedAssetName::HostMatch)
+    )
+    .then(|| AdhocToolSource {
+        owner: release.owner.to_string(),
+        repo: release.repo.to_string(),
+        tag_prefix: release.tag_prefix.to_string(),
+        require_digest: matches!(
+            release.verification,
+            ReleaseVerification::AvailableDigest { required: true }
+        ),
+    })
+}
+
+pub(super) fn adhoc_policy(name: &str, source: AdhocToolSource) -> ToolPolicy {
+    let canonical_name = leak_str(name.to_string());
+    let verified = if source.require_digest {
+        "digest required"
+    } else {
+        "verified when a digest is published"
+    };
+    ToolPolicy {
+        canonical_name,
+        aliases: &[],
+        source_label: leak_str(format!(
+            "GitHub Release (ad hoc {}/{}; {verified})",
+            source.owner, source.repo
+        )),
+        layout: ToolLayout::Binary,
+        package: None,
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Adhoc,
+        release: Some(adhoc_release_policy(canonical_name, source)),
+    }
+}
+
+fn adhoc_release_policy(name: &'static str, source: AdhocToolSource) -> ReleasePolicy {
+    ReleasePolicy {
+        project_label: name,
+        backend: ReleaseBackend::Github,
+        owner: leak_str(source.owner),
+        repo: leak_str(source.repo),
+        tag_prefix: leak_str(source.tag_prefix),
+        expected_asset_name: Some(ExpectedAssetName::HostMatch),
+        verification: ReleaseVerification::AvailableDigest {
+            required: source.require_digest,
+        },
+        track: ReleaseTrack::VersionedTags,
+    }
+}
+
+pub(super) fn parse_adhoc_repo(raw: &str) -> Result<(String, String)> {
+    let trimmed = raw.trim();
+    let path = trimmed
+        .strip_prefix("https://github.com/")
+        .or_else(|| trimmed.strip_prefix("github.com/"))
+        .unwrap_or(trimmed)
+        .trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let Some((owner, repo)) = path.split_once('/') else {
+        bail!("invalid GitHub repository `{raw}`: expected `owner/repo`");
+    };
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    };
+    if !valid(owner) || !valid(repo) {
+        bail!("invalid GitHub repository `{raw}`: expected `owner/repo`");
+    }
+    Ok((owner.to_string(), repo.to_string()))
+}
+
+/// Everything before the first digit, e.g. `v` for `v1.2.3` or `cli-` for `cli-0.4.0`.
+pub(super) fn release_tag_prefix(tag: &str) -> Result<&str> {
+    let Some(digit) = tag.find(|c: char| c.is_ascii_digit()) else {
+        bail!("release tag `{tag}` has no version number");
+    };
+    Ok(&tag[..digit])
+}
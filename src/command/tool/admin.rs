@@ -0,0 +1,222 @@
+//! Administrator policy for the global scope, read from `/etc/za/tool-policy.toml`.
+//!
+//! ```toml
+//! # Only these tools may be installed, updated or adopted; omit to allow every tool.
+//! allow = ["rg", "fd", "just"]
+//! # Always refused, even when listed in `allow`.
+//! deny = ["codex"]
+//! # Where tools may come from; omit to allow every source.
+//! sources = ["catalog", "system-registry", "adopted"]
+//!
+//! [versions]
+//! rg = ">=14, <15"
+//! ```
+//!
+//! Installs, updates, adoptions and rollbacks that break the policy are refused, installs and
+//! updates resolve the newest release inside the allowed range, and `za tool doctor` flags active
+//! tools that break it.
+
+use super::policy::ToolOrigin;
+use super::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+const ADMIN_POLICY_PATH: &str = "/etc/za/tool-policy.toml";
+
+/// Where an installed tool came from, as named in the policy's `sources` list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) enum AdminToolSource {
+    Catalog,
+    SystemRegistry,
+    UserRegistry,
+    Adhoc,
+    /// An unmanaged binary taken over with `za tool install --adopt`.
+    Adopted,
+}
+
+impl AdminToolSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Catalog => "catalog",
+            Self::SystemRegistry => "system-registry",
+            Self::UserRegistry => "user-registry",
+            Self::Adhoc => "adhoc",
+            Self::Adopted => "adopted",
+        }
+    }
+
+    /// Source of `name` when installed from its release; `None` for tools za does not know.
+    pub(super) fn of_tool(name: &str) -> Option<Self> {
+        Some(match find_tool_policy(name)?.origin {
+            ToolOrigin::Catalog => Self::Catalog,
+            ToolOrigin::SystemRegistry => Self::SystemRegistry,
+            ToolOrigin::UserRegistry => Self::UserRegistry,
+            ToolOrigin::Adhoc => Self::Adhoc,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AdminToolPolicyFile {
+    #[serde(default)]
+    allow: Option<Vec<String>>,
+    #[serde(default)]
+    deny: Vec<String>,
+    #[serde(default)]
+    sources: Option<Vec<AdminToolSource>>,
+    #[serde(default)]
+    versions: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct AdminToolPolicy {
+    path: PathBuf,
+    allow: Option<BTreeSet<String>>,
+    deny: BTreeSet<String>,
+    sources: Option<Vec<AdminToolSource>>,
+    versions: BTreeMap<String, VersionReq>,
+}
+
+/// Policy that applies to `home`; only the global scope is restricted.
+pub(super) fn load_admin_policy(home: &ToolHome) -> Result<Option<AdminToolPolicy>> {
+    if home.scope != ToolScope::Global {
+        return Ok(None);
+    }
+    let path = Path::new(ADMIN_POLICY_PATH);
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+    };
+    parse_admin_policy(path, &raw).map(Some)
+}
+
+pub(super) fn parse_admin_policy(path: &Path, raw: &str) -> Result<AdminToolPolicy> {
+    let file = toml::from_str::<AdminToolPolicyFile>(raw)
+        .with_context(|| format!("invalid admin tool policy {}", path.display()))?;
+    let versions = file
+        .versions
+        .into_iter()
+        .map(|(name, raw_req)| {
+            let requirement = VersionReq::parse(&raw_req).with_context(|| {
+                format!(
+                    "invalid version range `{raw_req}` for `{name}` in {}",
+                    path.display()
+                )
+            })?;
+            Ok((canonical_tool_name(&name), requirement))
+        })
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    Ok(AdminToolPolicy {
+        path: path.to_path_buf(),
+        allow: file
+            .allow
+            .map(|names| names.iter().map(|name| canonical_tool_name(name)).collect()),
+        deny: file
+            .deny
+            .iter()
+            .map(|name| canonical_tool_name(name))
+            .collect(),
+        sources: file.sources,
+        versions,
+    })
+}
+
+impl AdminToolPolicy {
+    /// Refuses `name` when it is denied, missing from the allow list, or comes from a source the
+    /// policy does not list.
+    pub(super) fn check_tool(&self, name: &str, source: Option<AdminToolSource>) -> Result<()> {
+        let path = self.path.display();
+        if self.deny.contains(name) {
+            bail!("`{name}` is denied by the admin tool policy {path}");
+        }
+        if let Some(allow) = self.allow.as_ref()
+            && !allow.contains(name)
+        {
+            bail!("`{name}` is not in the allow list of the admin tool policy {path}");
+        }
+        if let Some(sources) = self.sources.as_ref() {
+            let allowed = sources
+                .iter()
+                .map(|source| source.label())
+                .collect::<Vec<_>>()
+                .join(", ");
+            match source {
+                Some(source) if sources.contains(&source) => {}
+                Some(source) => bail!(
+                    "`{name}` comes from the {} source, which the admin tool policy {path} does not allow (allowed: {allowed})",
+                    source.label()
+                ),
+                None => bail!(
+                    "`{name}` has no known source; the admin tool policy {path} only allows {allowed}"
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Range every installed version of `name` must fall in.
+    pub(super) fn version_requirement(&self, name: &str) -> Option<&VersionReq> {
+        self.versions.get(name)
+    }
+
+    pub(super) fn check_version(&self, name: &str, version: &str) -> Result<()> {
+        let Some(requirement) = self.version_requirement(name) else {
+            return Ok(());
+        };
+        source::ensure_version_satisfies(name, version, requirement)
+            .with_context(|| format!("refused by the admin tool policy {}", self.path.display()))
+    }
+
+    /// Refuses running an already stored `tool` from `home` when the policy does not allow it.
+    pub(super) fn check_installed(&self, home: &ToolHome, tool: &ToolRef) -> Result<()> {
+        self.check_tool(&tool.name, installed_tool_source(home, tool))?;
+        self.check_version(&tool.name, &tool.version)
+    }
+
+    /// Every way the active `version` of `name` breaks the policy, for `za tool doctor`.
+    pub(super) fn violations(
+        &self,
+        name: &str,
+        version: &str,
+        source: Option<AdminToolSource>,
+    ) -> Vec<String> {
+        [
+            self.check_tool(name, source).err(),
+            self.check_version(name, version).err(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|err| format!("out of admin policy: {err:#}"))
+        .collect()
+    }
+}
+
+/// Narrows a requested range to the admin range so resolution only picks allowed versions.
+pub(super) fn resolution_requirement(
+    requested: Option<&VersionReq>,
+    admin: Option<&VersionReq>,
+) -> Option<VersionReq> {
+    match (requested, admin) {
+        (Some(requested), Some(admin)) => Some(VersionReq {
+            comparators: requested
+                .comparators
+                .iter()
+                .chain(&admin.comparators)
+                .cloned()
+                .collect(),
+        }),
+        (requested, admin) => requested.or(admin).cloned(),
+    }
+}
+
+/// Source recorded for an installed tool: adopted binaries keep that origin across updates.
+pub(super) fn installed_tool_source(home: &ToolHome, tool: &ToolRef) -> Option<AdminToolSource> {
+    match read_manifest(home, tool) {
+        Ok(Some(manifest)) if manifest.source_kind == SOURCE_KIND_ADOPTED => {
+            Some(AdminToolSource::Adopted)
+        }
+        _ => AdminToolSource::of_tool(&tool.name),
+    }
+}
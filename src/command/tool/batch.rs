@@ -1,26 +1,48 @@
+use super::admin::{AdminToolPolicy, load_admin_policy};
+use super::listing::hold_back_recent_release;
+use super::lockfile::{
+    TOOL_SYNC_LOCKFILE_VERSION, ToolSyncLockfile, collect_sync_lock_entries, locked_sync_specs,
+    read_sync_lockfile, sync_lockfile_path, write_sync_lockfile,
+};
+use super::target::ToolTarget;
 use super::*;
 
-pub(super) fn install_tools(
-    home: &ToolHome,
-    tools: &[String],
-    version: Option<&str>,
-    adopt: bool,
-    action: ToolAction,
-    dry_run: bool,
-    verbose: bool,
-) -> Result<()> {
+pub(super) struct ToolInstallRun<'a> {
+    pub(super) home: &'a ToolHome,
+    pub(super) tools: &'a [String],
+    pub(super) version: Option<&'a str>,
+    pub(super) adopt: bool,
+    pub(super) require_digest: bool,
+    pub(super) target: ToolTarget,
+    pub(super) dry_run: bool,
+    pub(super) verbose: bool,
+}
+
+pub(super) fn install_tools(input: ToolInstallRun<'_>) -> Result<()> {
+    let ToolInstallRun {
+        home,
+        tools,
+        version,
+        adopt,
+        require_digest,
+        target,
+        dry_run,
+        verbose,
+    } = input;
+    if require_digest && tools.iter().any(|tool| !tool.contains('/')) {
+        bail!("`za tool install --require-digest` only applies to `owner/repo` tools");
+    }
     if adopt && version.is_some() {
         bail!("`za tool install --adopt` does not accept `--version`");
     }
     if adopt && tools.len() != 1 {
         bail!("`za tool install --adopt` requires exactly one tool name");
     }
+    if adopt && !target.is_host() {
+        bail!("`za tool install --adopt` does not accept `--target`");
+    }
     if version.is_some() && tools.len() != 1 {
-        let command = match action {
-            ToolAction::Install => "install",
-            ToolAction::Update => "update",
-        };
-        bail!("`za tool {command} --version` requires exactly one tool name");
+        bail!("`za tool install --version` requires exactly one tool name");
     }
 
     let requested_names = if tools.is_empty() {
@@ -29,7 +51,17 @@ pub(super) fn install_tools(
         }
         collect_managed_tool_names(home)?
     } else {
-        normalize_requested_tool_names(tools)?
+        let tools = tools
+            .iter()
+            .map(|tool| {
+                if tool.contains('/') {
+                    adhoc::prepare_adhoc_tool(tool, require_digest, za_config::ProxyScope::Tool)
+                } else {
+                    Ok(tool.clone())
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        normalize_requested_tool_names(&tools)?
     };
 
     if requested_names.is_empty() {
@@ -55,19 +87,18 @@ pub(super) fn install_tools(
         .iter()
         .map(|name| ToolSpec::from_args(name, version))
         .collect::<Result<Vec<_>>>()?;
-    let kind = match action {
-        ToolAction::Install => ToolBatchKind::Install,
-        ToolAction::Update => ToolBatchKind::Update,
-    };
-    run_tool_batch(
+    run_tool_batch_inner(ToolBatchRun {
         home,
-        kind,
+        kind: ToolBatchKind::Install,
         specs,
         dry_run,
         verbose,
-        None,
-        ToolUpdateChannel::Stable,
-    )
+        source_label: None,
+        update_channel: ToolUpdateChannel::Stable,
+        skipped_unknown_tools: Vec::new(),
+        target,
+    })?;
+    Ok(())
 }
 
 pub(super) fn update_tools(
@@ -107,7 +138,14 @@ pub(super) fn update_tools(
 
     let specs = requested_names
         .iter()
-        .map(|name| ToolSpec::from_args(name, version))
+        .map(|name| {
+            let mut spec = ToolSpec::from_args(name, version)?;
+            if version.is_none() && channel == ToolUpdateChannel::Stable {
+                spec.requirement = read_active_requirement(home, &spec.name)?;
+            }
+            spec.target = read_active_target(home, &spec.name)?;
+            Ok(spec)
+        })
         .collect::<Result<Vec<_>>>()?;
     run_tool_batch_inner(ToolBatchRun {
         home,
@@ -118,28 +156,9 @@ pub(super) fn update_tools(
         source_label: None,
         update_channel: channel,
         skipped_unknown_tools,
-    })
-}
-
-pub(super) fn run_tool_batch(
-    home: &ToolHome,
-    kind: ToolBatchKind,
-    specs: Vec<ToolSpec>,
-    dry_run: bool,
-    verbose: bool,
-    source_label: Option<&str>,
-    update_channel: ToolUpdateChannel,
-) -> Result<()> {
-    run_tool_batch_inner(ToolBatchRun {
-        home,
-        kind,
-        specs,
-        dry_run,
-        verbose,
-        source_label,
-        update_channel,
-        skipped_unknown_tools: Vec::new(),
-    })
+        target: ToolTarget::host(),
+    })?;
+    Ok(())
 }
 
 struct ToolBatchRun<'a> {
@@ -151,9 +170,11 @@ struct ToolBatchRun<'a> {
     source_label: Option<&'a str>,
     update_channel: ToolUpdateChannel,
     skipped_unknown_tools: Vec<String>,
+    target: ToolTarget,
 }
 
-fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
+/// Runs one batch and returns the tool versions it settled on, in spec order.
+fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<Vec<ToolRef>> {
     let ToolBatchRun {
         home,
         kind,
@@ -163,7 +184,10 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
         source_label,
         update_channel,
         skipped_unknown_tools,
+        target,
     } = input;
+    let target_home = home.for_target(target);
+    let home = &target_home;
     let total = specs.len();
     let batch_mode = total > 1 || matches!(kind, ToolBatchKind::Update | ToolBatchKind::Sync);
     let compact_mode = batch_mode && !verbose;
@@ -173,6 +197,7 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
         ..Default::default()
     };
     let mut failed_tools = Vec::new();
+    let mut resolved_tools = Vec::new();
     let mut materialize_tasks = Vec::new();
 
     if compact_mode {
@@ -188,7 +213,15 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
         );
     }
 
-    let latest_lookup = resolve_batch_latest_lookup(&specs, update_channel)?;
+    let admin_policy = load_admin_policy(home)?;
+    let mut latest_lookup =
+        resolve_batch_latest_lookup(&specs, update_channel, admin_policy.as_ref())?;
+    if kind == ToolBatchKind::Update
+        && update_channel == ToolUpdateChannel::Stable
+        && let Some(lookup) = latest_lookup.as_mut()
+    {
+        hold_back_recent_batch_releases(home, lookup)?;
+    }
 
     for (idx, requested) in specs.iter().enumerate() {
         ensure_not_interrupted()?;
@@ -196,23 +229,25 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
             println!("➡️  [{}/{}] {}", idx + 1, total, requested.name);
         }
 
-        let resolved_spec = match resolve_batch_tool_spec(requested, latest_lookup.as_ref()) {
-            Ok(spec) => spec,
-            Err(err) => {
-                summary.failed += 1;
-                failed_tools.push(requested.name.clone());
-                let message = if compact_mode {
-                    summarize_tool_update_error(&err.to_string())
-                } else {
-                    err.to_string()
-                };
-                print_tool_stage("fail", format!("`{}` {message}", requested.name));
-                if total == 1 {
-                    return Err(err);
+        let resolved_spec =
+            match resolve_batch_tool_spec(requested, latest_lookup.as_ref(), admin_policy.as_ref())
+            {
+                Ok(spec) => spec,
+                Err(err) => {
+                    summary.failed += 1;
+                    failed_tools.push(requested.name.clone());
+                    let message = if compact_mode {
+                        summarize_tool_update_error(&err.to_string())
+                    } else {
+                        err.to_string()
+                    };
+                    print_tool_stage("fail", format!("`{}` {message}", requested.name));
+                    if total == 1 {
+                        return Err(err);
+                    }
+                    continue;
                 }
-                continue;
-            }
-        };
+            };
 
         let options = match kind {
             ToolBatchKind::Install => InstallOptions::install(za_config::ProxyScope::Tool),
@@ -221,6 +256,7 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
             }
         }
         .dry_run(dry_run)
+        .target(requested.target.unwrap_or(target))
         .emit_stages(!compact_mode)
         .emit_plan_stage(compact_mode)
         .download_display(if compact_mode {
@@ -241,6 +277,7 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
                     );
                     if update_plan_is_unchanged(&plan, options) {
                         summary = summary.record(InstallOutcome::Unchanged);
+                        resolved_tools.push((idx, plan.tool));
                         continue;
                     }
                     materialize_tasks.push(BatchInstallTask {
@@ -271,6 +308,7 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
         match install(home, resolved_spec, options) {
             Ok(result) => {
                 summary = summary.record(result.outcome);
+                resolved_tools.push((idx, result.tool));
             }
             Err(err) => {
                 summary.failed += 1;
@@ -315,7 +353,10 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
                 continue;
             };
             match activate_install_plan(home, &plan, result.activate_options) {
-                Ok(()) => summary = summary.record(plan.planned_outcome),
+                Ok(()) => {
+                    summary = summary.record(plan.planned_outcome);
+                    resolved_tools.push((result.index, plan.tool));
+                }
                 Err(err) => {
                     summary.failed += 1;
                     failed_tools.push(result.requested_name.clone());
@@ -335,7 +376,8 @@ fn run_tool_batch_inner(input: ToolBatchRun<'_>) -> Result<()> {
     }
 
     if failed_tools.is_empty() {
-        return Ok(());
+        resolved_tools.sort_by_key(|(index, _)| *index);
+        return Ok(resolved_tools.into_iter().map(|(_, tool)| tool).collect());
     }
 
     bail!(
@@ -683,13 +725,22 @@ pub(super) fn resolve_update_channel_request(
     Ok(ToolUpdateChannel::CodexAlpha)
 }
 
-fn resolve_batch_latest_lookup(
+/// Explicit versions, ranges and tools the admin policy pins to a range are resolved per tool
+/// while planning, so an admin range picks its newest allowed release instead of upstream latest.
+fn resolves_while_planning(spec: &ToolSpec, admin_policy: Option<&AdminToolPolicy>) -> bool {
+    spec.version.is_some()
+        || spec.requirement.is_some()
+        || admin_policy.is_some_and(|policy| policy.version_requirement(&spec.name).is_some())
+}
+
+pub(super) fn resolve_batch_latest_lookup(
     specs: &[ToolSpec],
     update_channel: ToolUpdateChannel,
+    admin_policy: Option<&AdminToolPolicy>,
 ) -> Result<Option<HashMap<String, LatestCheck>>> {
     let unresolved_names = specs
         .iter()
-        .filter(|spec| spec.version.is_none())
+        .filter(|spec| !resolves_while_planning(spec, admin_policy))
         .map(|spec| spec.name.clone())
         .collect::<Vec<_>>();
     if unresolved_names.is_empty() {
@@ -710,6 +761,34 @@ fn resolve_batch_latest_lookup(
     Ok(Some(lookup.latest_by_name))
 }
 
+/// Steps update targets back to releases older than the minimum release age.
+fn hold_back_recent_batch_releases(
+    home: &ToolHome,
+    lookup: &mut HashMap<String, LatestCheck>,
+) -> Result<()> {
+    let mut names = lookup.keys().cloned().collect::<Vec<_>>();
+    names.sort();
+    for name in names {
+        let Some(latest) = lookup.remove(&name) else {
+            continue;
+        };
+        let installed = read_current_version(home, &name)?;
+        let (latest, held_back) =
+            hold_back_recent_release(&name, installed.as_deref(), None, latest);
+        if let Some(skip) = held_back {
+            print_tool_stage(
+                "hold",
+                format!(
+                    "`{name}` {} is newer than the minimum release age; eligible on {}",
+                    skip.version, skip.eligible_on
+                ),
+            );
+        }
+        lookup.insert(name, latest);
+    }
+    Ok(())
+}
+
 pub(super) fn latest_resolution_mode_for_batch(
     specs: &[ToolSpec],
     update_channel: ToolUpdateChannel,
@@ -724,11 +803,12 @@ pub(super) fn latest_resolution_mode_for_batch(
     }
 }
 
-fn resolve_batch_tool_spec(
+pub(super) fn resolve_batch_tool_spec(
     requested: &ToolSpec,
     latest_lookup: Option<&HashMap<String, LatestCheck>>,
+    admin_policy: Option<&AdminToolPolicy>,
 ) -> Result<ToolSpec> {
-    if requested.version.is_some() {
+    if resolves_while_planning(requested, admin_policy) {
         return Ok(requested.clone());
     }
 
@@ -900,44 +980,210 @@ pub(super) fn normalize_requested_tool_names(names: &[String]) -> Result<Vec<Str
     Ok(out)
 }
 
-pub(super) fn sync_manifest(
-    home: &ToolHome,
-    file: &Path,
-    dry_run: bool,
-    verbose: bool,
-) -> Result<()> {
-    let specs = load_sync_specs_from_manifest(file)?;
-    let source_label = file.display().to_string();
-    let parsed = specs
+/// One resolved sync manifest entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ToolSyncEntry {
+    /// Canonical spec, e.g. `codex` or `just:^1.30`.
+    pub(super) spec: String,
+    pub(super) name: String,
+    pub(super) channel: ToolUpdateChannel,
+    pub(super) hold: bool,
+    pub(super) integrations: bool,
+}
+
+pub(super) struct ToolSyncRequest<'a> {
+    pub(super) file: &'a Path,
+    pub(super) target: ToolTarget,
+    pub(super) locked: bool,
+    pub(super) prune: bool,
+    pub(super) dry_run: bool,
+    pub(super) verbose: bool,
+}
+
+/// In project scope a relative manifest path that is missing here is looked up at the project
+/// root, so `za tool --project sync` works from any subdirectory.
+fn resolve_sync_manifest_path(home: &ToolHome, file: &Path) -> Result<PathBuf> {
+    if home.scope != ToolScope::Project || file.is_absolute() || file.exists() {
+        return Ok(file.to_path_buf());
+    }
+    let cwd = env::current_dir().context("read current working directory")?;
+    Ok(paths::find_project_root(&cwd)
+        .map(|root| root.join(file))
+        .unwrap_or_else(|| file.to_path_buf()))
+}
+
+pub(super) fn sync_manifest(home: &ToolHome, request: ToolSyncRequest<'_>) -> Result<()> {
+    let ToolSyncRequest {
+        file,
+        target,
+        locked,
+        prune,
+        dry_run,
+        verbose,
+    } = request;
+    if prune && !target.runs_on_host() {
+        bail!(
+            "`za tool sync --prune` cannot be combined with a `--target` for another architecture"
+        );
+    }
+    let file = &resolve_sync_manifest_path(home, file)?;
+    let entries = load_sync_entries_from_manifest(file)?;
+    let specs = entries
         .iter()
-        .map(|spec| ToolSpec::parse(spec))
-        .collect::<Result<Vec<_>>>()?;
-    run_tool_batch(
+        .map(|entry| entry.spec.clone())
+        .collect::<Vec<_>>();
+    let source_label = file.display().to_string();
+    let lockfile_path = sync_lockfile_path(file);
+    let parsed = if locked {
+        let Some(lockfile) = read_sync_lockfile(&lockfile_path)? else {
+            bail!(
+                "`za tool sync --locked` requires lockfile {}; run `za tool sync` first to create it",
+                lockfile_path.display()
+            );
+        };
+        locked_sync_specs(&lockfile_path, &lockfile, &entries, target)?
+    } else {
+        entries
+            .iter()
+            .map(|entry| sync_entry_tool_spec(home, entry))
+            .collect::<Result<Vec<_>>>()?
+    };
+    let resolved = run_tool_batch_inner(ToolBatchRun {
         home,
-        ToolBatchKind::Sync,
-        parsed,
+        kind: ToolBatchKind::Sync,
+        specs: parsed,
         dry_run,
         verbose,
-        Some(&source_label),
-        ToolUpdateChannel::Stable,
-    )
+        source_label: Some(&source_label),
+        update_channel: ToolUpdateChannel::Stable,
+        skipped_unknown_tools: Vec::new(),
+        target,
+    })?;
+    if prune {
+        prune_unlisted_tools(home, &entries, dry_run)?;
+    }
+    if dry_run || locked {
+        return Ok(());
+    }
+
+    let lockfile = ToolSyncLockfile {
+        version: TOOL_SYNC_LOCKFILE_VERSION,
+        tools: collect_sync_lock_entries(&home.for_target(target), &specs, &resolved)?,
+    };
+    if read_sync_lockfile(&lockfile_path).ok().flatten().as_ref() != Some(&lockfile) {
+        write_sync_lockfile(&lockfile_path, &lockfile)?;
+        print_tool_stage("lock", format!("wrote {}", lockfile_path.display()));
+    }
+    Ok(())
+}
+
+/// Applies `hold` and `channel` to one entry before it joins the sync batch.
+fn sync_entry_tool_spec(home: &ToolHome, entry: &ToolSyncEntry) -> Result<ToolSpec> {
+    let mut spec = ToolSpec::parse(&entry.spec)?;
+    spec.integrations = entry.integrations;
+    if entry.hold
+        && let Some(current) = read_current_version(home, &entry.name)?
+    {
+        spec.version = Some(current);
+        spec.requirement = None;
+        return Ok(spec);
+    }
+    if entry.channel == ToolUpdateChannel::CodexAlpha {
+        spec.version = Some(source::fetch_latest_codex_alpha_version(
+            za_config::ProxyScope::Tool,
+        )?);
+    }
+    Ok(spec)
 }
 
-pub(crate) fn load_sync_specs_from_manifest(file: &Path) -> Result<Vec<String>> {
+/// Uninstalls za-managed tools that the sync manifest no longer lists. `za` itself is never pruned.
+pub(super) fn prune_unlisted_tools(
+    home: &ToolHome,
+    entries: &[ToolSyncEntry],
+    dry_run: bool,
+) -> Result<()> {
+    let listed = entries
+        .iter()
+        .map(|entry| entry.name.as_str())
+        .collect::<HashSet<_>>();
+    for name in collect_managed_tool_names(home)? {
+        if name == "za" || listed.contains(name.as_str()) {
+            continue;
+        }
+        if dry_run {
+            print_tool_stage("prune", format!("would uninstall `{name}`"));
+            continue;
+        }
+        print_tool_stage("prune", format!("`{name}` is not listed in the manifest"));
+        uninstall(home, ToolSpec::from_args(&name, None)?)?;
+    }
+    Ok(())
+}
+
+pub(super) fn load_sync_entries_from_manifest(file: &Path) -> Result<Vec<ToolSyncEntry>> {
     let raw = fs::read_to_string(file)
         .with_context(|| format!("read sync manifest {}", file.display()))?;
     let manifest = toml::from_str::<ToolSyncManifest>(&raw)
         .with_context(|| format!("parse sync manifest {}", file.display()))?;
-    if manifest.tools.is_empty() {
+
+    let (raw_entries, table_form) = match manifest.tools {
+        toml::Value::Array(items) => {
+            let entries = items
+                .into_iter()
+                .map(|item| match item {
+                    toml::Value::String(spec) => Ok((spec, ToolSyncManifestTable::default())),
+                    other => bail!(
+                        "sync manifest {} lists `{other}`; expected a tool spec string",
+                        file.display()
+                    ),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            (entries, false)
+        }
+        toml::Value::Table(table) => {
+            let entries = table
+                .into_iter()
+                .map(|(name, value)| {
+                    let settings = match value {
+                        toml::Value::String(version) => ToolSyncManifestTable {
+                            version: Some(version),
+                            ..Default::default()
+                        },
+                        value @ toml::Value::Table(_) => value
+                            .try_into::<ToolSyncManifestTable>()
+                            .with_context(|| {
+                                format!("parse `tools.{name}` in {}", file.display())
+                            })?,
+                        other => bail!(
+                            "`tools.{name}` in {} must be a version string or a table, got `{other}`",
+                            file.display()
+                        ),
+                    };
+                    let spec = match settings.version.as_deref().map(str::trim) {
+                        Some(version) if !version.is_empty() => format!("{name}:{version}"),
+                        _ => name,
+                    };
+                    Ok((spec, settings))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            (entries, true)
+        }
+        _ => bail!(
+            "sync manifest {} has an invalid `tools` value; expected a list or a table",
+            file.display()
+        ),
+    };
+    if raw_entries.is_empty() {
         bail!(
             "sync manifest {} has no tools; expected `tools = [\"codex\", \"docker-compose\"]`",
             file.display()
         );
     }
 
-    let mut specs = Vec::new();
+    let mut entries = Vec::new();
     let mut seen = HashSet::new();
-    for raw_spec in manifest.tools {
+    let mut seen_names = HashSet::new();
+    for (raw_spec, settings) in raw_entries {
         let trimmed = raw_spec.trim();
         if trimmed.is_empty() {
             bail!(
@@ -951,14 +1197,41 @@ pub(crate) fn load_sync_specs_from_manifest(file: &Path) -> Result<Vec<String>>
         parsed.name = canonical_supported_tool_name(&parsed.name)
             .with_context(|| format!("validate sync spec `{trimmed}` in {}", file.display()))?;
 
-        let spec = match parsed.version {
-            Some(version) => format!("{}:{}", parsed.name, version),
-            None => parsed.name,
-        };
+        let channel = settings.channel.unwrap_or(ToolUpdateChannel::Stable);
+        if channel == ToolUpdateChannel::CodexAlpha {
+            if parsed.name != "codex" {
+                bail!(
+                    "sync spec `{trimmed}` in {}: `channel = \"alpha\"` is only supported for `codex`",
+                    file.display()
+                );
+            }
+            if parsed.version.is_some() || parsed.requirement.is_some() {
+                bail!(
+                    "sync spec `{trimmed}` in {}: `channel` cannot be combined with `version`",
+                    file.display()
+                );
+            }
+        }
+        if table_form && !seen_names.insert(parsed.name.clone()) {
+            bail!(
+                "sync manifest {} lists `{}` more than once",
+                file.display(),
+                parsed.name
+            );
+        }
+
+        let spec = parsed.display_spec();
         if seen.insert(spec.clone()) {
-            specs.push(spec);
+            entries.push(ToolSyncEntry {
+                spec,
+                name: parsed.name,
+                channel,
+                hold: settings.hold,
+                // Plain spec lists keep the historical behaviour of always running integrations.
+                integrations: !table_form || settings.integrations,
+            });
         }
     }
 
-    Ok(specs)
+    Ok(entries)
 }
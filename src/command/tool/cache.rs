@@ -0,0 +1,602 @@
+//! Content-addressed cache of downloaded release assets, keyed by SHA-256.
+//!
+//! Every scope of the same user shares one cache, so reinstalls, rollbacks and locked syncs reuse
+//! an asset without touching the network once its digest is known up front. Interrupted parallel
+//! downloads keep their part files under `partial/` so the next attempt can resume them.
+
+use super::source::format_bytes_u64;
+use super::*;
+use crate::cli::ToolCacheCommands;
+use graviola::hashing::{Hash, HashContext, Sha256};
+
+const CACHE_DIGEST_DIR: &str = "sha256";
+const CACHE_ENTRY_FILE: &str = "entry.json";
+const CACHE_ENTRY_TMP_MARKER: &str = ".tmp-";
+const CACHE_PARTIAL_DIR: &str = "partial";
+const PARTIAL_META_FILE: &str = "meta.json";
+const PARTIAL_LOCK_FILE: &str = "lock";
+const PARTIAL_PART_PREFIX: &str = "part-";
+const TOOL_CACHE_MAX_SIZE_ENV: &str = "ZA_TOOL_CACHE_MAX_SIZE";
+pub(super) const TOOL_CACHE_MAX_BYTES_DEFAULT: u64 = 2 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct CachedArtifact {
+    pub(super) sha256: String,
+    /// Asset file name as published upstream.
+    pub(super) file_name: String,
+    /// URL the asset was first downloaded from.
+    pub(super) url: String,
+    pub(super) size_bytes: u64,
+    pub(super) stored_at_unix_secs: u64,
+    pub(super) last_used_unix_secs: u64,
+    /// Tool releases installed from this asset, so a reinstall finds it without a manifest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) releases: Vec<CachedRelease>,
+}
+
+/// One `(tool, version, target)` an installed asset was recorded for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct CachedRelease {
+    pub(super) tool: String,
+    pub(super) version: String,
+    pub(super) target: String,
+}
+
+/// What an interrupted download was started against. Staged parts are only reused when a new
+/// attempt sees the same URL, ETag, length and part split.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct PartialDownloadMeta {
+    pub(super) url: String,
+    pub(super) etag: String,
+    pub(super) total_bytes: u64,
+    pub(super) parts: usize,
+}
+
+/// Staging directory of one resumable parallel download, held under an exclusive file lock.
+#[derive(Debug)]
+pub(super) struct PartialDownload {
+    dir: PathBuf,
+    etag: String,
+    resumed: bool,
+    lock: File,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct CachePrunePolicy {
+    pub(super) all: bool,
+    pub(super) older_than: Option<Duration>,
+    pub(super) max_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct CacheListJson<'a> {
+    dir: String,
+    total_bytes: u64,
+    max_bytes: u64,
+    entries: &'a [CachedArtifact],
+}
+
+pub(super) fn tool_cache_dir() -> Result<PathBuf> {
+    paths::tool_artifact_cache_dir()
+}
+
+pub(super) fn run_cache_command(cmd: ToolCacheCommands) -> Result<i32> {
+    let root = tool_cache_dir()?;
+    match cmd {
+        ToolCacheCommands::Ls { json } => {
+            let entries = list_cache_entries(&root)?;
+            if json {
+                print_cache_entries_json(&root, &entries)?;
+            } else {
+                print_cache_entries_text(&root, &entries);
+            }
+            Ok(0)
+        }
+        ToolCacheCommands::Prune {
+            all,
+            older_than,
+            max_size,
+            dry_run,
+        } => {
+            let older_than = older_than
+                .as_deref()
+                .map(|raw| {
+                    humantime::parse_duration(raw)
+                        .with_context(|| format!("invalid `--older-than` duration `{raw}`"))
+                })
+                .transpose()?;
+            let max_bytes = match max_size.as_deref() {
+                Some(raw) => Some(za_config::parse_byte_size(raw)?),
+                None if !all && older_than.is_none() => Some(cache_max_bytes()?),
+                None => None,
+            };
+            let policy = CachePrunePolicy {
+                all,
+                older_than,
+                max_bytes,
+            };
+            prune_cache(&root, policy, dry_run)?;
+            Ok(0)
+        }
+    }
+}
+
+impl PartialDownload {
+    /// Claims the staging area for `meta.url`. Parts left by an earlier attempt are kept when its
+    /// metadata matches and discarded otherwise. Returns `None` while another process downloads
+    /// the same URL.
+    pub(super) fn open(root: &Path, meta: &PartialDownloadMeta) -> Result<Option<Self>> {
+        let dir = partial_download_dir(root, &meta.url);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("create staging dir {}", dir.display()))?;
+        let lock_path = dir.join(PARTIAL_LOCK_FILE);
+        let lock = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(&lock_path)
+            .with_context(|| format!("open lock file {}", lock_path.display()))?;
+        match lock.try_lock() {
+            Ok(()) => {}
+            Err(fs::TryLockError::WouldBlock) => return Ok(None),
+            Err(fs::TryLockError::Error(err)) => {
+                return Err(err).with_context(|| format!("acquire lock {}", lock_path.display()));
+            }
+        }
+
+        let meta_path = dir.join(PARTIAL_META_FILE);
+        let previous = fs::read(&meta_path)
+            .ok()
+            .and_then(|raw| serde_json::from_slice::<PartialDownloadMeta>(&raw).ok());
+        let resumed = previous.as_ref() == Some(meta);
+        if !resumed {
+            for entry in
+                fs::read_dir(&dir).with_context(|| format!("read dir {}", dir.display()))?
+            {
+                let path = entry?.path();
+                let is_part = path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(PARTIAL_PART_PREFIX));
+                if is_part {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("remove stale part {}", path.display()))?;
+                }
+            }
+        }
+        // Rewritten on every attempt so `cache prune --older-than` measures from the latest one.
+        let content = serde_json::to_vec_pretty(meta).context("serialize download metadata")?;
+        write_file_atomically(&meta_path, content)
+            .with_context(|| format!("write {}", meta_path.display()))?;
+        Ok(Some(Self {
+            dir,
+            etag: meta.etag.clone(),
+            resumed,
+            lock,
+        }))
+    }
+
+    pub(super) fn part_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("{PARTIAL_PART_PREFIX}{index}"))
+    }
+
+    pub(super) fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// Whether parts from an earlier attempt were kept.
+    pub(super) fn resumed(&self) -> bool {
+        self.resumed
+    }
+
+    /// Removes the staging area once its parts are merged or can no longer be resumed.
+    pub(super) fn discard(self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+impl Drop for PartialDownload {
+    fn drop(&mut self) {
+        let _ = self.lock.unlock();
+    }
+}
+
+/// Copies a cached asset to `dst`, returning `None` when the digest is unknown or the cached copy
+/// no longer hashes to it. Corrupt entries are evicted so the caller downloads a fresh copy.
+pub(super) fn restore_cached_artifact(
+    root: &Path,
+    sha256: &str,
+    dst: &Path,
+) -> Result<Option<CachedArtifact>> {
+    let sha256 = sha256.to_ascii_lowercase();
+    if !is_cache_digest(&sha256) {
+        return Ok(None);
+    }
+    let Some(mut entry) = read_cache_entry(root, &sha256)? else {
+        return Ok(None);
+    };
+    let cached = cache_entry_dir(root, &sha256)?.join(&entry.file_name);
+    if !cached.is_file() {
+        remove_cache_entry(root, &sha256)?;
+        return Ok(None);
+    }
+    fs::copy(&cached, dst).with_context(|| {
+        format!(
+            "copy cached asset {} to {}",
+            cached.display(),
+            dst.display()
+        )
+    })?;
+    if sha256_file(dst)? != sha256 {
+        let _ = fs::remove_file(dst);
+        remove_cache_entry(root, &sha256)?;
+        return Ok(None);
+    }
+    entry.last_used_unix_secs = now_unix_secs();
+    write_cache_entry(root, &entry)?;
+    Ok(Some(entry))
+}
+
+/// Whether an entry for `sha256` exists; the digest is only checked again when it is restored.
+pub(super) fn contains_artifact(root: &Path, sha256: &str) -> bool {
+    let sha256 = sha256.to_ascii_lowercase();
+    is_cache_digest(&sha256)
+        && read_cache_entry(root, &sha256).is_ok_and(|entry| {
+            entry.is_some_and(|entry| {
+                cache_entry_dir(root, &sha256).is_ok_and(|dir| dir.join(entry.file_name).is_file())
+            })
+        })
+}
+
+/// Adds an asset whose digest was verified to the cache, then evicts least recently used entries
+/// over the size cap. Unverified downloads are never cached.
+pub(super) fn store_artifact(
+    root: &Path,
+    asset_path: &Path,
+    url: &str,
+    sha256: &str,
+) -> Result<CachedArtifact> {
+    let sha256 = sha256.to_ascii_lowercase();
+    let file_name = asset_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow!("asset path {} has no file name", asset_path.display()))?;
+    let dir = cache_entry_dir(root, &sha256)?;
+    fs::create_dir_all(&dir).with_context(|| format!("create cache dir {}", dir.display()))?;
+    let cached = dir.join(&file_name);
+    if !cached.is_file() {
+        let tmp = dir.join(format!(
+            ".{file_name}{CACHE_ENTRY_TMP_MARKER}{}",
+            std::process::id()
+        ));
+        fs::copy(asset_path, &tmp)
+            .with_context(|| format!("copy {} into cache", asset_path.display()))?;
+        fs::rename(&tmp, &cached)
+            .with_context(|| format!("move cached asset into {}", cached.display()))?;
+    }
+    let now = now_unix_secs();
+    let releases = read_cache_entry(root, &sha256)?
+        .map(|entry| entry.releases)
+        .unwrap_or_default();
+    let entry = CachedArtifact {
+        releases,
+        sha256,
+        file_name,
+        url: url.to_string(),
+        size_bytes: fs::metadata(&cached)
+            .with_context(|| format!("stat {}", cached.display()))?
+            .len(),
+        stored_at_unix_secs: now,
+        last_used_unix_secs: now,
+    };
+    write_cache_entry(root, &entry)?;
+
+    let entries = list_cache_entries(root)?;
+    let evicted = select_prunable_entries(
+        &entries,
+        CachePrunePolicy {
+            max_bytes: Some(cache_max_bytes()?),
+            ..CachePrunePolicy::default()
+        },
+        now,
+    );
+    for stale in evicted.iter().filter(|stale| stale.sha256 != entry.sha256) {
+        remove_cache_entry(root, &stale.sha256)?;
+    }
+    Ok(entry)
+}
+
+/// Notes that `release` was installed from the cached asset `sha256`; a no-op when the asset is
+/// not cached.
+pub(super) fn record_cached_release(
+    root: &Path,
+    sha256: &str,
+    release: CachedRelease,
+) -> Result<()> {
+    let sha256 = sha256.to_ascii_lowercase();
+    if !is_cache_digest(&sha256) {
+        return Ok(());
+    }
+    let Some(mut entry) = read_cache_entry(root, &sha256)? else {
+        return Ok(());
+    };
+    if entry.releases.contains(&release) {
+        return Ok(());
+    }
+    entry.releases.push(release);
+    write_cache_entry(root, &entry)
+}
+
+/// Cached asset recorded for `release`, most recently used first; the digest is only checked
+/// again when it is restored.
+pub(super) fn find_cached_release(root: &Path, release: &CachedRelease) -> Option<ReleaseAsset> {
+    list_cache_entries(root)
+        .ok()?
+        .into_iter()
+        .find(|entry| {
+            entry.releases.contains(release)
+                && cache_entry_dir(root, &entry.sha256)
+                    .is_ok_and(|dir| dir.join(&entry.file_name).is_file())
+        })
+        .map(|entry| ReleaseAsset {
+            url: entry.url,
+            sha256: entry.sha256,
+        })
+}
+
+/// Cached entries ordered from most to least recently used.
+pub(super) fn list_cache_entries(root: &Path) -> Result<Vec<CachedArtifact>> {
+    let mut entries = Vec::new();
+    for sha256 in collect_dir_names(&root.join(CACHE_DIGEST_DIR))? {
+        if !is_cache_digest(&sha256) {
+            continue;
+        }
+        if let Some(entry) = read_cache_entry(root, &sha256)? {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by(|a, b| {
+        b.last_used_unix_secs
+            .cmp(&a.last_used_unix_secs)
+            .then_with(|| a.file_name.cmp(&b.file_name))
+    });
+    Ok(entries)
+}
+
+/// Picks entries to remove: everything for `all`, otherwise entries unused for longer than
+/// `older_than` plus the least recently used entries that push the total over `max_bytes`.
+pub(super) fn select_prunable_entries(
+    entries: &[CachedArtifact],
+    policy: CachePrunePolicy,
+    now_unix_secs: u64,
+) -> Vec<CachedArtifact> {
+    if policy.all {
+        return entries.to_vec();
+    }
+    let mut kept = entries.to_vec();
+    kept.sort_by_key(|entry| std::cmp::Reverse(entry.last_used_unix_secs));
+    let mut pruned = Vec::new();
+    if let Some(older_than) = policy.older_than {
+        let cutoff = now_unix_secs.saturating_sub(older_than.as_secs());
+        let (stale, fresh) = kept
+            .into_iter()
+            .partition::<Vec<_>, _>(|entry| entry.last_used_unix_secs < cutoff);
+        pruned.extend(stale);
+        kept = fresh;
+    }
+    if let Some(max_bytes) = policy.max_bytes {
+        let mut total = 0u64;
+        for entry in kept {
+            total = total.saturating_add(entry.size_bytes);
+            if total > max_bytes {
+                pruned.push(entry);
+            }
+        }
+    }
+    pruned
+}
+
+/// Size cap applied after every cache write: `ZA_TOOL_CACHE_MAX_SIZE`, then the
+/// `tool-cache-max-size` config key, then the default.
+fn cache_max_bytes() -> Result<u64> {
+    match env::var(TOOL_CACHE_MAX_SIZE_ENV) {
+        Ok(raw) if !raw.trim().is_empty() => za_config::parse_byte_size(&raw)
+            .with_context(|| format!("read `{TOOL_CACHE_MAX_SIZE_ENV}`")),
+        _ => Ok(za_config::load_tool_cache_max_bytes()?.unwrap_or(TOOL_CACHE_MAX_BYTES_DEFAULT)),
+    }
+}
+
+fn prune_cache(root: &Path, policy: CachePrunePolicy, dry_run: bool) -> Result<()> {
+    prune_partial_downloads(root, policy, dry_run)?;
+    let entries = list_cache_entries(root)?;
+    let pruned = select_prunable_entries(&entries, policy, now_unix_secs());
+    let freed = pruned.iter().map(|entry| entry.size_bytes).sum::<u64>();
+    for entry in &pruned {
+        if dry_run {
+            println!(
+                "Would remove {} ({})",
+                entry.file_name,
+                short_digest(&entry.sha256)
+            );
+        } else {
+            remove_cache_entry(root, &entry.sha256)?;
+        }
+    }
+    let noun = text_render::pluralize(pruned.len(), "cached asset", "cached assets");
+    if dry_run {
+        println!(
+            "Would remove {} {noun} ({})",
+            pruned.len(),
+            format_bytes_u64(freed)
+        );
+    } else {
+        println!(
+            "🧹 Removed {} {noun} ({})",
+            pruned.len(),
+            format_bytes_u64(freed)
+        );
+    }
+    Ok(())
+}
+
+/// Drops staged parts of interrupted downloads for `--all`, or once untouched for `--older-than`.
+/// Staging areas locked by a running download are left alone.
+fn prune_partial_downloads(root: &Path, policy: CachePrunePolicy, dry_run: bool) -> Result<()> {
+    if !policy.all && policy.older_than.is_none() {
+        return Ok(());
+    }
+    let partial_root = root.join(CACHE_PARTIAL_DIR);
+    let cutoff = policy
+        .older_than
+        .and_then(|older_than| SystemTime::now().checked_sub(older_than));
+    let mut removed = 0usize;
+    for key in collect_dir_names(&partial_root)? {
+        let dir = partial_root.join(&key);
+        let modified = fs::metadata(dir.join(PARTIAL_META_FILE))
+            .and_then(|meta| meta.modified())
+            .ok();
+        let fresh = cutoff.is_none_or(|cutoff| modified.is_some_and(|at| at >= cutoff));
+        if !policy.all && fresh {
+            continue;
+        }
+        let Ok(lock) = File::open(dir.join(PARTIAL_LOCK_FILE)) else {
+            continue;
+        };
+        if lock.try_lock().is_err() {
+            continue;
+        }
+        if !dry_run {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("remove staging dir {}", dir.display()))?;
+        }
+        removed += 1;
+    }
+    if removed > 0 {
+        let noun = text_render::pluralize(removed, "interrupted download", "interrupted downloads");
+        if dry_run {
+            println!("Would remove {removed} {noun}");
+        } else {
+            println!("🧹 Removed {removed} {noun}");
+        }
+    }
+    Ok(())
+}
+
+fn print_cache_entries_text(root: &Path, entries: &[CachedArtifact]) {
+    if entries.is_empty() {
+        println!("Tool download cache is empty: {}", root.display());
+        return;
+    }
+    println!("{:<14} {:>10} {:<20} ASSET", "SHA256", "SIZE", "LAST USED");
+    for entry in entries {
+        println!(
+            "{:<14} {:>10} {:<20} {}",
+            short_digest(&entry.sha256),
+            format_bytes_u64(entry.size_bytes),
+            format_unix_secs(entry.last_used_unix_secs),
+            entry.file_name
+        );
+    }
+    let total = entries.iter().map(|entry| entry.size_bytes).sum::<u64>();
+    println!(
+        "\n{} {} · {} in {}",
+        entries.len(),
+        text_render::pluralize(entries.len(), "entry", "entries"),
+        format_bytes_u64(total),
+        root.display()
+    );
+}
+
+fn print_cache_entries_json(root: &Path, entries: &[CachedArtifact]) -> Result<()> {
+    let payload = CacheListJson {
+        dir: root.display().to_string(),
+        total_bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+        max_bytes: cache_max_bytes()?,
+        entries,
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&payload).context("serialize tool cache JSON")?
+    );
+    Ok(())
+}
+
+/// Staging directory for `url`, keyed by the SHA-256 of the URL itself.
+fn partial_download_dir(root: &Path, url: &str) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    let digest = hasher.finish();
+    let mut key = String::with_capacity(digest.as_ref().len() * 2);
+    for byte in digest.as_ref() {
+        let _ = write!(key, "{byte:02x}");
+    }
+    root.join(CACHE_PARTIAL_DIR).join(key)
+}
+
+/// Entry directory for `sha256`. Digests come from lockfiles and entry files, so anything but
+/// 64 lowercase hex characters is refused before it is joined into a path.
+fn cache_entry_dir(root: &Path, sha256: &str) -> Result<PathBuf> {
+    if !is_cache_digest(sha256) {
+        bail!("invalid cache digest `{sha256}`; expected 64 lowercase hex characters");
+    }
+    Ok(root.join(CACHE_DIGEST_DIR).join(sha256))
+}
+
+fn is_cache_digest(value: &str) -> bool {
+    value.len() == 64
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Cached file names come from entry files and must stay inside their entry directory.
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name != CACHE_ENTRY_FILE
+        && !name.contains(['/', '\\'])
+}
+
+fn read_cache_entry(root: &Path, sha256: &str) -> Result<Option<CachedArtifact>> {
+    let path = cache_entry_dir(root, sha256)?.join(CACHE_ENTRY_FILE);
+    let raw = match fs::read(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+    };
+    // An unreadable entry is treated as a miss; the next store for this digest rewrites it.
+    Ok(serde_json::from_slice::<CachedArtifact>(&raw)
+        .ok()
+        .filter(|entry| entry.sha256 == sha256 && is_plain_file_name(&entry.file_name)))
+}
+
+fn write_cache_entry(root: &Path, entry: &CachedArtifact) -> Result<()> {
+    let path = cache_entry_dir(root, &entry.sha256)?.join(CACHE_ENTRY_FILE);
+    let content = serde_json::to_vec_pretty(entry).context("serialize cache entry")?;
+    write_file_atomically(&path, content).with_context(|| format!("write {}", path.display()))
+}
+
+fn remove_cache_entry(root: &Path, sha256: &str) -> Result<()> {
+    let dir = cache_entry_dir(root, sha256)?;
+    match fs::remove_dir_all(&dir) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("remove cache entry {}", dir.display())),
+    }
+}
+
+fn short_digest(sha256: &str) -> &str {
+    &sha256[..sha256.len().min(12)]
+}
+
+fn format_unix_secs(secs: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(secs)).to_string()
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -0,0 +1,193 @@
+//! Shell completions for managed tools, installed through the same `shellcomp` activation as
+//! `za completion install`.
+
+use super::integrations::shell_is_configured;
+use super::policy::{CompletionSource, IntegrationShell};
+use super::*;
+use crate::command::completion::{install_program_completion, uninstall_program_completion};
+use shellcomp::{FileChange, Shell as ShellcompShell};
+use std::process::Command;
+
+/// Completion files copied out of the release archive live here inside the version dir.
+const COMPLETIONS_DIR: &str = "completions";
+
+impl From<IntegrationShell> for ShellcompShell {
+    fn from(shell: IntegrationShell) -> Self {
+        match shell {
+            IntegrationShell::Bash => Self::Bash,
+            IntegrationShell::Zsh => Self::Zsh,
+            IntegrationShell::Fish => Self::Fish,
+        }
+    }
+}
+
+fn completion_source(name: &str) -> Option<CompletionSource> {
+    find_tool_policy(name).and_then(|policy| policy.completions)
+}
+
+/// Keeps the completion files a release archive ships so activation can install them later.
+pub(super) fn stage_archived_completions(
+    home: &ToolHome,
+    tool: &ToolRef,
+    source: &PullSource,
+) -> Result<()> {
+    let (Some(completions), Some(unpack_root)) = (
+        completion_source(&tool.name),
+        source.cleanup_root.as_deref(),
+    ) else {
+        return Ok(());
+    };
+    let wanted = IntegrationShell::ALL
+        .into_iter()
+        .filter_map(|shell| completions.archive_file(shell))
+        .collect::<Vec<_>>();
+    if wanted.is_empty() {
+        return Ok(());
+    }
+
+    let mut files = Vec::new();
+    source::collect_files_recursive(unpack_root, &mut files)?;
+    files.sort();
+    let dst_dir = home.version_dir(tool).join(COMPLETIONS_DIR);
+    for name in wanted {
+        let Some(src) = files
+            .iter()
+            .find(|path| path.file_name().and_then(|file| file.to_str()) == Some(name))
+        else {
+            continue;
+        };
+        fs::create_dir_all(&dst_dir).with_context(|| format!("create {}", dst_dir.display()))?;
+        let dst = dst_dir.join(name);
+        fs::copy(src, &dst)
+            .with_context(|| format!("copy {} -> {}", src.display(), dst.display()))?;
+    }
+    Ok(())
+}
+
+pub(super) fn ensure_tool_completions(
+    home: &ToolHome,
+    tool: &ToolRef,
+    emit_stages: bool,
+) -> Result<()> {
+    let Some(completions) = completion_source(&tool.name) else {
+        return Ok(());
+    };
+    let mut installed = Vec::new();
+    for shell in IntegrationShell::ALL {
+        if !shell_is_configured(shell)? {
+            continue;
+        }
+        // Completions are a convenience; a tool that cannot provide them still installs fine.
+        let result = completion_script(home, tool, completions, shell).and_then(|script| {
+            script
+                .map(|script| install_program_completion(shell.into(), &tool.name, &script))
+                .transpose()
+        });
+        match result {
+            Ok(Some(_)) => installed.push(shell.name()),
+            Ok(None) => {}
+            Err(err) => print_tool_stage_if(
+                emit_stages,
+                "next",
+                format!(
+                    "skipped {} {} completions: {err:#}",
+                    tool.name,
+                    shell.name()
+                ),
+            ),
+        }
+    }
+    if !installed.is_empty() {
+        print_tool_stage_if(
+            emit_stages,
+            "next",
+            format!(
+                "{} completions installed for {}; open a new shell to pick them up",
+                tool.name,
+                installed.join(", ")
+            ),
+        );
+    }
+    Ok(())
+}
+
+pub(super) fn preview_tool_completions(tool: &ToolRef, emit_stages: bool) -> Result<()> {
+    if completion_source(&tool.name).is_none() {
+        return Ok(());
+    }
+    let mut shells = Vec::new();
+    for shell in IntegrationShell::ALL {
+        if shell_is_configured(shell)? {
+            shells.push(shell.name());
+        }
+    }
+    if !shells.is_empty() {
+        print_tool_stage_if(
+            emit_stages,
+            "next",
+            format!(
+                "{} completions would be installed for {}",
+                tool.name,
+                shells.join(", ")
+            ),
+        );
+    }
+    Ok(())
+}
+
+pub(super) fn remove_tool_completions(name: &str) -> Result<()> {
+    if completion_source(name).is_none() {
+        return Ok(());
+    }
+    let mut removed = Vec::new();
+    for shell in IntegrationShell::ALL {
+        let report = uninstall_program_completion(shell.into(), name)?;
+        if report.file_change == FileChange::Removed {
+            removed.push(shell.name());
+        }
+    }
+    if !removed.is_empty() {
+        print_tool_stage(
+            "next",
+            format!("removed {name} completions for {}", removed.join(", ")),
+        );
+    }
+    Ok(())
+}
+
+/// The completion script for `shell`, or `None` when this version does not ship one.
+fn completion_script(
+    home: &ToolHome,
+    tool: &ToolRef,
+    completions: CompletionSource,
+    shell: IntegrationShell,
+) -> Result<Option<Vec<u8>>> {
+    match completions {
+        CompletionSource::Archive { .. } => {
+            let Some(name) = completions.archive_file(shell) else {
+                return Ok(None);
+            };
+            let path = home.version_dir(tool).join(COMPLETIONS_DIR).join(name);
+            match fs::read(&path) {
+                Ok(script) => Ok(Some(script)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).with_context(|| format!("read {}", path.display())),
+            }
+        }
+        CompletionSource::Generate { args } => {
+            let program = home.install_path(tool);
+            let output = Command::new(&program)
+                .args(args.iter().map(|arg| arg.replace("{shell}", shell.name())))
+                .output()
+                .with_context(|| format!("run {}", program.display()))?;
+            if !output.status.success() || output.stdout.is_empty() {
+                bail!(
+                    "`{} {}` did not print a completion script",
+                    tool.name,
+                    args.join(" ").replace("{shell}", shell.name())
+                );
+            }
+            Ok(Some(output.stdout))
+        }
+    }
+}
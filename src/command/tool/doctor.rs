@@ -19,6 +19,14 @@ impl DoctorStatus {
     }
 }
 
+/// How `za tool doctor --fix` treats the repairs it finds.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum DoctorFixMode {
+    Apply,
+    DryRun,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DoctorIssueSeverity {
     Warn,
@@ -39,6 +47,8 @@ struct ToolDoctorRow {
     install_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     manifest_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -56,15 +66,92 @@ struct ToolDoctorReport {
     bin_dir: String,
     summary: ToolDoctorSummary,
     rows: Vec<ToolDoctorRow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<DoctorFixMode>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cleanup: Vec<String>,
+}
+
+/// A repair `za tool doctor --fix` can make without fetching anything.
+#[derive(Debug, Clone)]
+enum DoctorFix {
+    RelinkActive(ToolRef),
+    /// Writes a synthesized manifest where none exists.
+    RegenerateManifest(ToolRef),
+    /// Corrects the name and version of a parseable manifest, keeping every other recorded field.
+    RepairManifest(ToolRef),
+    RemoveOrphanedVersion(ToolRef),
+}
+
+impl DoctorFix {
+    fn apply(&self, home: &ToolHome) -> Result<()> {
+        match self {
+            Self::RelinkActive(tool) => activate_tool(home, tool),
+            Self::RegenerateManifest(tool) => ensure_manifest(home, tool),
+            Self::RepairManifest(tool) => repair_manifest_identity(home, tool),
+            Self::RemoveOrphanedVersion(tool) => {
+                let version_dir = home.version_dir(tool);
+                fs::remove_dir_all(&version_dir)
+                    .with_context(|| format!("remove {}", version_dir.display()))
+            }
+        }
+    }
+
+    fn describe(&self, home: &ToolHome, mode: DoctorFixMode) -> String {
+        let (done, planned, target) = match self {
+            Self::RelinkActive(tool) => (
+                "re-linked",
+                "would re-link",
+                format!("active entry {}", home.active_path(&tool.name).display()),
+            ),
+            Self::RegenerateManifest(tool) => (
+                "regenerated",
+                "would regenerate",
+                format!("manifest {}", home.manifest_path(tool).display()),
+            ),
+            Self::RepairManifest(tool) => (
+                "repaired",
+                "would repair",
+                format!("manifest {}", home.manifest_path(tool).display()),
+            ),
+            Self::RemoveOrphanedVersion(tool) => (
+                "removed",
+                "would remove",
+                format!("orphaned version dir {}", home.version_dir(tool).display()),
+            ),
+        };
+        match mode {
+            DoctorFixMode::Apply => format!("{done} {target}"),
+            DoctorFixMode::DryRun => format!("{planned} {target}"),
+        }
+    }
 }
 
-pub(super) fn run_doctor(home: &ToolHome, tools: &[String], json: bool) -> Result<i32> {
+pub(super) fn run_doctor(
+    home: &ToolHome,
+    tools: &[String],
+    json: bool,
+    fix: Option<DoctorFixMode>,
+) -> Result<i32> {
     let names = doctor_target_names(home, tools)?;
     let mut rows = Vec::with_capacity(names.len());
     let mut summary = ToolDoctorSummary::default();
+    let cleanup = match fix {
+        Some(mode) => fix_leftovers(home, mode)?,
+        None => Vec::new(),
+    };
+    let admin_policy = admin::load_admin_policy(home).unwrap_or_else(|err| {
+        eprintln!("warning: admin tool policy not checked: {err:#}");
+        None
+    });
 
     for name in names {
-        let row = inspect_tool(home, &name)?;
+        let fixes = match fix {
+            Some(mode) => fix_tool(home, &name, mode)?,
+            None => Vec::new(),
+        };
+        let mut row = inspect_tool(home, &name, admin_policy.as_ref())?;
+        row.fixes = fixes;
         match row.status {
             DoctorStatus::Ok => summary.ok += 1,
             DoctorStatus::Warn => summary.warn += 1,
@@ -86,6 +173,8 @@ pub(super) fn run_doctor(home: &ToolHome, tools: &[String], json: bool) -> Resul
         bin_dir: home.bin_dir.display().to_string(),
         summary,
         rows,
+        fix,
+        cleanup,
     };
 
     if json {
@@ -118,7 +207,148 @@ fn doctor_target_names(home: &ToolHome, tools: &[String]) -> Result<Vec<String>>
     Ok(names)
 }
 
-fn inspect_tool(home: &ToolHome, name: &str) -> Result<ToolDoctorRow> {
+/// Applies (or, in dry-run mode, only lists) the safe repairs for one tool.
+pub(super) fn fix_tool(home: &ToolHome, name: &str, mode: DoctorFixMode) -> Result<Vec<String>> {
+    let mut changes = Vec::new();
+    for fix in plan_tool_fixes(home, name)? {
+        if mode == DoctorFixMode::Apply {
+            fix.apply(home)?;
+        }
+        changes.push(fix.describe(home, mode));
+    }
+    Ok(changes)
+}
+
+fn plan_tool_fixes(home: &ToolHome, name: &str) -> Result<Vec<DoctorFix>> {
+    let mut fixes = Vec::new();
+    // An unreadable current file needs a human; leave it to the diagnosis.
+    let active_version = read_current_version(home, name)
+        .ok()
+        .flatten()
+        .map(|version| normalize_version(&version));
+
+    if let Some(version) = active_version.as_deref() {
+        let tool = ToolRef {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        let is_package = package_policy_for_name(name).is_some();
+        let payload_path = home.install_path(&tool);
+        // Without a usable payload the only repair is a reinstall, which doctor does not do.
+        if payload_path.exists() && (is_package || is_executable_file(&payload_path)) {
+            let active_path = home.active_path(name);
+            let active_broken =
+                !active_path.exists() || (!is_package && !is_executable_file(&active_path));
+            let exposed_stale =
+                stale_exposed_links(home, &tool).is_ok_and(|stale| !stale.is_empty());
+            if active_broken || exposed_stale {
+                fixes.push(DoctorFix::RelinkActive(tool.clone()));
+            }
+            match manifest_state(home, &tool) {
+                ManifestState::Missing => fixes.push(DoctorFix::RegenerateManifest(tool)),
+                ManifestState::Mismatched => fixes.push(DoctorFix::RepairManifest(tool)),
+                ManifestState::Healthy | ManifestState::Unusable => {}
+            }
+        }
+    }
+
+    // Other projects may still point at versions in a shared store.
+    if !home.shares_store() {
+        let mut versions = collect_dir_names(&home.name_dir(name))?;
+        versions.sort();
+        for version in versions {
+            if active_version.as_deref() == Some(normalize_version(&version).as_str()) {
+                continue;
+            }
+            let tool = ToolRef {
+                name: name.to_string(),
+                version,
+            };
+            if !home.install_path(&tool).exists() {
+                fixes.push(DoctorFix::RemoveOrphanedVersion(tool));
+            }
+        }
+    }
+
+    Ok(fixes)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ManifestState {
+    Healthy,
+    Missing,
+    /// Parseable, but recorded under another name or version.
+    Mismatched,
+    /// Unreadable or unparseable. Rewriting it would drop the recorded asset, target and range,
+    /// so doctor only reports it.
+    Unusable,
+}
+
+fn manifest_state(home: &ToolHome, tool: &ToolRef) -> ManifestState {
+    match fs::read_to_string(home.manifest_path(tool)) {
+        Ok(raw) => match serde_json::from_str::<ToolManifest>(&raw) {
+            Ok(manifest) if manifest_identity_matches(&manifest, tool) => ManifestState::Healthy,
+            Ok(_) => ManifestState::Mismatched,
+            Err(_) => ManifestState::Unusable,
+        },
+        Err(err) if err.kind() == io::ErrorKind::NotFound => ManifestState::Missing,
+        Err(_) => ManifestState::Unusable,
+    }
+}
+
+fn manifest_identity_matches(manifest: &ToolManifest, tool: &ToolRef) -> bool {
+    manifest.name == tool.name
+        && normalize_version(&manifest.version) == normalize_version(&tool.version)
+}
+
+fn repair_manifest_identity(home: &ToolHome, tool: &ToolRef) -> Result<()> {
+    let Some(mut manifest) = read_manifest(home, tool)? else {
+        return Ok(());
+    };
+    manifest.name = tool.name.clone();
+    manifest.version = tool.version.clone();
+    let manifest_path = home.manifest_path(tool);
+    let content = serde_json::to_vec_pretty(&manifest).context("serialize tool manifest")?;
+    write_file_atomically(&manifest_path, content)
+        .with_context(|| format!("write manifest {}", manifest_path.display()))
+}
+
+/// Scope-wide leftovers: legacy current-dir artifacts and temp dirs from interrupted runs.
+fn fix_leftovers(home: &ToolHome, mode: DoctorFixMode) -> Result<Vec<String>> {
+    let (artifacts, temp_dirs) = match mode {
+        DoctorFixMode::Apply => (
+            remove_legacy_current_dir_artifacts(home)?,
+            source::cleanup_stale_temp_dirs(),
+        ),
+        DoctorFixMode::DryRun => (
+            find_legacy_current_dir_artifacts(home)?.len(),
+            source::find_stale_temp_dirs().len(),
+        ),
+    };
+    let verb = match mode {
+        DoctorFixMode::Apply => "removed",
+        DoctorFixMode::DryRun => "would remove",
+    };
+    let mut changes = Vec::new();
+    if artifacts > 0 {
+        changes.push(format!(
+            "{verb} {artifacts} legacy tool state artifact(s) from {}",
+            home.current_dir.display()
+        ));
+    }
+    if temp_dirs > 0 {
+        changes.push(format!(
+            "{verb} {temp_dirs} stale temp dir(s) from previous interrupted runs"
+        ));
+    }
+    Ok(changes)
+}
+
+fn inspect_tool(
+    home: &ToolHome,
+    name: &str,
+    admin_policy: Option<&admin::AdminToolPolicy>,
+) -> Result<ToolDoctorRow> {
     let current_file = home.current_file(name);
     let active_path = home.active_path(name);
     let current_file_display = current_file.display().to_string();
@@ -192,7 +422,30 @@ fn inspect_tool(home: &ToolHome, name: &str) -> Result<ToolDoctorRow> {
                     format!("active path is not executable: {}", active_path.display()),
                 ));
             }
+            if payload_path.exists() {
+                match stale_exposed_links(home, &tool) {
+                    Ok(stale) => issues.extend(stale.into_iter().map(|link| {
+                        (
+                            DoctorIssueSeverity::Error,
+                            format!(
+                                "exposed entry link missing or stale: {}; repair with `za tool doctor --fix`",
+                                link.display()
+                            ),
+                        )
+                    })),
+                    Err(err) => issues.push((
+                        DoctorIssueSeverity::Error,
+                        format!("exposed entries not checked: {err:#}"),
+                    )),
+                }
+            }
             inspect_manifest(home, &tool, &mut issues)?;
+            if let Some(admin_policy) = admin_policy {
+                let source = admin::installed_tool_source(home, &tool);
+                for violation in admin_policy.violations(name, version, source) {
+                    issues.push((DoctorIssueSeverity::Error, violation));
+                }
+            }
         }
         None => {
             if installed_versions > 0 {
@@ -230,6 +483,7 @@ fn inspect_tool(home: &ToolHome, name: &str) -> Result<ToolDoctorRow> {
         active_path: active_path_display,
         install_path,
         manifest_path,
+        fixes: Vec::new(),
     })
 }
 
@@ -282,7 +536,10 @@ fn inspect_manifest(
         }
         Err(err) => issues.push((
             DoctorIssueSeverity::Error,
-            format!("manifest invalid: {} ({err})", manifest_path.display()),
+            format!(
+                "manifest invalid: {} ({err}); fix or remove it by hand, `--fix` leaves it alone",
+                manifest_path.display()
+            ),
         )),
     }
 
@@ -323,6 +580,7 @@ fn render_doctor_lines(report: &ToolDoctorReport) -> Vec<String> {
 
     if report.rows.is_empty() {
         lines.push(tty_style::dim("No za-managed tools found in this scope."));
+        push_doctor_fix_lines(report, &mut lines);
         return lines;
     }
 
@@ -362,9 +620,44 @@ fn render_doctor_lines(report: &ToolDoctorReport) -> Vec<String> {
         ));
     }
 
+    push_doctor_fix_lines(report, &mut lines);
     lines
 }
 
+fn push_doctor_fix_lines(report: &ToolDoctorReport, lines: &mut Vec<String>) {
+    let Some(mode) = report.fix else {
+        return;
+    };
+    lines.push(String::new());
+    lines.push(tty_style::header(match mode {
+        DoctorFixMode::Apply => "fixes",
+        DoctorFixMode::DryRun => "fixes (dry run)",
+    }));
+    let changes = report
+        .cleanup
+        .iter()
+        .map(|change| ("scope", change))
+        .chain(report.rows.iter().flat_map(|row| {
+            row.fixes
+                .iter()
+                .map(move |change| (row.name.as_str(), change))
+        }))
+        .collect::<Vec<_>>();
+    if changes.is_empty() {
+        lines.push(tty_style::dim("Nothing to fix."));
+        return;
+    }
+    let width = changes
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(5)
+        .clamp(5, 24);
+    for (label, change) in changes {
+        lines.push(format!("{label:<width$}  {change}"));
+    }
+}
+
 fn render_doctor_summary(summary: &ToolDoctorSummary) -> String {
     let mut parts = Vec::new();
     if summary.error > 0 {
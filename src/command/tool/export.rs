@@ -0,0 +1,422 @@
+//! `za tool export`: turns the active tool set into an artifact that reproduces it elsewhere.
+
+use super::lockfile::{
+    TOOL_SYNC_LOCKFILE_VERSION, ToolSyncLockEntry, ToolSyncLockfile, sync_lockfile_path,
+    write_sync_lockfile,
+};
+use super::*;
+use crate::cli::ToolExportFormat;
+
+const EXPORT_DOCKER_BIN_DIR: &str = "/opt/za/bin";
+const EXPORT_DOCKER_SHARE_DIR: &str = "/opt/za/share";
+const EXPORT_DOCKER_CLI_PLUGINS_DIR: &str = "/opt/za/cli-plugins";
+
+/// One active tool version as recorded in its store manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ToolExportEntry {
+    pub(super) name: String,
+    pub(super) version: String,
+    pub(super) target: Option<String>,
+    pub(super) asset: Option<ReleaseAsset>,
+    /// Entry path inside the payload for package-layout tools.
+    pub(super) package_entry: Option<&'static str>,
+    /// Extra entries linked into the bin or Docker CLI plugin directory, as activation does.
+    pub(super) exposed: &'static [ExposedEntry],
+    pub(super) source_kind: Option<String>,
+}
+
+pub(super) fn run_export(
+    home: &ToolHome,
+    tools: &[String],
+    format: ToolExportFormat,
+    output: Option<&Path>,
+) -> Result<i32> {
+    let entries = collect_export_entries(home, tools)?;
+    if entries.is_empty() {
+        bail!(
+            "no active za-managed tools to export in {} scope",
+            home.scope.label()
+        );
+    }
+    for entry in entries.iter().filter(|entry| entry.asset.is_none()) {
+        if format != ToolExportFormat::SyncManifest {
+            eprintln!(
+                "⚠️  Skipped {} {}: no recorded release asset ({}); reinstall it with `za tool install {}` to export it",
+                entry.name,
+                entry.version,
+                entry.source_kind.as_deref().unwrap_or("no manifest"),
+                entry.name
+            );
+        }
+    }
+    if format == ToolExportFormat::Dockerfile {
+        for entry in &entries {
+            if let Some(target) = entry.target.as_deref()
+                && !target.contains("linux")
+            {
+                eprintln!(
+                    "⚠️  {} {} was installed for {target}; the Dockerfile needs a Linux asset, so re-export it from a Linux host",
+                    entry.name, entry.version
+                );
+            }
+        }
+    }
+
+    let rendered = match format {
+        ToolExportFormat::Dockerfile => render_export_dockerfile(&entries),
+        ToolExportFormat::Sh => render_export_script(&entries, None),
+        ToolExportFormat::SyncManifest => render_export_sync_manifest(&entries)?,
+    };
+    let Some(output) = output else {
+        print!("{rendered}");
+        return Ok(0);
+    };
+    let lockfile = match format {
+        ToolExportFormat::SyncManifest => Some(export_sync_lockfile(&entries)?),
+        _ => None,
+    };
+
+    write_file_atomically(output, &rendered)
+        .with_context(|| format!("write {}", output.display()))?;
+    #[cfg(unix)]
+    if format == ToolExportFormat::Sh {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(output, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("chmod {}", output.display()))?;
+    }
+    println!(
+        "📤 Exported {} tool(s) to {}",
+        entries.len(),
+        output.display()
+    );
+    if let Some(lockfile) = lockfile {
+        let lockfile_path = sync_lockfile_path(output);
+        write_sync_lockfile(&lockfile_path, &lockfile)?;
+        println!(
+            "🔒 Wrote {}; reproduce with `za tool sync --file {} --locked`",
+            lockfile_path.display(),
+            output.display()
+        );
+    }
+    Ok(0)
+}
+
+pub(super) fn collect_export_entries(
+    home: &ToolHome,
+    tools: &[String],
+) -> Result<Vec<ToolExportEntry>> {
+    let explicit = !tools.is_empty();
+    let names = if explicit {
+        normalize_requested_tool_names(tools)?
+    } else {
+        collect_managed_tool_names(home)?
+    };
+
+    let mut entries = Vec::with_capacity(names.len());
+    for name in names {
+        let Some(version) = read_current_version(home, &name)? else {
+            if explicit {
+                bail!(
+                    "`{name}` has no active version in {} scope",
+                    home.scope.label()
+                );
+            }
+            continue;
+        };
+        let tool = ToolRef {
+            name: name.clone(),
+            version: normalize_version(&version),
+        };
+        let manifest = read_manifest(home, &tool)?;
+        let asset = manifest.as_ref().and_then(|manifest| {
+            Some(ReleaseAsset {
+                url: manifest.asset_url.clone()?,
+                sha256: manifest.asset_sha256.clone()?,
+            })
+        });
+        entries.push(ToolExportEntry {
+            package_entry: package_policy_for_name(&name).map(|package| package.entry_relpath),
+            exposed: exposed_entries_for_name(&name),
+            target: manifest
+                .as_ref()
+                .and_then(|manifest| manifest.target.clone()),
+            source_kind: manifest.map(|manifest| manifest.source_kind),
+            asset,
+            name,
+            version: tool.version,
+        });
+    }
+    Ok(entries)
+}
+
+pub(super) fn render_export_sync_manifest(entries: &[ToolExportEntry]) -> Result<String> {
+    let mut tools = toml::Table::new();
+    for entry in entries {
+        tools.insert(
+            entry.name.clone(),
+            toml::Value::String(entry.version.clone()),
+        );
+    }
+    let mut root = toml::Table::new();
+    root.insert("tools".to_string(), toml::Value::Table(tools));
+    let body = toml::to_string_pretty(&root).context("serialize sync manifest")?;
+    Ok(format!(
+        "# Generated by `za tool export --format sync-manifest`.\n# Install these exact versions with `za tool sync --file <this file>`.\n\n{body}"
+    ))
+}
+
+fn export_sync_lockfile(entries: &[ToolExportEntry]) -> Result<ToolSyncLockfile> {
+    let tools = entries
+        .iter()
+        .map(|entry| {
+            let Some(asset) = &entry.asset else {
+                bail!(
+                    "cannot lock {} {}: no recorded release asset ({}); reinstall it with `za tool install {}` or leave it out of the export",
+                    entry.name,
+                    entry.version,
+                    entry.source_kind.as_deref().unwrap_or("no manifest"),
+                    entry.name
+                );
+            };
+            Ok(ToolSyncLockEntry {
+                name: entry.name.clone(),
+                spec: ToolSpec::from_args(&entry.name, Some(&entry.version))?.display_spec(),
+                version: entry.version.clone(),
+                target: entry.target.clone(),
+                url: Some(asset.url.clone()),
+                sha256: Some(asset.sha256.clone()),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ToolSyncLockfile {
+        version: TOOL_SYNC_LOCKFILE_VERSION,
+        tools,
+    })
+}
+
+pub(super) fn render_export_dockerfile(entries: &[ToolExportEntry]) -> String {
+    let script = render_export_script(
+        entries,
+        Some((
+            EXPORT_DOCKER_BIN_DIR,
+            EXPORT_DOCKER_SHARE_DIR,
+            EXPORT_DOCKER_CLI_PLUGINS_DIR,
+        )),
+    );
+    format!(
+        r#"# syntax=docker/dockerfile:1
+# Generated by `za tool export --format dockerfile`.
+# Use it as a build stage and copy the tools into your own image:
+#   COPY --from=za-tools {EXPORT_DOCKER_BIN_DIR}/ /usr/local/bin/
+# Package tools and links to them also need {EXPORT_DOCKER_SHARE_DIR}/ and
+# {EXPORT_DOCKER_CLI_PLUGINS_DIR}/ (Docker CLI plugins) copied to the same paths.
+ARG ZA_EXPORT_BASE_IMAGE=debian:bookworm-slim
+FROM ${{ZA_EXPORT_BASE_IMAGE}} AS za-tools
+RUN if command -v apt-get >/dev/null 2>&1; then \
+      apt-get update \
+      && apt-get install -y --no-install-recommends bzip2 ca-certificates curl unzip xz-utils zstd \
+      && rm -rf /var/lib/apt/lists/*; \
+    elif command -v apk >/dev/null 2>&1; then \
+      apk add --no-cache bzip2 ca-certificates curl unzip xz zstd; \
+    fi
+RUN <<'ZA_EXPORT'
+{script}ZA_EXPORT
+ENV PATH="{EXPORT_DOCKER_BIN_DIR}:${{PATH}}"
+"#
+    )
+}
+
+/// POSIX `sh` installer; `dirs` bakes in the bin, share and Docker CLI plugin directories
+/// instead of env defaults.
+pub(super) fn render_export_script(
+    entries: &[ToolExportEntry],
+    dirs: Option<(&str, &str, &str)>,
+) -> String {
+    let (bin_dir, share_dir, cli_plugins_dir) = match dirs {
+        Some((bin_dir, share_dir, cli_plugins_dir)) => (
+            shell_single_quote(bin_dir),
+            shell_single_quote(share_dir),
+            shell_single_quote(cli_plugins_dir),
+        ),
+        None => (
+            "\"${ZA_EXPORT_BIN_DIR:-/usr/local/bin}\"".to_string(),
+            "\"${ZA_EXPORT_SHARE_DIR:-/usr/local/share/za}\"".to_string(),
+            format!(
+                "\"${{ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR:-{}}}\"",
+                paths::GLOBAL_DOCKER_CLI_PLUGINS_DIR
+            ),
+        ),
+    };
+    let mut out = format!(
+        r#"#!/bin/sh
+# Generated by `za tool export`; installs the exact tool versions below.
+# Every asset is checked against its recorded SHA-256 before anything is installed.
+set -eu
+
+ZA_EXPORT_BIN_DIR={bin_dir}
+ZA_EXPORT_SHARE_DIR={share_dir}
+ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR={cli_plugins_dir}
+{EXPORT_SCRIPT_FUNCTIONS}"#
+    );
+    for entry in entries {
+        let target = entry
+            .target
+            .as_deref()
+            .map(|target| format!(" ({target})"))
+            .unwrap_or_default();
+        out.push_str(&format!("\n# {} {}{target}\n", entry.name, entry.version));
+        let Some(asset) = &entry.asset else {
+            out.push_str(
+                "# skipped: no recorded release asset; reinstall it with za to export it\n",
+            );
+            continue;
+        };
+        let mut args = vec![
+            shell_single_quote(&entry.name),
+            shell_single_quote(&asset.url),
+            shell_single_quote(&asset.sha256),
+        ];
+        let function = match entry.package_entry {
+            Some(package_entry) => {
+                args.push(shell_single_quote(package_entry));
+                "za_export_package"
+            }
+            None => {
+                args.extend(
+                    command_candidates(&entry.name)
+                        .iter()
+                        .map(|candidate| shell_single_quote(candidate)),
+                );
+                "za_export_binary"
+            }
+        };
+        out.push_str(&format!("{function} {}\n", args.join(" ")));
+        for exposed in entry.exposed {
+            if let Some(link) = export_link_command(entry, exposed) {
+                out.push_str(&link);
+            }
+        }
+    }
+    out
+}
+
+/// `za_export_link` call placing one exposed entry where activation would link it.
+fn export_link_command(entry: &ToolExportEntry, exposed: &ExposedEntry) -> Option<String> {
+    let name = shell_single_quote(&entry.name);
+    let source = if exposed.relpath.is_empty() {
+        if entry.package_entry.is_some() {
+            return None;
+        }
+        format!("\"$ZA_EXPORT_BIN_DIR\"/{name}")
+    } else {
+        format!(
+            "\"$ZA_EXPORT_SHARE_DIR\"/{name}/{}",
+            shell_single_quote(exposed.relpath)
+        )
+    };
+    let dir = match exposed.placement {
+        EntryPlacement::Bin(link_name) => {
+            if exposed.relpath.is_empty() && link_name == entry.name {
+                return None;
+            }
+            "\"$ZA_EXPORT_BIN_DIR\""
+        }
+        EntryPlacement::DockerCliPlugin(_) => "\"$ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR\"",
+    };
+    Some(format!(
+        "za_export_link {source} {dir} {}\n",
+        shell_single_quote(exposed.placement.file_name())
+    ))
+}
+
+const EXPORT_SCRIPT_FUNCTIONS: &str = r#"
+za_export_fetch() {
+  # usage: za_export_fetch <url> <sha256> <dest>
+  if command -v curl >/dev/null 2>&1; then
+    curl -fsSL --retry 3 -o "$3" "$1"
+  else
+    wget -qO "$3" "$1"
+  fi
+  if command -v sha256sum >/dev/null 2>&1; then
+    actual=$(sha256sum "$3" | cut -d ' ' -f 1)
+  else
+    actual=$(shasum -a 256 "$3" | cut -d ' ' -f 1)
+  fi
+  if [ "$actual" != "$2" ]; then
+    echo "za export: SHA-256 mismatch for $1: expected $2, got $actual" >&2
+    exit 1
+  fi
+}
+
+za_export_unpack() {
+  # usage: za_export_unpack <asset> <dir>; fails for assets that are not archives
+  case "$1" in
+    *.tar.gz | *.tgz) mkdir -p "$2" && tar -xzf "$1" -C "$2" ;;
+    *.tar.xz | *.txz) mkdir -p "$2" && tar -xJf "$1" -C "$2" ;;
+    *.tar.zst | *.tzst) mkdir -p "$2" && zstd -dcq "$1" | tar -xf - -C "$2" ;;
+    *.tar.bz2 | *.tbz2 | *.tbz) mkdir -p "$2" && tar -xjf "$1" -C "$2" ;;
+    *.zip) mkdir -p "$2" && unzip -q "$1" -d "$2" ;;
+    *) return 1 ;;
+  esac
+}
+
+za_export_binary() {
+  # usage: za_export_binary <name> <url> <sha256> <executable-name>...
+  name=$1 url=$2 sha256=$3
+  shift 3
+  work=$(mktemp -d)
+  asset="$work/${url##*/}"
+  za_export_fetch "$url" "$sha256" "$asset"
+  case "$asset" in
+    *.tar.gz | *.tgz) ;;
+    *.gz) gunzip -f "$asset" && asset=${asset%.gz} ;;
+  esac
+  src=$asset
+  if za_export_unpack "$asset" "$work/unpack"; then
+    src=
+    for candidate in "$@"; do
+      src=$(find "$work/unpack" -type f -name "$candidate" | sort | head -n 1)
+      [ -n "$src" ] && break
+    done
+    if [ -z "$src" ]; then
+      echo "za export: no \`$name\` executable in ${url##*/}" >&2
+      exit 1
+    fi
+  fi
+  mkdir -p "$ZA_EXPORT_BIN_DIR"
+  cp "$src" "$ZA_EXPORT_BIN_DIR/$name"
+  chmod 0755 "$ZA_EXPORT_BIN_DIR/$name"
+  rm -rf "$work"
+  echo "$name installed to $ZA_EXPORT_BIN_DIR/$name"
+}
+
+za_export_package() {
+  # usage: za_export_package <name> <url> <sha256> <entry>
+  name=$1 url=$2 sha256=$3 entry=$4
+  work=$(mktemp -d)
+  asset="$work/${url##*/}"
+  za_export_fetch "$url" "$sha256" "$asset"
+  za_export_unpack "$asset" "$work/unpack"
+  root="$work/unpack"
+  if [ "$(ls -A "$root" | wc -l)" -eq 1 ] && [ -d "$root/$(ls -A "$root")" ]; then
+    root="$root/$(ls -A "$root")"
+  fi
+  mkdir -p "$ZA_EXPORT_SHARE_DIR"
+  rm -rf "${ZA_EXPORT_SHARE_DIR:?}/$name"
+  mv "$root" "$ZA_EXPORT_SHARE_DIR/$name"
+  rm -rf "$work"
+  echo "$name installed; entry: $ZA_EXPORT_SHARE_DIR/$name/$entry"
+}
+
+za_export_link() {
+  # usage: za_export_link <source> <dir> <link-name>; never replaces a file that is not a link
+  if [ -e "$2/$3" ] && [ ! -L "$2/$3" ]; then
+    echo "za export: skipped $2/$3: existing file is not a link" >&2
+    return 0
+  fi
+  mkdir -p "$2"
+  ln -sfn "$1" "$2/$3"
+  echo "linked $2/$3 -> $1"
+}
+"#;
@@ -0,0 +1,431 @@
+//! Append-only log of install, update, adopt, rollback and uninstall events for one tool scope.
+
+use super::listing::column_width;
+use super::*;
+use graviola::hashing::{Hash, HashContext, Sha256};
+use sqlx::{
+    Connection, Row, SqliteConnection,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqliteSynchronous},
+};
+use tokio::runtime::Builder;
+
+pub(super) const HISTORY_DB_FILE: &str = "history.db";
+
+/// `<project>-<digest>.db`, where the digest of the project's `current/` path tells apart
+/// projects that share a directory name.
+pub(super) fn project_history_db_file(current_dir: &Path) -> String {
+    let project = current_dir
+        .ancestors()
+        .nth(3)
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "project".to_string());
+    let mut hasher = Sha256::new();
+    hasher.update(current_dir.as_os_str().as_encoded_bytes());
+    let digest = hasher.finish();
+    let mut key = String::with_capacity(16);
+    for byte in &digest.as_ref()[..8] {
+        let _ = write!(key, "{byte:02x}");
+    }
+    format!("{project}-{key}.db")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) enum ToolHistoryAction {
+    Install,
+    Update,
+    Repair,
+    Adopt,
+    Rollback,
+    Uninstall,
+    SelfUpdate,
+}
+
+impl ToolHistoryAction {
+    const ALL: [Self; 7] = [
+        Self::Install,
+        Self::Update,
+        Self::Repair,
+        Self::Adopt,
+        Self::Rollback,
+        Self::Uninstall,
+        Self::SelfUpdate,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Install => "install",
+            Self::Update => "update",
+            Self::Repair => "repair",
+            Self::Adopt => "adopt",
+            Self::Rollback => "rollback",
+            Self::Uninstall => "uninstall",
+            Self::SelfUpdate => "self-update",
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == raw)
+            .ok_or_else(|| anyhow!("unknown tool history action `{raw}`"))
+    }
+
+    /// History action for an activated install plan; unchanged activations are not recorded.
+    pub(super) fn for_install_plan(plan: &InstallPlan) -> Option<Self> {
+        let adopted = plan
+            .adoption
+            .as_ref()
+            .is_some_and(|adopted| adopted.version == plan.tool.version);
+        match plan.planned_outcome {
+            _ if adopted && !plan.already_installed => Some(Self::Adopt),
+            InstallOutcome::Installed => Some(Self::Install),
+            InstallOutcome::Updated => Some(Self::Update),
+            InstallOutcome::Repaired => Some(Self::Repair),
+            InstallOutcome::Unchanged => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct ToolHistoryEvent {
+    pub(super) recorded_at_unix_ms: u64,
+    pub(super) action: ToolHistoryAction,
+    pub(super) tool: String,
+    pub(super) version: Option<String>,
+    pub(super) previous_version: Option<String>,
+    pub(super) detail: Option<String>,
+}
+
+impl ToolHistoryEvent {
+    pub(super) fn new(action: ToolHistoryAction, tool: &str, version: Option<&str>) -> Self {
+        Self {
+            recorded_at_unix_ms: now_unix_ms(),
+            action,
+            tool: tool.to_string(),
+            version: version.map(str::to_string),
+            previous_version: None,
+            detail: None,
+        }
+    }
+
+    pub(super) fn previous_version(mut self, previous_version: Option<&str>) -> Self {
+        self.previous_version = previous_version
+            .filter(|previous| self.version.as_deref() != Some(*previous))
+            .map(str::to_string);
+        self
+    }
+
+    pub(super) fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ToolHistoryReportJson {
+    scope: String,
+    path: String,
+    events: Vec<ToolHistoryEventJson>,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolHistoryEventJson {
+    recorded_at: String,
+    #[serde(flatten)]
+    event: ToolHistoryEvent,
+}
+
+/// Records an event in the scope's history. Failing to record never fails the change itself.
+pub(super) fn record_tool_event(home: &ToolHome, event: ToolHistoryEvent) {
+    if let Err(err) = home
+        .history_db_path()
+        .and_then(|path| append_event(&path, &event))
+        && !is_permission_denied_error(&err)
+    {
+        eprintln!("warning: tool history was not recorded: {err:#}");
+    }
+}
+
+/// Records the install, update, repair or adoption an activated plan just applied.
+pub(super) fn record_install_plan_event(home: &ToolHome, plan: &InstallPlan) {
+    let Some(action) = ToolHistoryAction::for_install_plan(plan) else {
+        return;
+    };
+    let tool = &plan.tool;
+    let mut event = ToolHistoryEvent::new(action, &tool.name, Some(&tool.version))
+        .previous_version(plan.previous_active.as_deref());
+    if let Ok(Some(manifest)) = read_manifest(home, tool) {
+        event = event.detail(manifest.source_kind);
+    }
+    record_tool_event(home, event);
+}
+
+pub(super) fn run_history(
+    home: &ToolHome,
+    tool: Option<&str>,
+    days: Option<u64>,
+    json: bool,
+) -> Result<i32> {
+    let tool = tool.map(canonical_tool_name);
+    let path = home.history_db_path()?;
+    let cutoff_ms =
+        days.map(|days| now_unix_ms().saturating_sub(days.saturating_mul(24 * 60 * 60 * 1000)));
+    let events = load_events(&path, tool.as_deref(), cutoff_ms)?;
+
+    if json {
+        let report = ToolHistoryReportJson {
+            scope: home.scope.label().to_string(),
+            path: path.display().to_string(),
+            events: events
+                .into_iter()
+                .map(|event| ToolHistoryEventJson {
+                    recorded_at: format_unix_ms(event.recorded_at_unix_ms),
+                    event,
+                })
+                .collect(),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("serialize tool history JSON")?
+        );
+        return Ok(0);
+    }
+
+    match render_history_table(&events) {
+        Some(table) => println!("{table}"),
+        None => println!(
+            "No tool history recorded in {} scope{}.",
+            home.scope.label(),
+            tool.map(|tool| format!(" for `{tool}`"))
+                .unwrap_or_default()
+        ),
+    }
+    println!("\nHistory: {}", path.display());
+    Ok(0)
+}
+
+pub(super) fn load_events(
+    path: &Path,
+    tool: Option<&str>,
+    cutoff_ms: Option<u64>,
+) -> Result<Vec<ToolHistoryEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    with_runtime(async {
+        let mut conn = open_connection(path, false)
+            .await
+            .with_context(|| format!("open tool history db {}", path.display()))?;
+        ensure_schema(&mut conn)
+            .await
+            .with_context(|| format!("initialize tool history db {}", path.display()))?;
+        query_events(&mut conn, tool, cutoff_ms).await
+    })
+}
+
+fn append_event(path: &Path, event: &ToolHistoryEvent) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("create tool history dir {}", parent.display()))?;
+    }
+    with_runtime(async {
+        let mut conn = open_connection(path, true)
+            .await
+            .with_context(|| format!("open tool history db {}", path.display()))?;
+        ensure_schema(&mut conn)
+            .await
+            .with_context(|| format!("initialize tool history db {}", path.display()))?;
+        insert_event(&mut conn, event).await
+    })
+}
+
+fn with_runtime<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("create tool history tokio runtime")?
+        .block_on(future)
+}
+
+async fn open_connection(path: &Path, create_if_missing: bool) -> Result<SqliteConnection> {
+    let mut options = SqliteConnectOptions::new()
+        .filename(path)
+        .busy_timeout(Duration::from_secs(2))
+        .create_if_missing(create_if_missing);
+    if create_if_missing {
+        options = options
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal);
+    }
+    SqliteConnection::connect_with(&options)
+        .await
+        .with_context(|| format!("connect sqlite {}", path.display()))
+}
+
+async fn ensure_schema(conn: &mut SqliteConnection) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS tool_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            recorded_at_unix_ms INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            version TEXT,
+            previous_version TEXT,
+            detail TEXT
+        )
+        "#,
+    )
+    .execute(&mut *conn)
+    .await
+    .context("create tool history table")?;
+
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS tool_events_tool_recorded_at_idx ON tool_events (tool, recorded_at_unix_ms)",
+    )
+    .execute(&mut *conn)
+    .await
+    .context("create tool history index")?;
+
+    Ok(())
+}
+
+async fn insert_event(conn: &mut SqliteConnection, event: &ToolHistoryEvent) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO tool_events (
+            recorded_at_unix_ms,
+            action,
+            tool,
+            version,
+            previous_version,
+            detail
+        ) VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(i64_from_u64(
+        event.recorded_at_unix_ms,
+        "recorded_at_unix_ms",
+    )?)
+    .bind(event.action.as_str())
+    .bind(&event.tool)
+    .bind(&event.version)
+    .bind(&event.previous_version)
+    .bind(&event.detail)
+    .execute(&mut *conn)
+    .await
+    .context("insert tool history event")?;
+    Ok(())
+}
+
+async fn query_events(
+    conn: &mut SqliteConnection,
+    tool: Option<&str>,
+    cutoff_ms: Option<u64>,
+) -> Result<Vec<ToolHistoryEvent>> {
+    let cutoff_ms = i64_from_u64(cutoff_ms.unwrap_or(0), "cutoff_ms")?;
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            recorded_at_unix_ms,
+            action,
+            tool,
+            version,
+            previous_version,
+            detail
+        FROM tool_events
+        WHERE recorded_at_unix_ms >= ? AND (? IS NULL OR tool = ?)
+        ORDER BY recorded_at_unix_ms DESC, id DESC
+        "#,
+    )
+    .bind(cutoff_ms)
+    .bind(tool)
+    .bind(tool)
+    .fetch_all(&mut *conn)
+    .await
+    .context("query tool history events")?;
+
+    rows.into_iter().map(row_to_event).collect()
+}
+
+fn row_to_event(row: sqlx::sqlite::SqliteRow) -> Result<ToolHistoryEvent> {
+    Ok(ToolHistoryEvent {
+        recorded_at_unix_ms: u64_from_i64(
+            row.try_get::<i64, _>("recorded_at_unix_ms")?,
+            "recorded_at_unix_ms",
+        )?,
+        action: ToolHistoryAction::parse(
+            &row.try_get::<String, _>("action")
+                .context("decode action")?,
+        )?,
+        tool: row.try_get("tool").context("decode tool")?,
+        version: row.try_get("version").context("decode version")?,
+        previous_version: row
+            .try_get("previous_version")
+            .context("decode previous_version")?,
+        detail: row.try_get("detail").context("decode detail")?,
+    })
+}
+
+fn render_history_table(events: &[ToolHistoryEvent]) -> Option<String> {
+    if events.is_empty() {
+        return None;
+    }
+
+    let rows = events
+        .iter()
+        .map(|event| {
+            let change = match (event.previous_version.as_deref(), event.version.as_deref()) {
+                (Some(previous), Some(version)) => format!("{previous} -> {version}"),
+                (None, Some(version)) => version.to_string(),
+                (Some(previous), None) => previous.to_string(),
+                (None, None) => "-".to_string(),
+            };
+            (
+                format_unix_ms(event.recorded_at_unix_ms),
+                event.action.as_str(),
+                event.tool.as_str(),
+                change,
+                event.detail.as_deref().unwrap_or("-"),
+            )
+        })
+        .collect::<Vec<_>>();
+    let time_width = column_width("TIME", 20, rows.iter().map(|row| row.0.as_str()));
+    let action_width = column_width("ACTION", 8, rows.iter().map(|row| row.1));
+    let tool_width = column_width("TOOL", 16, rows.iter().map(|row| row.2));
+    let change_width = column_width("CHANGE", 16, rows.iter().map(|row| row.3.as_str()));
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    lines.push(format!(
+        "{:<time_width$} {:<action_width$} {:<tool_width$} {:<change_width$} DETAIL",
+        "TIME", "ACTION", "TOOL", "CHANGE",
+    ));
+    for (time, action, tool, change, detail) in rows {
+        lines.push(format!(
+            "{time:<time_width$} {action:<action_width$} {tool:<tool_width$} {change:<change_width$} {detail}"
+        ));
+    }
+    Some(lines.join("\n"))
+}
+
+fn now_unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+fn format_unix_ms(unix_ms: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_millis(unix_ms)).to_string()
+}
+
+fn i64_from_u64(value: u64, field: &str) -> Result<i64> {
+    i64::try_from(value).with_context(|| format!("encode {field} as sqlite integer"))
+}
+
+fn u64_from_i64(value: i64, field: &str) -> Result<u64> {
+    u64::try_from(value).with_context(|| format!("decode {field} from sqlite integer"))
+}
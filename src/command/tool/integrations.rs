@@ -1,3 +1,7 @@
+use super::completions::{
+    ensure_tool_completions, preview_tool_completions, remove_tool_completions,
+};
+use super::policy::{IntegrationShell, ShellInitPosition, ShellInitSnippet};
 use super::*;
 
 pub(super) fn ensure_post_activation_integrations(
@@ -5,11 +9,8 @@ pub(super) fn ensure_post_activation_integrations(
     tool: &ToolRef,
     emit_stages: bool,
 ) -> Result<()> {
-    match tool.name.as_str() {
-        "starship" => ensure_starship_bash_init(home, tool, emit_stages),
-        "ble.sh" => ensure_blesh_bash_init(home, tool, emit_stages),
-        _ => Ok(()),
-    }
+    ensure_declared_shell_init(home, tool, emit_stages)?;
+    ensure_tool_completions(home, tool, emit_stages)
 }
 
 pub(super) fn preview_post_activation_integrations(
@@ -17,175 +18,239 @@ pub(super) fn preview_post_activation_integrations(
     tool: &ToolRef,
     emit_stages: bool,
 ) -> Result<()> {
-    match tool.name.as_str() {
-        "starship" => preview_starship_bash_init(home, tool, emit_stages),
-        "ble.sh" => preview_blesh_bash_init(home, tool, emit_stages),
-        _ => Ok(()),
-    }
+    preview_declared_shell_init(home, tool, emit_stages)?;
+    preview_tool_completions(tool, emit_stages)
 }
 
-fn ensure_starship_bash_init(home: &ToolHome, tool: &ToolRef, emit_stages: bool) -> Result<()> {
-    let rc_path = resolve_home_dir()?.join(".bashrc");
+fn declared_shell_init(name: &str) -> &'static [ShellInitSnippet] {
+    find_tool_policy(name).map_or(&[], |policy| policy.shell_init)
+}
+
+fn ensure_declared_shell_init(home: &ToolHome, tool: &ToolRef, emit_stages: bool) -> Result<()> {
     let active_path = home.active_path(&tool.name);
-    let helper_change = ensure_ide_terminal_bash_helper(&rc_path)?;
-    let change = upsert_managed_block(
-        &rc_path,
-        STARSHIP_BASH_INIT_START_MARKER,
-        STARSHIP_BASH_INIT_END_MARKER,
-        ManagedBlockPosition::BeforeMarker(BLESH_BASH_INIT_BOTTOM_START_MARKER),
-        &starship_bash_init_block(&active_path),
-    )
-    .with_context(|| format!("configure starship bash init in `{}`", rc_path.display()))?;
-    print_tool_stage_if(
-        emit_stages,
-        "next",
-        format!(
-            "starship bash init {} helper={} in {}; open a new JetBrains/Zed bash shell or `source {}`",
-            change.label(),
-            helper_change.label(),
-            rc_path.display(),
-            rc_path.display()
-        ),
-    );
+    for snippet in declared_shell_init(&tool.name) {
+        let Some(rc_path) = configured_shell_init_path(snippet.shell, &tool.name)? else {
+            continue;
+        };
+        if snippet.ide_terminal_helper {
+            let change = ensure_ide_terminal_bash_helper(&rc_path)?;
+            if change != ManagedFileChange::Unchanged {
+                print_tool_stage_if(
+                    emit_stages,
+                    "next",
+                    format!(
+                        "IDE terminal helper {} in {}",
+                        change.label(),
+                        rc_path.display()
+                    ),
+                );
+            }
+        }
+        let (start_marker, end_marker) = shell_init_snippet_markers(&tool.name, snippet);
+        let change = upsert_managed_block(
+            &rc_path,
+            &start_marker,
+            &end_marker,
+            managed_block_position(snippet.position),
+            &shell_init_block(snippet, &active_path),
+        )
+        .with_context(|| {
+            format!(
+                "configure {} {} init in `{}`",
+                tool.name,
+                shell_init_label(snippet),
+                rc_path.display()
+            )
+        })?;
+        print_tool_stage_if(
+            emit_stages,
+            "next",
+            format!(
+                "{} {} init {} in {}; open a new {} shell to pick it up",
+                tool.name,
+                shell_init_label(snippet),
+                change.label(),
+                rc_path.display(),
+                snippet.shell.name()
+            ),
+        );
+    }
     Ok(())
 }
 
-fn preview_starship_bash_init(home: &ToolHome, tool: &ToolRef, emit_stages: bool) -> Result<()> {
-    let rc_path = resolve_home_dir()?.join(".bashrc");
+fn preview_declared_shell_init(home: &ToolHome, tool: &ToolRef, emit_stages: bool) -> Result<()> {
     let active_path = home.active_path(&tool.name);
-    let helper_change = preview_ide_terminal_bash_helper(&rc_path)?;
-    let change = preview_managed_block(
-        &rc_path,
-        STARSHIP_BASH_INIT_START_MARKER,
-        STARSHIP_BASH_INIT_END_MARKER,
-        ManagedBlockPosition::BeforeMarker(BLESH_BASH_INIT_BOTTOM_START_MARKER),
-        &starship_bash_init_block(&active_path),
-    )?;
-    print_tool_stage_if(
-        emit_stages,
-        "next",
-        format!(
-            "starship bash init would be {} helper={} in {}",
-            change.label(),
-            helper_change.label(),
-            rc_path.display()
-        ),
-    );
+    for snippet in declared_shell_init(&tool.name) {
+        let Some(rc_path) = configured_shell_init_path(snippet.shell, &tool.name)? else {
+            continue;
+        };
+        if snippet.ide_terminal_helper {
+            let change = preview_ide_terminal_bash_helper(&rc_path)?;
+            if change != ManagedFileChange::Unchanged {
+                print_tool_stage_if(
+                    emit_stages,
+                    "next",
+                    format!(
+                        "IDE terminal helper would be {} in {}",
+                        change.label(),
+                        rc_path.display()
+                    ),
+                );
+            }
+        }
+        let (start_marker, end_marker) = shell_init_snippet_markers(&tool.name, snippet);
+        let change = preview_managed_block(
+            &rc_path,
+            &start_marker,
+            &end_marker,
+            managed_block_position(snippet.position),
+            &shell_init_block(snippet, &active_path),
+        )?;
+        print_tool_stage_if(
+            emit_stages,
+            "next",
+            format!(
+                "{} {} init would be {} in {}",
+                tool.name,
+                shell_init_label(snippet),
+                change.label(),
+                rc_path.display()
+            ),
+        );
+    }
     Ok(())
 }
 
-fn resolve_home_dir() -> Result<PathBuf> {
-    env::var_os("HOME")
-        .map(PathBuf::from)
-        .ok_or_else(|| anyhow!("cannot resolve home directory: set `HOME`"))
+/// Removes every shell's init block for `name`, even if the policy no longer declares it.
+/// Blocks split into parts are only known from the current declaration.
+fn remove_declared_shell_init(name: &str) -> Result<()> {
+    let parts = declared_shell_init(name)
+        .iter()
+        .filter(|snippet| snippet.part.is_some())
+        .map(|snippet| (snippet.shell, shell_init_snippet_markers(name, snippet)));
+    let whole = IntegrationShell::ALL
+        .into_iter()
+        .map(|shell| (shell, shell_init_markers(name, shell)));
+    for (shell, (start_marker, end_marker)) in whole.chain(parts) {
+        let rc_path = shell_init_path(shell, name)?;
+        if !remove_managed_block(&rc_path, &start_marker, &end_marker)? {
+            continue;
+        }
+        // Fish snippets live in their own conf.d file; drop it once it is empty.
+        if shell == IntegrationShell::Fish
+            && fs::read_to_string(&rc_path).is_ok_and(|content| content.trim().is_empty())
+        {
+            remove_file_if_exists(&rc_path)?;
+        }
+        print_tool_stage(
+            "next",
+            format!(
+                "removed {name} {} init from {}",
+                shell.name(),
+                rc_path.display()
+            ),
+        );
+    }
+    Ok(())
 }
 
-fn ensure_blesh_bash_init(home: &ToolHome, tool: &ToolRef, emit_stages: bool) -> Result<()> {
-    let rc_path = resolve_home_dir()?.join(".bashrc");
-    let active_path = home.active_path(&tool.name);
-    let helper_change = ensure_ide_terminal_bash_helper(&rc_path)?;
-    let top_change = upsert_managed_block(
-        &rc_path,
-        BLESH_BASH_INIT_TOP_START_MARKER,
-        BLESH_BASH_INIT_TOP_END_MARKER,
-        ManagedBlockPosition::AfterMarker(IDE_TERMINAL_BASH_HELPER_END_MARKER),
-        &blesh_bash_init_top_block(&active_path),
-    )
-    .with_context(|| format!("configure ble.sh bash prelude in `{}`", rc_path.display()))?;
-    let bottom_change = upsert_managed_block(
-        &rc_path,
-        BLESH_BASH_INIT_BOTTOM_START_MARKER,
-        BLESH_BASH_INIT_BOTTOM_END_MARKER,
-        ManagedBlockPosition::Bottom,
-        blesh_bash_init_bottom_block(),
+pub(crate) fn shell_init_markers(name: &str, shell: IntegrationShell) -> (String, String) {
+    (
+        format!("# >>> za {name} ({}) >>>", shell.name()),
+        format!("# <<< za {name} ({}) <<<", shell.name()),
     )
-    .with_context(|| {
-        format!(
-            "configure ble.sh bash attach hook in `{}`",
-            rc_path.display()
-        )
-    })?;
-    print_tool_stage_if(
-        emit_stages,
-        "next",
-        format!(
-            "ble.sh bash init top={} bottom={} helper={} in {}; open a new JetBrains/Zed bash shell or `source {}`",
-            top_change.label(),
-            bottom_change.label(),
-            helper_change.label(),
-            rc_path.display(),
-            rc_path.display()
-        ),
-    );
-    Ok(())
 }
 
-fn preview_blesh_bash_init(home: &ToolHome, tool: &ToolRef, emit_stages: bool) -> Result<()> {
-    let rc_path = resolve_home_dir()?.join(".bashrc");
-    let active_path = home.active_path(&tool.name);
-    let helper_change = preview_ide_terminal_bash_helper(&rc_path)?;
-    let top_change = preview_managed_block(
-        &rc_path,
-        BLESH_BASH_INIT_TOP_START_MARKER,
-        BLESH_BASH_INIT_TOP_END_MARKER,
-        ManagedBlockPosition::AfterMarker(IDE_TERMINAL_BASH_HELPER_END_MARKER),
-        &blesh_bash_init_top_block(&active_path),
-    )?;
-    let bottom_change = preview_managed_block(
-        &rc_path,
-        BLESH_BASH_INIT_BOTTOM_START_MARKER,
-        BLESH_BASH_INIT_BOTTOM_END_MARKER,
-        ManagedBlockPosition::Bottom,
-        blesh_bash_init_bottom_block(),
-    )?;
-    print_tool_stage_if(
-        emit_stages,
-        "next",
-        format!(
-            "ble.sh bash init would set top={} bottom={} helper={} in {}",
-            top_change.label(),
-            bottom_change.label(),
-            helper_change.label(),
-            rc_path.display()
+/// Markers of one declared snippet; a `part` joins the shell name inside the marker.
+pub(crate) fn shell_init_snippet_markers(
+    name: &str,
+    snippet: &ShellInitSnippet,
+) -> (String, String) {
+    match snippet.part {
+        Some(part) => (
+            format!("# >>> za {name} ({} {part}) >>>", snippet.shell.name()),
+            format!("# <<< za {name} ({} {part}) <<<", snippet.shell.name()),
         ),
-    );
-    Ok(())
+        None => shell_init_markers(name, snippet.shell),
+    }
 }
 
-pub(super) fn cleanup_post_uninstall_integrations(_home: &ToolHome, name: &str) -> Result<()> {
-    match name {
-        "starship" => {
-            let rc_path = resolve_home_dir()?.join(".bashrc");
-            remove_managed_block(
-                &rc_path,
-                STARSHIP_BASH_INIT_START_MARKER,
-                STARSHIP_BASH_INIT_END_MARKER,
-            )?;
-            print_tool_stage(
-                "next",
-                format!("removed starship bash init from {}", rc_path.display()),
-            );
+fn shell_init_label(snippet: &ShellInitSnippet) -> String {
+    match snippet.part {
+        Some(part) => format!("{} {part}", snippet.shell.name()),
+        None => snippet.shell.name().to_string(),
+    }
+}
+
+fn managed_block_position(position: ShellInitPosition) -> ManagedBlockPosition {
+    match position {
+        ShellInitPosition::Bottom => ManagedBlockPosition::Bottom,
+        ShellInitPosition::AfterIdeTerminalHelper => {
+            ManagedBlockPosition::AfterMarker(IDE_TERMINAL_BASH_HELPER_END_MARKER)
         }
-        "ble.sh" => {
-            let rc_path = resolve_home_dir()?.join(".bashrc");
-            remove_managed_block(
-                &rc_path,
-                BLESH_BASH_INIT_TOP_START_MARKER,
-                BLESH_BASH_INIT_TOP_END_MARKER,
-            )?;
-            remove_managed_block(
-                &rc_path,
-                BLESH_BASH_INIT_BOTTOM_START_MARKER,
-                BLESH_BASH_INIT_BOTTOM_END_MARKER,
-            )?;
-            print_tool_stage(
-                "next",
-                format!("removed ble.sh bash init from {}", rc_path.display()),
-            );
+        ShellInitPosition::BeforeBleshAttach => {
+            ManagedBlockPosition::BeforeMarker(BLESH_BASH_INIT_BOTTOM_START_MARKER)
         }
-        _ => {}
     }
-    Ok(())
+}
+
+pub(crate) fn shell_init_block(snippet: &ShellInitSnippet, active_path: &Path) -> String {
+    snippet.body.replace(
+        "{path}",
+        &shell_single_quote(&active_path.display().to_string()),
+    )
+}
+
+fn shell_init_path(shell: IntegrationShell, name: &str) -> Result<PathBuf> {
+    let home_dir = resolve_home_dir()?;
+    Ok(match shell {
+        IntegrationShell::Bash => home_dir.join(".bashrc"),
+        IntegrationShell::Zsh => zsh_config_dir(home_dir).join(".zshrc"),
+        IntegrationShell::Fish => fish_config_dir(&home_dir)
+            .join("conf.d")
+            .join(format!("za-{name}.fish")),
+    })
+}
+
+/// Like `shell_init_path`, but `None` when the user has not set that shell up.
+fn configured_shell_init_path(shell: IntegrationShell, name: &str) -> Result<Option<PathBuf>> {
+    shell_is_configured(shell)?
+        .then(|| shell_init_path(shell, name))
+        .transpose()
+}
+
+/// Whether the user has a startup file (or, for fish, a config dir) for `shell`.
+pub(super) fn shell_is_configured(shell: IntegrationShell) -> Result<bool> {
+    let home_dir = resolve_home_dir()?;
+    Ok(match shell {
+        IntegrationShell::Bash => home_dir.join(".bashrc").exists(),
+        IntegrationShell::Zsh => zsh_config_dir(home_dir).join(".zshrc").exists(),
+        IntegrationShell::Fish => fish_config_dir(&home_dir).is_dir(),
+    })
+}
+
+fn zsh_config_dir(home_dir: PathBuf) -> PathBuf {
+    env::var_os("ZDOTDIR")
+        .filter(|value| !value.is_empty())
+        .map_or(home_dir, PathBuf::from)
+}
+
+fn fish_config_dir(home_dir: &Path) -> PathBuf {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map_or_else(|| home_dir.join(".config"), PathBuf::from)
+        .join("fish")
+}
+
+fn resolve_home_dir() -> Result<PathBuf> {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("cannot resolve home directory: set `HOME`"))
+}
+
+pub(super) fn cleanup_post_uninstall_integrations(_home: &ToolHome, name: &str) -> Result<()> {
+    remove_declared_shell_init(name)?;
+    remove_tool_completions(name)
 }
 
 fn ensure_ide_terminal_bash_helper(rc_path: &Path) -> Result<ManagedFileChange> {
@@ -222,34 +287,6 @@ pub(crate) fn ide_terminal_bash_helper_block() -> &'static str {
 }"#
 }
 
-pub(crate) fn starship_bash_init_block(active_path: &Path) -> String {
-    format!(
-        r#"if _za_is_supported_ide_terminal && [[ $- == *i* ]] && [ -x {} ]; then
-  eval "$({} init bash)"
-fi"#,
-        shell_single_quote(&active_path.display().to_string()),
-        shell_single_quote(&active_path.display().to_string())
-    )
-}
-
-pub(crate) fn blesh_bash_init_top_block(active_path: &Path) -> String {
-    format!(
-        r#"if _za_is_supported_ide_terminal && [[ $- == *i* ]]; then
-  if source -- {} --attach=none; then
-    bleopt prompt_command_changes_layout=1
-    bleopt internal_suppress_bash_output=
-  fi
-fi"#,
-        shell_single_quote(&active_path.display().to_string())
-    )
-}
-
-pub(crate) fn blesh_bash_init_bottom_block() -> &'static str {
-    r#"if _za_is_supported_ide_terminal && [[ ${BLE_VERSION-} ]]; then
-  VSCODE_INJECTION=1 ble-attach
-fi"#
-}
-
 pub(crate) fn upsert_managed_block(
     target_path: &Path,
     start_marker: &str,
@@ -403,6 +440,6 @@ fn insert_managed_block_before_marker(content: &str, block: &str, marker: &str)
     }
 }
 
-fn shell_single_quote(value: &str) -> String {
+pub(super) fn shell_single_quote(value: &str) -> String {
     format!("'{}'", value.replace('\'', "'\"'\"'"))
 }
@@ -1,5 +1,8 @@
 use super::*;
 
+const CHANGELOG_LINES_PER_RELEASE: usize = 6;
+const CHANGELOG_LINE_MAX_CHARS: usize = 100;
+
 #[derive(Debug, Clone)]
 pub(super) enum LatestCheck {
     Latest(String),
@@ -82,6 +85,9 @@ struct ToolVersionDetail {
     version: String,
     active: bool,
     source: String,
+    /// Where the payload came from and which checks it passed, as recorded at install time.
+    source_detail: Option<String>,
+    target: Option<String>,
     executable_path: String,
     manifest_path: String,
 }
@@ -97,6 +103,7 @@ struct ToolDetailReport {
     active_bin_path: Option<String>,
     supported_source: Option<String>,
     installed: Vec<ToolVersionDetail>,
+    rollback_version: Option<String>,
     unmanaged: Option<UnmanagedBinary>,
 }
 
@@ -105,6 +112,9 @@ struct ToolVersionDetailJson {
     version: String,
     active: bool,
     source: String,
+    /// Where the payload came from and which checks it passed, as recorded at install time.
+    source_detail: Option<String>,
+    target: Option<String>,
     executable_path: String,
     manifest_path: String,
 }
@@ -120,6 +130,7 @@ struct ToolDetailReportJson {
     active_bin_path: Option<String>,
     supported_source: Option<String>,
     installed: Vec<ToolVersionDetailJson>,
+    rollback_version: Option<String>,
     unmanaged: Option<UnmanagedBinary>,
 }
 
@@ -131,6 +142,10 @@ struct OutdatedRow {
     source: String,
     update: String,
     latest: Option<String>,
+    requirement: Option<String>,
+    changelog: Option<Vec<source::ReleaseNotes>>,
+    changelog_error: Option<String>,
+    held_back: Option<source::ReleaseCooldownSkip>,
 }
 
 #[derive(Debug)]
@@ -162,6 +177,14 @@ struct OutdatedRowJson {
     source: String,
     update: String,
     latest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requirement: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changelog: Option<Vec<source::ReleaseNotes>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changelog_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    held_back: Option<source::ReleaseCooldownSkip>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -322,6 +345,7 @@ pub(super) fn list_outdated(
     home: &ToolHome,
     tools: &[String],
     json: bool,
+    changelog: bool,
     fail_on_updates: bool,
     fail_on_check_errors: bool,
 ) -> Result<i32> {
@@ -345,7 +369,7 @@ pub(super) fn list_outdated(
         }
     }
 
-    let report = build_outdated_report(home, &names)?;
+    let report = build_outdated_report(home, &names, changelog)?;
     if json {
         print_outdated_json(&report)?;
     } else {
@@ -438,10 +462,16 @@ fn build_tool_detail_report(home: &ToolHome, tool: &str) -> Result<ToolDetailRep
                 name: name.clone(),
                 version: version.clone(),
             };
+            let manifest = read_manifest(home, &tool).ok().flatten();
             Ok(ToolVersionDetail {
                 version: version.clone(),
                 active: active_version.as_deref() == Some(version.as_str()),
                 source: manifest_source_label(home, &tool)?,
+                source_detail: manifest
+                    .as_ref()
+                    .map(|manifest| manifest.source_detail.clone())
+                    .filter(|detail| !detail.is_empty()),
+                target: manifest.and_then(|manifest| manifest.target),
                 executable_path: home.install_path(&tool).display().to_string(),
                 manifest_path: home.manifest_path(&tool).display().to_string(),
             })
@@ -450,6 +480,7 @@ fn build_tool_detail_report(home: &ToolHome, tool: &str) -> Result<ToolDetailRep
     let active_bin_path = active_version
         .as_ref()
         .map(|_| home.active_path(&name).display().to_string());
+    let rollback_version = rollback_candidate(home, &name)?;
 
     Ok(ToolDetailReport {
         name,
@@ -469,12 +500,28 @@ fn build_tool_detail_report(home: &ToolHome, tool: &str) -> Result<ToolDetailRep
         active_bin_path,
         supported_source: policy.map(|policy| policy.source_label.to_string()),
         installed,
+        rollback_version,
         unmanaged,
     })
 }
 
-fn build_outdated_report(home: &ToolHome, names: &[String]) -> Result<OutdatedReport> {
-    let latest_lookup = resolve_latest_checks_for_names(names)?;
+fn build_outdated_report(
+    home: &ToolHome,
+    names: &[String],
+    changelog: bool,
+) -> Result<OutdatedReport> {
+    let mut requirements = HashMap::new();
+    for name in names {
+        if let Some(requirement) = read_active_requirement(home, name)? {
+            requirements.insert(name.clone(), requirement);
+        }
+    }
+    let unranged_names = names
+        .iter()
+        .filter(|name| !requirements.contains_key(*name))
+        .cloned()
+        .collect::<Vec<_>>();
+    let latest_lookup = resolve_latest_checks_for_names(&unranged_names)?;
     let mut rows = Vec::new();
     let mut check_failures = Vec::new();
     let mut has_updates = false;
@@ -483,16 +530,27 @@ fn build_outdated_report(home: &ToolHome, names: &[String]) -> Result<OutdatedRe
         let Some((version, active)) = select_primary_version(home, name)? else {
             continue;
         };
-        let latest = latest_lookup
-            .latest_by_name
-            .get(name)
-            .cloned()
-            .unwrap_or(LatestCheck::Unsupported);
+        let requirement = requirements.get(name);
+        let latest = match requirement {
+            Some(requirement) => resolve_latest_in_range(name, requirement),
+            None => latest_lookup
+                .latest_by_name
+                .get(name)
+                .cloned()
+                .unwrap_or(LatestCheck::Unsupported),
+        };
+        let (latest, held_back) =
+            hold_back_recent_release(name, Some(&version), requirement, latest);
         if let LatestCheck::Error(err) = &latest {
             check_failures.push((name.clone(), source::truncate_for_log(err, 120)));
         }
 
-        let update = list_update_status(&version, &latest);
+        let update = match requirement {
+            Some(requirement) => {
+                format!("{} ({requirement})", list_update_status(&version, &latest))
+            }
+            None => list_update_status(&version, &latest),
+        };
         let latest_version = match &latest {
             LatestCheck::Latest(remote) => Some(remote.clone()),
             LatestCheck::Unsupported | LatestCheck::Error(_) => None,
@@ -503,6 +561,23 @@ fn build_outdated_report(home: &ToolHome, names: &[String]) -> Result<OutdatedRe
                 if normalize_version(&version) != normalize_version(remote)
         );
         has_updates |= update_available;
+        let changelog = match latest_version.as_deref() {
+            Some(latest) if changelog && update_available => Some(
+                source::fetch_release_notes_between(
+                    name,
+                    &version,
+                    latest,
+                    za_config::ProxyScope::Tool,
+                )
+                .map_err(|err| source::truncate_for_log(&format!("{err:#}"), 120)),
+            ),
+            _ => None,
+        };
+        let (changelog, changelog_error) = match changelog {
+            Some(Ok(notes)) => (Some(notes), None),
+            Some(Err(err)) => (None, Some(err)),
+            None => (None, None),
+        };
 
         rows.push(OutdatedRow {
             name: name.clone(),
@@ -517,6 +592,10 @@ fn build_outdated_report(home: &ToolHome, names: &[String]) -> Result<OutdatedRe
             )?,
             update,
             latest: latest_version,
+            requirement: requirement.map(ToString::to_string),
+            changelog,
+            changelog_error,
+            held_back,
         });
     }
 
@@ -530,6 +609,35 @@ fn build_outdated_report(home: &ToolHome, names: &[String]) -> Result<OutdatedRe
     })
 }
 
+/// Applies the minimum release age to a resolved latest version.
+pub(super) fn hold_back_recent_release(
+    name: &str,
+    installed: Option<&str>,
+    requirement: Option<&VersionReq>,
+    latest: LatestCheck,
+) -> (LatestCheck, Option<source::ReleaseCooldownSkip>) {
+    let LatestCheck::Latest(candidate) = latest else {
+        return (latest, None);
+    };
+    match source::apply_min_release_age(
+        name,
+        installed,
+        &candidate,
+        requirement,
+        za_config::ProxyScope::Tool,
+    ) {
+        Ok((version, held_back)) => (LatestCheck::Latest(version), held_back),
+        Err(err) => (LatestCheck::Error(format!("{err:#}")), None),
+    }
+}
+
+fn resolve_latest_in_range(name: &str, requirement: &VersionReq) -> LatestCheck {
+    match source::resolve_version_in_range(name, requirement, za_config::ProxyScope::Tool) {
+        Ok(version) => LatestCheck::Latest(version),
+        Err(err) => LatestCheck::Error(format!("{err:#}")),
+    }
+}
+
 fn select_primary_version(home: &ToolHome, name: &str) -> Result<Option<(String, bool)>> {
     if let Some(active) = read_current_version(home, name)? {
         return Ok(Some((active, true)));
@@ -574,7 +682,7 @@ fn text_width(value: &str) -> usize {
     value.chars().count()
 }
 
-fn column_width<'a, I>(header: &str, min_width: usize, values: I) -> usize
+pub(super) fn column_width<'a, I>(header: &str, min_width: usize, values: I) -> usize
 where
     I: IntoIterator<Item = &'a str>,
 {
@@ -773,12 +881,26 @@ fn print_tool_detail_text(report: &ToolDetailReport) {
         println!("Installed versions:");
         for item in &report.installed {
             let active_marker = if item.active { " [active]" } else { "" };
+            let target = item
+                .target
+                .as_deref()
+                .map(|target| format!("  target={target}"))
+                .unwrap_or_default();
             println!(
-                "- {}{}  source={}  path={}",
-                item.version, active_marker, item.source, item.executable_path
+                "- {}{}  source={}{}  path={}",
+                item.version, active_marker, item.source, target, item.executable_path
             );
+            if let Some(detail) = &item.source_detail {
+                println!("  from: {detail}");
+            }
         }
     }
+    if let Some(version) = &report.rollback_version {
+        println!(
+            "Rollback: {version} (run `za tool rollback {}`)",
+            report.name
+        );
+    }
 
     if let Some(unmanaged) = &report.unmanaged {
         println!(
@@ -809,10 +931,13 @@ fn print_tool_detail_json(report: &ToolDetailReport) -> Result<()> {
                 version: item.version.clone(),
                 active: item.active,
                 source: item.source.clone(),
+                source_detail: item.source_detail.clone(),
+                target: item.target.clone(),
                 executable_path: item.executable_path.clone(),
                 manifest_path: item.manifest_path.clone(),
             })
             .collect(),
+        rollback_version: report.rollback_version.clone(),
         unmanaged: report.unmanaged.clone(),
     };
     println!(
@@ -829,6 +954,24 @@ fn print_outdated_text(report: &OutdatedReport) {
         println!("No za-managed tools are installed.");
     }
 
+    if let Some(changelog) = render_outdated_changelog(&report.rows) {
+        println!("\n{changelog}");
+    }
+    let held_back = report
+        .rows
+        .iter()
+        .filter_map(|row| Some((row.name.as_str(), row.held_back.as_ref()?)))
+        .collect::<Vec<_>>();
+    if !held_back.is_empty() {
+        println!("\nHeld back by the minimum release age:");
+        for (name, skip) in held_back {
+            println!(
+                "- {name} {}: eligible on {}",
+                skip.version, skip.eligible_on
+            );
+        }
+    }
+
     println!("\nScope: {}", report.scope);
     println!("Tool binaries path: {}", report.bin_path);
     if !report.check_failures.is_empty() {
@@ -840,6 +983,68 @@ fn print_outdated_text(report: &OutdatedReport) {
     print_unmanaged_binaries_text(&report.unmanaged);
 }
 
+/// Condensed release notes per outdated tool: a few meaningful lines of each release body.
+fn render_outdated_changelog(rows: &[OutdatedRow]) -> Option<String> {
+    let mut lines = Vec::new();
+    for row in rows {
+        if row.changelog.is_none() && row.changelog_error.is_none() {
+            continue;
+        }
+        lines.push(format!(
+            "{} {} -> {}",
+            row.name,
+            row.version,
+            row.latest.as_deref().unwrap_or("-")
+        ));
+        if let Some(err) = row.changelog_error.as_deref() {
+            lines.push(format!("  release notes unavailable: {err}"));
+            continue;
+        }
+        let releases = row.changelog.as_deref().unwrap_or_default();
+        if releases.is_empty() {
+            lines.push("  no release notes were found".to_string());
+        }
+        for release in releases {
+            match release.published_at.as_deref() {
+                Some(published_at) => lines.push(format!(
+                    "  {} ({})",
+                    release.version,
+                    published_at.get(..10).unwrap_or(published_at)
+                )),
+                None => lines.push(format!("  {}", release.version)),
+            }
+            lines.extend(
+                condense_release_notes(&release.body, CHANGELOG_LINES_PER_RELEASE)
+                    .into_iter()
+                    .map(|line| format!("    {line}")),
+            );
+        }
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.insert(0, "Changelog:".to_string());
+    Some(lines.join("\n"))
+}
+
+/// Drops blank lines, heading markers and HTML comments, keeping at most `max_lines` lines.
+fn condense_release_notes(body: &str, max_lines: usize) -> Vec<String> {
+    let meaningful = body
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("<!--"))
+        .collect::<Vec<_>>();
+    let mut out = meaningful
+        .iter()
+        .take(max_lines)
+        .map(|line| source::truncate_for_log(line, CHANGELOG_LINE_MAX_CHARS))
+        .collect::<Vec<_>>();
+    if meaningful.len() > max_lines {
+        out.push(format!("… {} more line(s)", meaningful.len() - max_lines));
+    }
+    out
+}
+
 fn print_outdated_json(report: &OutdatedReport) -> Result<()> {
     let json = OutdatedReportJson {
         scope: report.scope.clone(),
@@ -854,6 +1059,10 @@ fn print_outdated_json(report: &OutdatedReport) -> Result<()> {
                 source: row.source.clone(),
                 update: row.update.clone(),
                 latest: row.latest.clone(),
+                requirement: row.requirement.clone(),
+                changelog: row.changelog.clone(),
+                changelog_error: row.changelog_error.clone(),
+                held_back: row.held_back.clone(),
             })
             .collect(),
         unmanaged: report.unmanaged.clone(),
@@ -1052,7 +1261,7 @@ fn fetch_latest_checks_parallel(
 }
 
 fn resolve_latest_for_policy(policy: ToolPolicy, mode: LatestResolutionMode) -> LatestCheck {
-    let Some(release) = policy.github_release else {
+    let Some(release) = policy.release else {
         return LatestCheck::Unsupported;
     };
     let result = match mode {
@@ -1152,6 +1361,10 @@ mod tests {
                 source: "download".to_string(),
                 update: "update -> nightly-20260317+cafebabe".to_string(),
                 latest: Some("nightly-20260317+cafebabe".to_string()),
+                requirement: None,
+                changelog: None,
+                changelog_error: None,
+                held_back: None,
             },
             OutdatedRow {
                 name: "za".to_string(),
@@ -1160,6 +1373,10 @@ mod tests {
                 source: "download".to_string(),
                 update: "latest".to_string(),
                 latest: Some("0.1.41".to_string()),
+                requirement: None,
+                changelog: None,
+                changelog_error: None,
+                held_back: None,
             },
         ];
 
@@ -1185,4 +1402,39 @@ mod tests {
 
         assert_eq!(render_outdated_table(&rows), Some(expected));
     }
+
+    #[test]
+    fn outdated_changelog_condenses_release_bodies() {
+        let rows = vec![OutdatedRow {
+            name: "rg".to_string(),
+            version: "14.0.0".to_string(),
+            active: true,
+            source: "download".to_string(),
+            update: "update -> 14.1.0".to_string(),
+            latest: Some("14.1.0".to_string()),
+            requirement: None,
+            changelog: Some(vec![source::ReleaseNotes {
+                version: "14.1.0".to_string(),
+                published_at: Some("2026-01-02T03:04:05Z".to_string()),
+                body: "<!-- generated -->\n## Features\n\n- one\n- two\n- three".to_string(),
+            }]),
+            changelog_error: None,
+            held_back: None,
+        }];
+
+        assert_eq!(
+            render_outdated_changelog(&rows).as_deref(),
+            Some(
+                "Changelog:\nrg 14.0.0 -> 14.1.0\n  14.1.0 (2026-01-02)\n    Features\n    - one\n    - two\n    - three"
+            )
+        );
+        assert_eq!(
+            condense_release_notes("a\n\nb\nc\nd", 2),
+            vec![
+                "a".to_string(),
+                "b".to_string(),
+                "… 2 more line(s)".to_string()
+            ]
+        );
+    }
 }
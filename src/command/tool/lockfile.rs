@@ -0,0 +1,197 @@
+//! Lockfile written next to a sync manifest so later syncs can reproduce the same installs.
+
+use super::batch::ToolSyncEntry;
+use super::target::ToolTarget;
+use super::*;
+
+pub(super) const TOOL_SYNC_LOCKFILE_VERSION: u32 = 1;
+const TOOL_SYNC_LOCKFILE_HEADER: &str = "# Generated by `za tool sync`; do not edit by hand.\n# Install exactly these assets with `za tool sync --locked`.\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ToolSyncLockfile {
+    pub(super) version: u32,
+    #[serde(default)]
+    pub(super) tools: Vec<ToolSyncLockEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ToolSyncLockEntry {
+    /// Canonical tool name.
+    pub(super) name: String,
+    /// Manifest spec this entry was resolved from, e.g. `codex` or `codex:0.1.0`.
+    pub(super) spec: String,
+    /// Exact version that was installed.
+    pub(super) version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) sha256: Option<String>,
+}
+
+/// `za.tools.toml` locks to `za.tools.lock` in the same directory.
+pub(super) fn sync_lockfile_path(manifest: &Path) -> PathBuf {
+    manifest.with_extension("lock")
+}
+
+pub(super) fn read_sync_lockfile(path: &Path) -> Result<Option<ToolSyncLockfile>> {
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("read lockfile {}", path.display())),
+    };
+    let lockfile = toml::from_str::<ToolSyncLockfile>(&raw)
+        .with_context(|| format!("parse lockfile {}", path.display()))?;
+    if lockfile.version != TOOL_SYNC_LOCKFILE_VERSION {
+        bail!(
+            "lockfile {} has unsupported version {}; expected {TOOL_SYNC_LOCKFILE_VERSION}",
+            path.display(),
+            lockfile.version
+        );
+    }
+    Ok(Some(lockfile))
+}
+
+pub(super) fn write_sync_lockfile(path: &Path, lockfile: &ToolSyncLockfile) -> Result<()> {
+    let body = toml::to_string_pretty(lockfile).context("serialize sync lockfile")?;
+    write_file_atomically(path, format!("{TOOL_SYNC_LOCKFILE_HEADER}{body}"))
+        .with_context(|| format!("write lockfile {}", path.display()))
+}
+
+/// Builds lock entries from the store state left behind by a successful sync. `resolved` holds
+/// the versions the sync settled on, which foreign-target syncs never activate. Tools without a
+/// recorded asset (adopted ones, or installs older than asset recording) are locked by version
+/// only, with a warning; `--locked` refuses such entries.
+pub(super) fn collect_sync_lock_entries(
+    home: &ToolHome,
+    specs: &[String],
+    resolved: &[ToolRef],
+) -> Result<Vec<ToolSyncLockEntry>> {
+    let mut entries = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let parsed = ToolSpec::parse(spec)?;
+        let name = canonical_tool_name(&parsed.name);
+        let version = match parsed.version {
+            Some(version) => version,
+            None => resolved
+                .iter()
+                .find(|tool| tool.name == name)
+                .map(|tool| tool.version.clone())
+                .ok_or_else(|| anyhow!("`{name}` was not resolved by this sync"))?,
+        };
+        let tool = ToolRef {
+            name: name.clone(),
+            version: normalize_version(&version),
+        };
+        let manifest = read_manifest(home, &tool)?;
+        let (target, asset) = match manifest {
+            Some(ToolManifest {
+                target,
+                asset_url: Some(url),
+                asset_sha256: Some(sha256),
+                ..
+            }) => (target, Some((url, sha256))),
+            manifest => {
+                eprintln!(
+                    "⚠️  `{name}` {} has no recorded release asset ({}); it is locked by version only and `za tool sync --locked` refuses it until you reinstall it with `za tool install {name}`",
+                    tool.version,
+                    manifest
+                        .as_ref()
+                        .map_or("no manifest", |manifest| manifest.source_kind.as_str())
+                );
+                (manifest.and_then(|manifest| manifest.target), None)
+            }
+        };
+        let (url, sha256) = asset.unzip();
+        entries.push(ToolSyncLockEntry {
+            name,
+            spec: spec.clone(),
+            version: tool.version,
+            target,
+            url,
+            sha256,
+        });
+    }
+    Ok(entries)
+}
+
+/// Turns lock entries into exact install specs, failing when the manifest and lockfile disagree.
+pub(super) fn locked_sync_specs(
+    lockfile_path: &Path,
+    lockfile: &ToolSyncLockfile,
+    manifest_entries: &[ToolSyncEntry],
+    target: ToolTarget,
+) -> Result<Vec<ToolSpec>> {
+    let locked_specs = lockfile
+        .tools
+        .iter()
+        .map(|entry| entry.spec.as_str())
+        .collect::<HashSet<_>>();
+    let wanted = manifest_entries
+        .iter()
+        .map(|entry| (entry.spec.as_str(), entry))
+        .collect::<HashMap<_, _>>();
+    let mut missing = manifest_entries
+        .iter()
+        .filter(|entry| !locked_specs.contains(entry.spec.as_str()))
+        .map(|entry| entry.spec.clone())
+        .collect::<Vec<_>>();
+    let mut stale = lockfile
+        .tools
+        .iter()
+        .filter(|entry| !wanted.contains_key(entry.spec.as_str()))
+        .map(|entry| entry.spec.clone())
+        .collect::<Vec<_>>();
+    if !missing.is_empty() || !stale.is_empty() {
+        missing.sort();
+        stale.sort();
+        let mut details = Vec::new();
+        if !missing.is_empty() {
+            details.push(format!("not locked: {}", missing.join(", ")));
+        }
+        if !stale.is_empty() {
+            details.push(format!("no longer in manifest: {}", stale.join(", ")));
+        }
+        bail!(
+            "lockfile {} is out of date with the sync manifest ({}); run `za tool sync` without `--locked` to refresh it",
+            lockfile_path.display(),
+            details.join("; ")
+        );
+    }
+
+    let requested_target = target.to_string();
+    lockfile
+        .tools
+        .iter()
+        .map(|entry| {
+            if let Some(locked_target) = entry.target.as_deref()
+                && locked_target != requested_target
+            {
+                bail!(
+                    "lockfile {} pins `{}` for {locked_target}, but this sync targets {requested_target}",
+                    lockfile_path.display(),
+                    entry.name
+                );
+            }
+            let mut spec = ToolSpec::from_args(&entry.name, Some(&entry.version))?;
+            spec.integrations = wanted
+                .get(entry.spec.as_str())
+                .is_none_or(|wanted| wanted.integrations);
+            let (Some(url), Some(sha256)) = (&entry.url, &entry.sha256) else {
+                bail!(
+                    "lockfile {} entry for `{}` must set both `url` and `sha256`; run `za tool sync` without `--locked` to refresh it",
+                    lockfile_path.display(),
+                    entry.name
+                );
+            };
+            spec.locked = Some(ReleaseAsset {
+                url: url.clone(),
+                sha256: sha256.clone(),
+            });
+            Ok(spec)
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
-use anyhow::{Result, bail};
-use std::env;
+use super::target::ToolTarget;
+use anyhow::{Result, anyhow, bail};
+use std::sync::{LazyLock, Mutex};
 
 const CODEX_GITHUB_OWNER: &str = "openai";
 const CODEX_GITHUB_REPO: &str = "codex";
@@ -83,7 +84,7 @@ pub(super) struct PackagePolicy {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(super) enum GithubReleaseTrack {
+pub(super) enum ReleaseTrack {
     VersionedTags,
     RollingTagAssets {
         tag: &'static str,
@@ -93,21 +94,279 @@ pub(super) enum GithubReleaseTrack {
     },
 }
 
+/// How a tool's release assets are found and verified, whichever `backend` publishes them.
 #[derive(Debug, Clone, Copy)]
-pub(super) struct GithubReleasePolicy {
+pub(super) struct ReleasePolicy {
     pub(super) project_label: &'static str,
+    pub(super) backend: ReleaseBackend,
     pub(super) owner: &'static str,
     pub(super) repo: &'static str,
     pub(super) tag_prefix: &'static str,
-    pub(super) expected_asset_name: Option<fn(&str) -> Result<String>>,
-    pub(super) verification: GithubReleaseVerification,
-    pub(super) track: GithubReleaseTrack,
+    pub(super) expected_asset_name: Option<ExpectedAssetName>,
+    pub(super) verification: ReleaseVerification,
+    pub(super) track: ReleaseTrack,
 }
 
+/// Release API a tool's assets are published through.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(super) enum GithubReleaseVerification {
+pub(super) enum ReleaseBackend {
+    Github,
+    /// gitlab.com or a self-hosted instance, e.g. `https://gitlab.example.com`.
+    Gitlab {
+        base_url: &'static str,
+    },
+    /// Gitea or Forgejo instance; both serve the same release API.
+    Gitea {
+        base_url: &'static str,
+    },
+}
+
+impl ReleaseBackend {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Self::Github => "GitHub",
+            Self::Gitlab { .. } => "GitLab",
+            Self::Gitea { .. } => "Gitea",
+        }
+    }
+
+    /// Only the GitHub API reports per-asset digests.
+    pub(super) fn publishes_asset_digests(self) -> bool {
+        self == Self::Github
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) enum ExpectedAssetName {
+    Resolver(fn(&str, ToolTarget) -> Result<String>),
+    Template(AssetTemplate),
+    /// Picked from the release's asset list by OS, architecture and libc tokens in file names.
+    HostMatch,
+}
+
+impl ExpectedAssetName {
+    pub(super) fn resolve(self, version: &str, target: ToolTarget) -> Result<String> {
+        match self {
+            Self::Resolver(resolver) => resolver(version, target),
+            Self::Template(template) => template.render(version, target),
+            Self::HostMatch => {
+                bail!("asset for {target} is only known once the release asset list is fetched")
+            }
+        }
+    }
+}
+
+/// Asset-name template declared by a tool registry entry.
+///
+/// Supports `{version}`, `{target}`, `{os}` and `{arch}` placeholders. `{target}` comes from
+/// the entry's `targets` table keyed by `<os>-<arch>`, or defaults to the Rust target triple
+/// of the install target.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct AssetTemplate {
+    pub(super) project_label: &'static str,
+    pub(super) template: &'static str,
+    pub(super) targets: &'static [(&'static str, &'static str)],
+}
+
+pub(super) const ASSET_TEMPLATE_PLACEHOLDERS: [&str; 4] = ["version", "target", "os", "arch"];
+
+impl AssetTemplate {
+    pub(super) fn render(self, version: &str, target: ToolTarget) -> Result<String> {
+        let target_name = self.target_name(target)?;
+        Ok(self
+            .template
+            .replace("{version}", version)
+            .replace("{target}", &target_name)
+            .replace("{os}", target.os)
+            .replace("{arch}", target.arch))
+    }
+
+    fn target_name(self, target: ToolTarget) -> Result<String> {
+        if self.targets.is_empty() {
+            return target.rust_triple();
+        }
+        let platform = target.platform_key();
+        self.targets
+            .iter()
+            .find(|(key, _)| *key == platform)
+            .map(|(_, name)| (*name).to_string())
+            .ok_or_else(|| {
+                anyhow!(
+                    "unsupported platform for {} release asset: {}-{}",
+                    self.project_label,
+                    target.arch,
+                    target.os
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ReleaseVerification {
     RequiredSha256Digest,
+    /// SHA-256 read from a checksum asset published in the same release.
+    ChecksumFile,
+    /// Detached `<asset>.minisig` signature checked against a pinned public key.
+    Minisign {
+        public_key: &'static str,
+    },
+    /// No API digest; a release checksum file is still used when one is published.
     NoSha256Digest,
+    /// API digest when the asset has one, else a published checksum file, else unverified with a
+    /// warning; `required` refuses the asset instead.
+    AvailableDigest {
+        required: bool,
+    },
+}
+
+/// Shell whose startup file can carry a tool's init snippet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum IntegrationShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl IntegrationShell {
+    pub(super) const ALL: [Self; 3] = [Self::Bash, Self::Zsh, Self::Fish];
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+        }
+    }
+}
+
+/// Init snippet written into a shell startup file after activation.
+///
+/// `{path}` in `body` expands to the single-quoted active entry path.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ShellInitSnippet {
+    pub(super) shell: IntegrationShell,
+    /// Tells several snippets for one shell apart in their block markers, e.g. ble.sh's `top`.
+    pub(super) part: Option<&'static str>,
+    pub(super) position: ShellInitPosition,
+    /// Bash body calls `_za_is_supported_ide_terminal`, so za's helper block is written first.
+    pub(super) ide_terminal_helper: bool,
+    pub(super) body: &'static str,
+}
+
+/// Where a shell init snippet sits in its startup file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ShellInitPosition {
+    Bottom,
+    /// Right after za's IDE terminal helper, ahead of the user's own setup.
+    AfterIdeTerminalHelper,
+    /// Ahead of ble.sh's attach hook, which has to run last.
+    BeforeBleshAttach,
+}
+
+const STARSHIP_SHELL_INIT: [ShellInitSnippet; 3] = [
+    ShellInitSnippet {
+        shell: IntegrationShell::Bash,
+        part: None,
+        position: ShellInitPosition::BeforeBleshAttach,
+        ide_terminal_helper: true,
+        body: r#"if _za_is_supported_ide_terminal && [[ $- == *i* ]] && [ -x {path} ]; then
+  eval "$({path} init bash)"
+fi"#,
+    },
+    ShellInitSnippet {
+        shell: IntegrationShell::Zsh,
+        part: None,
+        position: ShellInitPosition::Bottom,
+        ide_terminal_helper: false,
+        body: r#"if [[ -o interactive ]] && [ -x {path} ] && { [ "${TERMINAL_EMULATOR-}" = "JetBrains-JediTerm" ] || { [ "${ZED_TERM-}" = "true" ] && [ "${TERM_PROGRAM-}" = "zed" ]; }; }; then
+  eval "$({path} init zsh)"
+fi"#,
+    },
+    ShellInitSnippet {
+        shell: IntegrationShell::Fish,
+        part: None,
+        position: ShellInitPosition::Bottom,
+        ide_terminal_helper: false,
+        body: r#"if status is-interactive; and test -x {path}
+    if test "$TERMINAL_EMULATOR" = JetBrains-JediTerm; or begin; test "$ZED_TERM" = true; and test "$TERM_PROGRAM" = zed; end
+        {path} init fish | source
+    end
+end"#,
+    },
+];
+
+const BLESH_SHELL_INIT: [ShellInitSnippet; 2] = [
+    ShellInitSnippet {
+        shell: IntegrationShell::Bash,
+        part: Some("top"),
+        position: ShellInitPosition::AfterIdeTerminalHelper,
+        ide_terminal_helper: true,
+        body: r#"if _za_is_supported_ide_terminal && [[ $- == *i* ]]; then
+  if source -- {path} --attach=none; then
+    bleopt prompt_command_changes_layout=1
+    bleopt internal_suppress_bash_output=
+  fi
+fi"#,
+    },
+    ShellInitSnippet {
+        shell: IntegrationShell::Bash,
+        part: Some("bottom"),
+        position: ShellInitPosition::Bottom,
+        ide_terminal_helper: true,
+        body: r#"if _za_is_supported_ide_terminal && [[ ${BLE_VERSION-} ]]; then
+  VSCODE_INJECTION=1 ble-attach
+fi"#,
+    },
+];
+
+/// Where a tool's shell completion scripts come from.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum CompletionSource {
+    /// Files shipped in the release archive, found by file name anywhere in it.
+    Archive {
+        bash: &'static str,
+        zsh: &'static str,
+        fish: &'static str,
+    },
+    /// Printed by the installed binary; `{shell}` in `args` becomes the shell name.
+    Generate { args: &'static [&'static str] },
+}
+
+impl CompletionSource {
+    pub(super) fn archive_file(self, shell: IntegrationShell) -> Option<&'static str> {
+        match self {
+            Self::Archive { bash, zsh, fish } => Some(match shell {
+                IntegrationShell::Bash => bash,
+                IntegrationShell::Zsh => zsh,
+                IntegrationShell::Fish => fish,
+            }),
+            Self::Generate { .. } => None,
+        }
+    }
+}
+
+/// Extra link created on activation and removed with the active entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ExposedEntry {
+    /// File inside the package payload; empty for a binary tool's own executable.
+    pub(super) relpath: &'static str,
+    pub(super) placement: EntryPlacement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum EntryPlacement {
+    /// `<bin>/<name>` next to the other managed executables.
+    Bin(&'static str),
+    /// Docker CLI plugin directory, so `docker-compose` runs as `docker compose`.
+    DockerCliPlugin(&'static str),
+}
+
+impl EntryPlacement {
+    pub(super) fn file_name(self) -> &'static str {
+        match self {
+            Self::Bin(name) | Self::DockerCliPlugin(name) => name,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -117,7 +376,21 @@ pub(super) struct ToolPolicy {
     pub(super) source_label: &'static str,
     pub(super) layout: ToolLayout,
     pub(super) package: Option<PackagePolicy>,
-    pub(super) github_release: Option<GithubReleasePolicy>,
+    pub(super) shell_init: &'static [ShellInitSnippet],
+    pub(super) completions: Option<CompletionSource>,
+    pub(super) exposed: &'static [ExposedEntry],
+    pub(super) origin: ToolOrigin,
+    pub(super) release: Option<ReleasePolicy>,
+}
+
+/// Where a tool definition comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ToolOrigin {
+    Catalog,
+    SystemRegistry,
+    UserRegistry,
+    /// An `owner/repo` install; see `adhoc.rs`.
+    Adhoc,
 }
 
 impl ToolPolicy {
@@ -139,14 +412,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "za",
+            backend: ReleaseBackend::Github,
             owner: ZA_GITHUB_OWNER,
             repo: ZA_GITHUB_REPO,
             tag_prefix: ZA_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(za_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(za_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -155,14 +433,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "codex",
+            backend: ReleaseBackend::Github,
             owner: CODEX_GITHUB_OWNER,
             repo: CODEX_GITHUB_REPO,
             tag_prefix: CODEX_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(codex_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(codex_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -171,14 +454,24 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[ExposedEntry {
+            relpath: "",
+            placement: EntryPlacement::DockerCliPlugin("docker-compose"),
+        }],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "docker-compose",
+            backend: ReleaseBackend::Github,
             owner: DOCKER_COMPOSE_GITHUB_OWNER,
             repo: DOCKER_COMPOSE_GITHUB_REPO,
             tag_prefix: DOCKER_COMPOSE_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(docker_compose_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(
+                docker_compose_expected_asset_name,
+            )),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -187,14 +480,23 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: Some(CompletionSource::Archive {
+            bash: "rg.bash",
+            zsh: "_rg",
+            fish: "rg.fish",
+        }),
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "ripgrep",
+            backend: ReleaseBackend::Github,
             owner: RIPGREP_GITHUB_OWNER,
             repo: RIPGREP_GITHUB_REPO,
             tag_prefix: RIPGREP_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(ripgrep_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(ripgrep_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -203,14 +505,23 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: Some(CompletionSource::Archive {
+            bash: "fd.bash",
+            zsh: "_fd",
+            fish: "fd.fish",
+        }),
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "fd",
+            backend: ReleaseBackend::Github,
             owner: FD_GITHUB_OWNER,
             repo: FD_GITHUB_REPO,
             tag_prefix: FD_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(fd_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(fd_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -219,14 +530,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "tcping-rs",
+            backend: ReleaseBackend::Github,
             owner: TCPING_GITHUB_OWNER,
             repo: TCPING_GITHUB_REPO,
             tag_prefix: TCPING_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(tcping_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(tcping_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -235,14 +551,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "motdyn",
+            backend: ReleaseBackend::Github,
             owner: MOTDYN_GITHUB_OWNER,
             repo: MOTDYN_GITHUB_REPO,
             tag_prefix: MOTDYN_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(motdyn_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(motdyn_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -251,14 +572,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "bottom",
+            backend: ReleaseBackend::Github,
             owner: BOTTOM_GITHUB_OWNER,
             repo: BOTTOM_GITHUB_REPO,
             tag_prefix: BOTTOM_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(bottom_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(bottom_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -267,14 +593,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "bpftop",
+            backend: ReleaseBackend::Github,
             owner: BPFTOP_GITHUB_OWNER,
             repo: BPFTOP_GITHUB_REPO,
             tag_prefix: BPFTOP_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(bpftop_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(bpftop_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -283,14 +614,23 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: Some(CompletionSource::Archive {
+            bash: "hyperfine.bash",
+            zsh: "_hyperfine",
+            fish: "hyperfine.fish",
+        }),
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "hyperfine",
+            backend: ReleaseBackend::Github,
             owner: HYPERFINE_GITHUB_OWNER,
             repo: HYPERFINE_GITHUB_REPO,
             tag_prefix: HYPERFINE_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(hyperfine_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(hyperfine_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -299,14 +639,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "dust",
+            backend: ReleaseBackend::Github,
             owner: DUST_GITHUB_OWNER,
             repo: DUST_GITHUB_REPO,
             tag_prefix: DUST_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(dust_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(dust_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -315,14 +660,23 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: Some(CompletionSource::Archive {
+            bash: "just.bash",
+            zsh: "just.zsh",
+            fish: "just.fish",
+        }),
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "just",
+            backend: ReleaseBackend::Github,
             owner: JUST_GITHUB_OWNER,
             repo: JUST_GITHUB_REPO,
             tag_prefix: JUST_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(just_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(just_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -331,14 +685,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "oha",
+            backend: ReleaseBackend::Github,
             owner: OHA_GITHUB_OWNER,
             repo: OHA_GITHUB_REPO,
             tag_prefix: OHA_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(oha_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(oha_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -347,14 +706,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "actionlint",
+            backend: ReleaseBackend::Github,
             owner: ACTIONLINT_GITHUB_OWNER,
             repo: ACTIONLINT_GITHUB_REPO,
             tag_prefix: ACTIONLINT_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(actionlint_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(actionlint_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -363,14 +727,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "sccache",
+            backend: ReleaseBackend::Github,
             owner: SCCACHE_GITHUB_OWNER,
             repo: SCCACHE_GITHUB_REPO,
             tag_prefix: SCCACHE_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(sccache_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(sccache_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -379,14 +748,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "protobuf",
+            backend: ReleaseBackend::Github,
             owner: PROTOBUF_GITHUB_OWNER,
             repo: PROTOBUF_GITHUB_REPO,
             tag_prefix: PROTOBUF_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(protoc_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(protoc_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -395,14 +769,21 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &STARSHIP_SHELL_INIT,
+        completions: Some(CompletionSource::Generate {
+            args: &["completions", "{shell}"],
+        }),
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "starship",
+            backend: ReleaseBackend::Github,
             owner: STARSHIP_GITHUB_OWNER,
             repo: STARSHIP_GITHUB_REPO,
             tag_prefix: STARSHIP_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(starship_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(starship_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -411,14 +792,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "git-cliff",
+            backend: ReleaseBackend::Github,
             owner: GIT_CLIFF_GITHUB_OWNER,
             repo: GIT_CLIFF_GITHUB_REPO,
             tag_prefix: GIT_CLIFF_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(git_cliff_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(git_cliff_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -427,14 +813,21 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "cargo-release",
+            backend: ReleaseBackend::Github,
             owner: CARGO_RELEASE_GITHUB_OWNER,
             repo: CARGO_RELEASE_GITHUB_REPO,
             tag_prefix: CARGO_RELEASE_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(cargo_release_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(
+                cargo_release_expected_asset_name,
+            )),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -443,14 +836,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "cargo-nextest",
+            backend: ReleaseBackend::Github,
             owner: NEXTEST_GITHUB_OWNER,
             repo: NEXTEST_GITHUB_REPO,
             tag_prefix: NEXTEST_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(nextest_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(nextest_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -459,14 +857,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 verified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "cargo-fuzz",
+            backend: ReleaseBackend::Github,
             owner: CARGO_FUZZ_GITHUB_OWNER,
             repo: CARGO_FUZZ_GITHUB_REPO,
             tag_prefix: CARGO_FUZZ_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(cargo_fuzz_expected_asset_name),
-            verification: GithubReleaseVerification::RequiredSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(cargo_fuzz_expected_asset_name)),
+            verification: ReleaseVerification::RequiredSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -475,14 +878,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         source_label: "GitHub Release (SHA-256 unavailable; unverified)",
         layout: ToolLayout::Binary,
         package: None,
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &[],
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "cross",
+            backend: ReleaseBackend::Github,
             owner: CROSS_GITHUB_OWNER,
             repo: CROSS_GITHUB_REPO,
             tag_prefix: CROSS_GITHUB_TAG_PREFIX,
-            expected_asset_name: Some(cross_expected_asset_name),
-            verification: GithubReleaseVerification::NoSha256Digest,
-            track: GithubReleaseTrack::VersionedTags,
+            expected_asset_name: Some(ExpectedAssetName::Resolver(cross_expected_asset_name)),
+            verification: ReleaseVerification::NoSha256Digest,
+            track: ReleaseTrack::VersionedTags,
         }),
     },
     ToolPolicy {
@@ -493,14 +901,19 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
         package: Some(PackagePolicy {
             entry_relpath: "ble.sh",
         }),
-        github_release: Some(GithubReleasePolicy {
+        shell_init: &BLESH_SHELL_INIT,
+        completions: None,
+        exposed: &[],
+        origin: ToolOrigin::Catalog,
+        release: Some(ReleasePolicy {
             project_label: "ble.sh",
+            backend: ReleaseBackend::Github,
             owner: BLESH_GITHUB_OWNER,
             repo: BLESH_GITHUB_REPO,
             tag_prefix: "",
             expected_asset_name: None,
-            verification: GithubReleaseVerification::NoSha256Digest,
-            track: GithubReleaseTrack::RollingTagAssets {
+            verification: ReleaseVerification::NoSha256Digest,
+            track: ReleaseTrack::RollingTagAssets {
                 tag: BLESH_NIGHTLY_TAG,
                 asset_prefix: "ble-nightly-",
                 asset_suffix: ".tar.xz",
@@ -510,15 +923,65 @@ const TOOL_POLICIES: [ToolPolicy; 23] = [
     },
 ];
 
+static TOOL_CATALOG: LazyLock<ToolCatalog> = LazyLock::new(ToolCatalog::load);
+/// Ad hoc `owner/repo` policies registered while this process runs; see `adhoc.rs`.
+static ADHOC_POLICIES: Mutex<Vec<ToolPolicy>> = Mutex::new(Vec::new());
+
+struct ToolCatalog {
+    policies: Vec<ToolPolicy>,
+    registry_error: Option<String>,
+}
+
+impl ToolCatalog {
+    fn load() -> Self {
+        let mut policies = TOOL_POLICIES.to_vec();
+        match super::registry::load_registry_policies(&TOOL_POLICIES) {
+            Ok(extra) => {
+                policies.extend(extra);
+                Self {
+                    policies,
+                    registry_error: None,
+                }
+            }
+            Err(err) => Self {
+                policies,
+                registry_error: Some(format!("{err:#}")),
+            },
+        }
+    }
+}
+
+/// Built-in tool policies followed by entries loaded from the user and system registries.
 pub(super) fn tool_policies() -> &'static [ToolPolicy] {
-    &TOOL_POLICIES
+    &TOOL_CATALOG.policies
 }
 
+pub(super) fn ensure_tool_registry_loaded() -> Result<()> {
+    if let Some(err) = &TOOL_CATALOG.registry_error {
+        bail!("invalid tool registry: {err}");
+    }
+    Ok(())
+}
+
+/// Catalog and registry entries win over ad hoc policies with the same name.
 pub(super) fn find_tool_policy(name: &str) -> Option<ToolPolicy> {
     tool_policies()
         .iter()
         .copied()
         .find(|policy| policy.matches(name))
+        .or_else(|| {
+            ADHOC_POLICIES
+                .lock()
+                .ok()
+                .and_then(|policies| policies.iter().copied().find(|policy| policy.matches(name)))
+        })
+}
+
+pub(super) fn register_adhoc_policy(policy: ToolPolicy) {
+    if let Ok(mut policies) = ADHOC_POLICIES.lock() {
+        policies.retain(|existing| existing.canonical_name != policy.canonical_name);
+        policies.push(policy);
+    }
 }
 
 pub(super) fn supported_tool_names_csv() -> String {
@@ -535,153 +998,177 @@ pub(super) fn canonical_tool_name(name: &str) -> String {
         .unwrap_or_else(|| name.to_string())
 }
 
-fn codex_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("codex-{}.tar.gz", codex_target_triple()?))
+fn codex_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("codex-{}.tar.gz", codex_target_triple(target)?))
 }
 
-fn za_expected_asset_name(version: &str) -> Result<String> {
-    Ok(format!("za-{version}-{}.tar.gz", za_target_triple()?))
+fn za_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("za-{version}-{}.tar.gz", za_target_triple(target)?))
 }
 
-fn docker_compose_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("docker-compose-{}", docker_compose_target()?))
+fn docker_compose_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("docker-compose-{}", docker_compose_target(target)?))
 }
 
-fn ripgrep_expected_asset_name(version: &str) -> Result<String> {
+fn ripgrep_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "ripgrep-{version}-{}.tar.gz",
-        ripgrep_target_triple()?
+        ripgrep_target_triple(target)?
     ))
 }
 
-fn fd_expected_asset_name(version: &str) -> Result<String> {
-    Ok(format!("fd-v{version}-{}.tar.gz", fd_target_triple()?))
+fn fd_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!(
+        "fd-v{version}-{}.tar.gz",
+        fd_target_triple(target)?
+    ))
 }
 
-fn tcping_expected_asset_name(version: &str) -> Result<String> {
+fn tcping_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "tcping-{version}-{}.tar.gz",
-        tcping_target_triple()?
+        tcping_target_triple(target)?
     ))
 }
 
-fn motdyn_expected_asset_name(version: &str) -> Result<String> {
+fn motdyn_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "motdyn-{version}-{}.tar.gz",
-        motdyn_target_triple()?
+        motdyn_target_triple(target)?
     ))
 }
 
-fn bottom_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("bottom_{}.tar.gz", bottom_target_triple()?))
+fn bottom_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("bottom_{}.tar.gz", bottom_target_triple(target)?))
 }
 
-fn bpftop_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("bpftop-{}", bpftop_target_triple()?))
+fn bpftop_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("bpftop-{}", bpftop_target_triple(target)?))
 }
 
-fn hyperfine_expected_asset_name(version: &str) -> Result<String> {
+fn hyperfine_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "hyperfine-v{version}-{}.tar.gz",
-        hyperfine_target_triple()?
+        hyperfine_target_triple(target)?
     ))
 }
 
-fn dust_expected_asset_name(version: &str) -> Result<String> {
-    Ok(format!("dust-v{version}-{}.tar.gz", dust_target_triple()?))
+fn dust_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!(
+        "dust-v{version}-{}.tar.gz",
+        dust_target_triple(target)?
+    ))
 }
 
-fn just_expected_asset_name(version: &str) -> Result<String> {
-    Ok(format!("just-{version}-{}.tar.gz", just_target_triple()?))
+fn just_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!(
+        "just-{version}-{}.tar.gz",
+        just_target_triple(target)?
+    ))
 }
 
-fn oha_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("oha-{}", oha_target()?))
+fn oha_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("oha-{}", oha_target(target)?))
 }
 
-fn actionlint_expected_asset_name(version: &str) -> Result<String> {
+fn actionlint_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "actionlint_{version}_{}.tar.gz",
-        actionlint_target()?
+        actionlint_target(target)?
     ))
 }
 
-fn sccache_expected_asset_name(version: &str) -> Result<String> {
+fn sccache_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "sccache-v{version}-{}.tar.gz",
-        sccache_target_triple()?
+        sccache_target_triple(target)?
     ))
 }
 
-fn protoc_expected_asset_name(version: &str) -> Result<String> {
-    Ok(format!("protoc-{version}-{}.zip", protoc_target()?))
+fn protoc_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("protoc-{version}-{}.zip", protoc_target(target)?))
 }
 
-fn starship_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("starship-{}.tar.gz", starship_target_triple()?))
+fn starship_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!(
+        "starship-{}.tar.gz",
+        starship_target_triple(target)?
+    ))
 }
 
-fn git_cliff_expected_asset_name(version: &str) -> Result<String> {
+fn git_cliff_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "git-cliff-{version}-{}.tar.gz",
-        git_cliff_target_triple()?
+        git_cliff_target_triple(target)?
     ))
 }
 
-fn cargo_release_expected_asset_name(version: &str) -> Result<String> {
+fn cargo_release_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "cargo-release-v{version}-{}.tar.gz",
-        cargo_release_target_triple()?
+        cargo_release_target_triple(target)?
     ))
 }
 
-fn nextest_expected_asset_name(version: &str) -> Result<String> {
+fn nextest_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "cargo-nextest-{version}-{}.tar.gz",
-        nextest_target_triple()?
+        nextest_target_triple(target)?
     ))
 }
 
-fn cargo_fuzz_expected_asset_name(version: &str) -> Result<String> {
+fn cargo_fuzz_expected_asset_name(version: &str, target: ToolTarget) -> Result<String> {
     Ok(format!(
         "cargo-fuzz-{version}-{}.tar.gz",
-        cargo_fuzz_target_triple()?
+        cargo_fuzz_target_triple(target)?
     ))
 }
 
-fn cross_expected_asset_name(_version: &str) -> Result<String> {
-    Ok(format!("cross-{}.tar.gz", cross_target_triple()?))
+fn cross_expected_asset_name(_version: &str, target: ToolTarget) -> Result<String> {
+    Ok(format!("cross-{}.tar.gz", cross_target_triple(target)?))
 }
 
-fn codex_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn codex_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "codex",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => target.linux_libc_asset(
+            "codex",
+            Some("aarch64-unknown-linux-musl"),
+            Some("aarch64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for codex release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn za_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn za_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("za", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("za", Some("aarch64-unknown-linux-musl"), None)
+        }
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for za release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn docker_compose_target() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
+fn docker_compose_target(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
         ("linux", "x86_64") => Ok("linux-x86_64"),
         ("linux", "aarch64") => Ok("linux-aarch64"),
         ("macos", "x86_64") => Ok("darwin-x86_64"),
@@ -690,176 +1177,230 @@ fn docker_compose_target() -> Result<&'static str> {
         ("windows", "aarch64") => Ok("windows-aarch64.exe"),
         _ => bail!(
             "unsupported platform for docker-compose release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn ripgrep_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-gnu"),
+fn ripgrep_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("ripgrep", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("ripgrep", None, Some("aarch64-unknown-linux-gnu"))
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for ripgrep release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn fd_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn fd_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "fd",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => target.linux_libc_asset(
+            "fd",
+            Some("aarch64-unknown-linux-musl"),
+            Some("aarch64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for fd release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn tcping_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn tcping_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("tcping-rs", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("tcping-rs", Some("aarch64-unknown-linux-musl"), None)
+        }
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for tcping-rs release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn motdyn_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn motdyn_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("motdyn", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("motdyn", Some("aarch64-unknown-linux-musl"), None)
+        }
         _ => bail!(
             "unsupported platform for motdyn release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn bottom_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn bottom_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "bottom",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => target.linux_libc_asset(
+            "bottom",
+            Some("aarch64-unknown-linux-musl"),
+            Some("aarch64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for bottom release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn bpftop_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-gnu"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-gnu"),
+fn bpftop_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("bpftop", None, Some("x86_64-unknown-linux-gnu"))
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("bpftop", None, Some("aarch64-unknown-linux-gnu"))
+        }
         _ => bail!(
             "unsupported platform for bpftop release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn hyperfine_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-gnu"),
+fn hyperfine_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "hyperfine",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("hyperfine", None, Some("aarch64-unknown-linux-gnu"))
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for hyperfine release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn dust_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn dust_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "dust",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => target.linux_libc_asset(
+            "dust",
+            Some("aarch64-unknown-linux-musl"),
+            Some("aarch64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         _ => bail!(
             "unsupported platform for dust release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn just_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn just_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("just", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("just", Some("aarch64-unknown-linux-musl"), None)
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for just release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn oha_target() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
+fn oha_target(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
         ("linux", "x86_64") => Ok("linux-amd64"),
         ("linux", "aarch64") => Ok("linux-arm64"),
         ("macos", "x86_64") => Ok("macos-amd64"),
         ("macos", "aarch64") => Ok("macos-arm64"),
         _ => bail!(
             "unsupported platform for oha release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn actionlint_target() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
+fn actionlint_target(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
         ("linux", "x86_64") => Ok("linux_amd64"),
         ("linux", "aarch64") => Ok("linux_arm64"),
         ("macos", "x86_64") => Ok("darwin_amd64"),
         ("macos", "aarch64") => Ok("darwin_arm64"),
         _ => bail!(
             "unsupported platform for actionlint release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn sccache_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn sccache_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("sccache", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("sccache", Some("aarch64-unknown-linux-musl"), None)
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for sccache release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn protoc_target() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
+fn protoc_target(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
         ("linux", "x86_64") => Ok("linux-x86_64"),
         ("linux", "aarch64") => Ok("linux-aarch_64"),
         ("macos", "x86_64") => Ok("osx-x86_64"),
@@ -868,91 +1409,123 @@ fn protoc_target() -> Result<&'static str> {
         ("windows", "x86") => Ok("win32"),
         _ => bail!(
             "unsupported platform for protobuf protoc release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn starship_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn starship_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "starship",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("starship", Some("aarch64-unknown-linux-musl"), None)
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for starship release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn git_cliff_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn git_cliff_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "git-cliff",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => target.linux_libc_asset(
+            "git-cliff",
+            Some("aarch64-unknown-linux-musl"),
+            Some("aarch64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for git-cliff release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn cargo_release_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn cargo_release_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("cargo-release", Some("x86_64-unknown-linux-musl"), None)
+        }
+        ("linux", "aarch64") => {
+            target.linux_libc_asset("cargo-release", Some("aarch64-unknown-linux-musl"), None)
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         _ => bail!(
             "unsupported platform for cargo-release release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn nextest_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
-        ("linux", "aarch64") => Ok("aarch64-unknown-linux-musl"),
+fn nextest_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "cargo-nextest",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
+        ("linux", "aarch64") => target.linux_libc_asset(
+            "cargo-nextest",
+            Some("aarch64-unknown-linux-musl"),
+            Some("aarch64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
         ("windows", "x86_64") => Ok("x86_64-pc-windows-msvc"),
         ("windows", "aarch64") => Ok("aarch64-pc-windows-msvc"),
         _ => bail!(
             "unsupported platform for cargo-nextest release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn cargo_fuzz_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
+fn cargo_fuzz_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => {
+            target.linux_libc_asset("cargo-fuzz", Some("x86_64-unknown-linux-musl"), None)
+        }
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         _ => bail!(
             "unsupported platform for cargo-fuzz release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
 
-fn cross_target_triple() -> Result<&'static str> {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Ok("x86_64-unknown-linux-musl"),
+fn cross_target_triple(target: ToolTarget) -> Result<&'static str> {
+    match (target.os, target.arch) {
+        ("linux", "x86_64") => target.linux_libc_asset(
+            "cross",
+            Some("x86_64-unknown-linux-musl"),
+            Some("x86_64-unknown-linux-gnu"),
+        ),
         ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
         ("windows", "x86_64") => Ok("x86_64-pc-windows-msvc"),
         _ => bail!(
             "unsupported platform for cross release asset: {}-{}",
-            env::consts::ARCH,
-            env::consts::OS
+            target.arch,
+            target.os
         ),
     }
 }
@@ -0,0 +1,480 @@
+//! User and system tool registries that extend the built-in policy catalog.
+
+use super::policy::{
+    ASSET_TEMPLATE_PLACEHOLDERS, AssetTemplate, EntryPlacement, ExpectedAssetName, ExposedEntry,
+    IntegrationShell, ReleaseBackend, ReleaseTrack, ReleaseVerification, ShellInitPosition,
+    ShellInitSnippet, ToolOrigin,
+};
+use super::verify::MinisignPublicKey;
+use super::*;
+use std::collections::BTreeMap;
+
+const TOOL_REGISTRY_FILE_NAME: &str = "tools.toml";
+const SYSTEM_TOOL_REGISTRY_PATH: &str = "/etc/za/tools.toml";
+const GITLAB_DEFAULT_BASE_URL: &str = "https://gitlab.com";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolRegistryFile {
+    #[serde(default)]
+    tools: BTreeMap<String, ToolRegistryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolRegistryEntry {
+    #[serde(default)]
+    forge: ToolRegistryForge,
+    #[serde(default)]
+    base_url: Option<String>,
+    owner: String,
+    repo: String,
+    #[serde(default)]
+    tag_prefix: String,
+    asset: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    layout: ToolRegistryLayout,
+    #[serde(default)]
+    entry: Option<String>,
+    /// Defaults to `sha256-digest` on GitHub and `checksum-file` elsewhere.
+    #[serde(default)]
+    verification: Option<ToolRegistryVerification>,
+    #[serde(default)]
+    minisign_key: Option<String>,
+    #[serde(default)]
+    targets: BTreeMap<String, String>,
+    /// Per-shell init snippets written after activation; `{path}` is the active entry.
+    #[serde(default)]
+    shell_init: ToolRegistryShellInit,
+    /// Extra links made on activation, e.g. more binaries from a package payload.
+    #[serde(default)]
+    expose: Vec<ToolRegistryExpose>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolRegistryExpose {
+    /// Payload-relative file of a package; omitted to expose a binary tool's executable.
+    #[serde(default)]
+    path: Option<String>,
+    /// Link name; defaults to the file name of `path`, else the tool name.
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    placement: ToolRegistryPlacement,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ToolRegistryPlacement {
+    #[default]
+    Bin,
+    DockerCliPlugin,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolRegistryShellInit {
+    #[serde(default)]
+    bash: Option<String>,
+    #[serde(default)]
+    zsh: Option<String>,
+    #[serde(default)]
+    fish: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ToolRegistryLayout {
+    #[default]
+    Binary,
+    Package,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ToolRegistryForge {
+    #[default]
+    Github,
+    Gitlab,
+    #[serde(alias = "forgejo")]
+    Gitea,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ToolRegistryVerification {
+    Sha256Digest,
+    ChecksumFile,
+    Minisign,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ToolRegistryOrigin {
+    System,
+    User,
+}
+
+impl ToolRegistryOrigin {
+    fn label(self) -> &'static str {
+        match self {
+            Self::System => "system registry",
+            Self::User => "user registry",
+        }
+    }
+}
+
+/// Registry files in precedence order; later files override earlier entries with the same name.
+pub(super) fn tool_registry_paths() -> Vec<(ToolRegistryOrigin, PathBuf)> {
+    let mut out = vec![(
+        ToolRegistryOrigin::System,
+        PathBuf::from(SYSTEM_TOOL_REGISTRY_PATH),
+    )];
+    if let Some(dir) = za_config::config_base_dir() {
+        out.push((ToolRegistryOrigin::User, dir.join(TOOL_REGISTRY_FILE_NAME)));
+    }
+    out
+}
+
+pub(super) fn load_registry_policies(builtins: &[ToolPolicy]) -> Result<Vec<ToolPolicy>> {
+    let mut sources = Vec::new();
+    for (origin, path) in tool_registry_paths() {
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+        };
+        sources.push((origin, path, raw));
+    }
+    let sources = sources
+        .iter()
+        .map(|(origin, path, raw)| (*origin, path.as_path(), raw.as_str()))
+        .collect::<Vec<_>>();
+    parse_tool_registries(&sources, builtins)
+}
+
+pub(super) fn parse_tool_registries(
+    sources: &[(ToolRegistryOrigin, &Path, &str)],
+    builtins: &[ToolPolicy],
+) -> Result<Vec<ToolPolicy>> {
+    let mut merged: BTreeMap<String, (ToolRegistryOrigin, ToolRegistryEntry)> = BTreeMap::new();
+    for (origin, path, raw) in sources {
+        let file: ToolRegistryFile =
+            toml::from_str(raw).with_context(|| format!("parse {}", path.display()))?;
+        for (name, entry) in file.tools {
+            merged.insert(name, (*origin, entry));
+        }
+    }
+
+    let mut claimed = HashSet::new();
+    for policy in builtins {
+        claimed.extend(policy.supported_names());
+    }
+
+    let mut policies = Vec::with_capacity(merged.len());
+    for (name, (origin, entry)) in merged {
+        let policy = registry_entry_policy(&name, origin, entry)
+            .with_context(|| format!("tool `{name}` in {}", origin.label()))?;
+        for supported in policy.supported_names() {
+            if !claimed.insert(supported) {
+                bail!(
+                    "tool `{name}` in {} reuses name `{supported}` that is already registered",
+                    origin.label()
+                );
+            }
+        }
+        policies.push(policy);
+    }
+    Ok(policies)
+}
+
+fn registry_entry_policy(
+    name: &str,
+    origin: ToolRegistryOrigin,
+    entry: ToolRegistryEntry,
+) -> Result<ToolPolicy> {
+    validate_name(name)?;
+    for alias in &entry.aliases {
+        validate_name(alias).context("invalid alias")?;
+    }
+    if entry.owner.trim().is_empty() || entry.repo.trim().is_empty() {
+        bail!("`owner` and `repo` must not be empty");
+    }
+    validate_asset_template(&entry.asset)?;
+
+    let package = match (entry.layout, entry.entry) {
+        (ToolRegistryLayout::Binary, None) => None,
+        (ToolRegistryLayout::Binary, Some(_)) => {
+            bail!("`entry` is only valid with `layout = \"package\"`")
+        }
+        (ToolRegistryLayout::Package, None) => bail!("`layout = \"package\"` requires `entry`"),
+        (ToolRegistryLayout::Package, Some(entry_relpath)) => {
+            validate_package_entry(&entry_relpath)?;
+            Some(PackagePolicy {
+                entry_relpath: leak_str(entry_relpath),
+            })
+        }
+    };
+    let layout = match entry.layout {
+        ToolRegistryLayout::Binary => ToolLayout::Binary,
+        ToolRegistryLayout::Package => ToolLayout::Package,
+    };
+    let backend = match (entry.forge, entry.base_url) {
+        (ToolRegistryForge::Github, None) => ReleaseBackend::Github,
+        (ToolRegistryForge::Github, Some(_)) => {
+            bail!("`base_url` is only valid with `forge = \"gitlab\"` or `forge = \"gitea\"`")
+        }
+        (ToolRegistryForge::Gitlab, base_url) => ReleaseBackend::Gitlab {
+            base_url: leak_str(validate_base_url(
+                base_url.unwrap_or_else(|| GITLAB_DEFAULT_BASE_URL.to_string()),
+            )?),
+        },
+        (ToolRegistryForge::Gitea, None) => bail!("`forge = \"gitea\"` requires `base_url`"),
+        (ToolRegistryForge::Gitea, Some(base_url)) => ReleaseBackend::Gitea {
+            base_url: leak_str(validate_base_url(base_url)?),
+        },
+    };
+    let verification = entry
+        .verification
+        .unwrap_or(if backend.publishes_asset_digests() {
+            ToolRegistryVerification::Sha256Digest
+        } else {
+            ToolRegistryVerification::ChecksumFile
+        });
+    if verification == ToolRegistryVerification::Sha256Digest && !backend.publishes_asset_digests()
+    {
+        bail!(
+            "{} releases do not publish asset digests; use `verification = \"checksum-file\"`, `\"minisign\"` or `\"none\"`",
+            backend.label()
+        );
+    }
+    let verification = match (verification, entry.minisign_key) {
+        (ToolRegistryVerification::Minisign, None) => {
+            bail!("`verification = \"minisign\"` requires `minisign_key`")
+        }
+        (ToolRegistryVerification::Minisign, Some(key)) => {
+            MinisignPublicKey::parse(&key).context("invalid `minisign_key`")?;
+            ReleaseVerification::Minisign {
+                public_key: leak_str(key),
+            }
+        }
+        (_, Some(_)) => bail!("`minisign_key` is only valid with `verification = \"minisign\"`"),
+        (ToolRegistryVerification::Sha256Digest, None) => ReleaseVerification::RequiredSha256Digest,
+        (ToolRegistryVerification::ChecksumFile, None) => ReleaseVerification::ChecksumFile,
+        (ToolRegistryVerification::None, None) => ReleaseVerification::NoSha256Digest,
+    };
+    let source_label = match verification {
+        ReleaseVerification::RequiredSha256Digest => {
+            format!(
+                "{} Release (SHA-256 verified; {})",
+                backend.label(),
+                origin.label()
+            )
+        }
+        ReleaseVerification::ChecksumFile => {
+            format!(
+                "{} Release (checksum file verified; {})",
+                backend.label(),
+                origin.label()
+            )
+        }
+        ReleaseVerification::Minisign { .. } => {
+            format!(
+                "{} Release (minisign verified; {})",
+                backend.label(),
+                origin.label()
+            )
+        }
+        ReleaseVerification::NoSha256Digest | ReleaseVerification::AvailableDigest { .. } => {
+            format!(
+                "{} Release (SHA-256 unavailable; unverified; {})",
+                backend.label(),
+                origin.label()
+            )
+        }
+    };
+
+    let shell_init = [
+        (IntegrationShell::Bash, entry.shell_init.bash),
+        (IntegrationShell::Zsh, entry.shell_init.zsh),
+        (IntegrationShell::Fish, entry.shell_init.fish),
+    ]
+    .into_iter()
+    .filter_map(|(shell, body)| body.map(|body| (shell, body)))
+    .map(|(shell, body)| {
+        validate_shell_init(shell, &body)?;
+        Ok(ShellInitSnippet {
+            shell,
+            part: None,
+            position: ShellInitPosition::Bottom,
+            ide_terminal_helper: false,
+            body: leak_str(body.trim_end().to_string()),
+        })
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+    let exposed = registry_exposed_entries(name, layout, entry.expose)?;
+
+    let canonical_name = leak_str(name.to_string());
+    let aliases = entry.aliases.into_iter().map(leak_str).collect::<Vec<_>>();
+    let targets = entry
+        .targets
+        .into_iter()
+        .map(|(platform, target)| (leak_str(platform), leak_str(target)))
+        .collect::<Vec<_>>();
+
+    Ok(ToolPolicy {
+        canonical_name,
+        aliases: Box::leak(aliases.into_boxed_slice()),
+        source_label: leak_str(source_label),
+        layout,
+        package,
+        shell_init: Box::leak(shell_init.into_boxed_slice()),
+        completions: None,
+        exposed: Box::leak(exposed.into_boxed_slice()),
+        origin: match origin {
+            ToolRegistryOrigin::System => ToolOrigin::SystemRegistry,
+            ToolRegistryOrigin::User => ToolOrigin::UserRegistry,
+        },
+        release: Some(ReleasePolicy {
+            project_label: canonical_name,
+            backend,
+            owner: leak_str(entry.owner),
+            repo: leak_str(entry.repo),
+            tag_prefix: leak_str(entry.tag_prefix),
+            expected_asset_name: Some(ExpectedAssetName::Template(AssetTemplate {
+                project_label: canonical_name,
+                template: leak_str(entry.asset),
+                targets: Box::leak(targets.into_boxed_slice()),
+            })),
+            verification,
+            track: ReleaseTrack::VersionedTags,
+        }),
+    })
+}
+
+fn registry_exposed_entries(
+    name: &str,
+    layout: ToolLayout,
+    expose: Vec<ToolRegistryExpose>,
+) -> Result<Vec<ExposedEntry>> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::with_capacity(expose.len());
+    for item in expose {
+        let relpath = match (layout, item.path) {
+            (ToolLayout::Package, Some(path)) => {
+                validate_package_entry(&path).context("invalid `expose.path`")?;
+                path
+            }
+            (ToolLayout::Package, None) => bail!("`expose` entries of a package require `path`"),
+            (ToolLayout::Binary, Some(_)) => {
+                bail!("`expose.path` is only valid with `layout = \"package\"`")
+            }
+            (ToolLayout::Binary, None) => String::new(),
+        };
+        let link_name = match item.name {
+            Some(link_name) => link_name,
+            None if relpath.is_empty() => name.to_string(),
+            None => Path::new(&relpath)
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .unwrap_or(name)
+                .to_string(),
+        };
+        validate_name(&link_name).context("invalid `expose.name`")?;
+        if layout == ToolLayout::Binary
+            && item.placement == ToolRegistryPlacement::Bin
+            && link_name == name
+        {
+            bail!("`expose` must not repeat the tool's own `bin/{name}` link");
+        }
+        if !seen.insert((item.placement, link_name.clone())) {
+            bail!("`expose` lists `{link_name}` twice for the same placement");
+        }
+        let link_name = leak_str(link_name);
+        out.push(ExposedEntry {
+            relpath: leak_str(relpath),
+            placement: match item.placement {
+                ToolRegistryPlacement::Bin => EntryPlacement::Bin(link_name),
+                ToolRegistryPlacement::DockerCliPlugin => {
+                    EntryPlacement::DockerCliPlugin(link_name)
+                }
+            },
+        });
+    }
+    Ok(out)
+}
+
+fn validate_base_url(base_url: String) -> Result<String> {
+    let trimmed = base_url.trim().trim_end_matches('/');
+    let host = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"));
+    if host.is_none_or(|host| host.is_empty() || host.contains(['?', '#'])) {
+        bail!("`base_url` must be an http(s) URL such as `https://gitlab.example.com`");
+    }
+    Ok(trimmed.to_string())
+}
+
+fn validate_asset_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        bail!("`asset` must not be empty");
+    }
+    if template.contains('/') {
+        bail!("`asset` must be a release asset file name, not a path");
+    }
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let tail = &rest[start + 1..];
+        let Some(end) = tail.find('}') else {
+            bail!("`asset` template `{template}` has an unterminated placeholder");
+        };
+        let placeholder = &tail[..end];
+        if !ASSET_TEMPLATE_PLACEHOLDERS.contains(&placeholder) {
+            bail!(
+                "`asset` template `{template}` uses unknown placeholder `{{{placeholder}}}`; expected one of: {}",
+                ASSET_TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|name| format!("{{{name}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        rest = &tail[end + 1..];
+    }
+    Ok(())
+}
+
+fn validate_shell_init(shell: IntegrationShell, body: &str) -> Result<()> {
+    if body.trim().is_empty() {
+        bail!("`shell_init.{}` must not be empty", shell.name());
+    }
+    // A nested marker would make the managed block impossible to remove cleanly.
+    if body.contains("# >>> za ") || body.contains("# <<< za ") {
+        bail!(
+            "`shell_init.{}` must not contain za managed block markers",
+            shell.name()
+        );
+    }
+    Ok(())
+}
+
+fn validate_package_entry(entry_relpath: &str) -> Result<()> {
+    let path = Path::new(entry_relpath);
+    if entry_relpath.trim().is_empty()
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|component| !matches!(component, std::path::Component::Normal(_)))
+    {
+        bail!("`entry` must be a relative path inside the package payload");
+    }
+    Ok(())
+}
+
+pub(super) fn leak_str(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
@@ -0,0 +1,552 @@
+//! `za tool sbom`: describes the active tools of a scope as a CycloneDX or SPDX document.
+
+use super::policy::ReleaseBackend;
+use super::source::{fetch_repository_license, release_repository_url};
+use super::*;
+use crate::cli::ToolSbomFormat;
+use graviola::hashing::{Hash, HashContext, Sha256};
+
+const CYCLONEDX_SPEC_VERSION: &str = "1.5";
+const SPDX_VERSION: &str = "SPDX-2.3";
+const SPDX_NOASSERTION: &str = "NOASSERTION";
+
+/// One active tool version with the provenance its manifest and policy record.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct SbomEntry {
+    pub(super) name: String,
+    pub(super) version: String,
+    pub(super) installed_at_unix_secs: Option<u64>,
+    /// Digest of the installed payload.
+    pub(super) sha256: Option<String>,
+    pub(super) asset: Option<ReleaseAsset>,
+    pub(super) repository: Option<String>,
+    pub(super) purl: Option<String>,
+    pub(super) license: Option<String>,
+}
+
+/// Document-level fields that change on every run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SbomDocumentInfo {
+    pub(super) scope: &'static str,
+    pub(super) created_unix_secs: u64,
+    pub(super) uuid: String,
+}
+
+pub(super) fn run_sbom(
+    home: &ToolHome,
+    format: ToolSbomFormat,
+    output: Option<&Path>,
+) -> Result<i32> {
+    let mut entries = collect_sbom_entries(home)?;
+    fill_repository_licenses(&mut entries);
+    let created_unix_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let info = SbomDocumentInfo {
+        scope: home.scope.label(),
+        created_unix_secs,
+        uuid: document_uuid(&entries),
+    };
+    let rendered = match format {
+        ToolSbomFormat::Cyclonedx => render_cyclonedx(&entries, &info)?,
+        ToolSbomFormat::Spdx => render_spdx(&entries, &info)?,
+    };
+    let Some(output) = output else {
+        println!("{rendered}");
+        return Ok(0);
+    };
+    write_file_atomically(output, format!("{rendered}\n"))
+        .with_context(|| format!("write {}", output.display()))?;
+    println!(
+        "📤 Wrote an SBOM of {} tool(s) to {}",
+        entries.len(),
+        output.display()
+    );
+    Ok(0)
+}
+
+pub(super) fn collect_sbom_entries(home: &ToolHome) -> Result<Vec<SbomEntry>> {
+    let mut entries = Vec::new();
+    for name in collect_managed_tool_names(home)? {
+        let Some(version) = read_current_version(home, &name)? else {
+            continue;
+        };
+        let tool = ToolRef {
+            name,
+            version: normalize_version(&version),
+        };
+        let manifest = read_manifest(home, &tool)?;
+        let release = find_tool_policy(&tool.name).and_then(|policy| policy.release);
+        let purl = release
+            .filter(|release| release.backend == ReleaseBackend::Github)
+            .map(|release| {
+                format!(
+                    "pkg:github/{}/{}@{}",
+                    release.owner.to_ascii_lowercase(),
+                    release.repo.to_ascii_lowercase(),
+                    purl_encode(&format!("{}{}", release.tag_prefix, tool.version))
+                )
+            });
+        entries.push(SbomEntry {
+            installed_at_unix_secs: manifest
+                .as_ref()
+                .map(|manifest| manifest.installed_at_unix_secs),
+            sha256: manifest.as_ref().map(|manifest| manifest.sha256.clone()),
+            asset: manifest.as_ref().and_then(|manifest| {
+                Some(ReleaseAsset {
+                    url: manifest.asset_url.clone()?,
+                    sha256: manifest.asset_sha256.clone()?,
+                })
+            }),
+            repository: release.map(release_repository_url),
+            purl,
+            license: None,
+            name: tool.name,
+            version: tool.version,
+        });
+    }
+    Ok(entries)
+}
+
+/// Looks up each repository's declared license once. A failed lookup only leaves the license
+/// unasserted, so an offline run still produces a document.
+fn fill_repository_licenses(entries: &mut [SbomEntry]) {
+    let mut licenses: HashMap<String, Option<String>> = HashMap::new();
+    for entry in entries.iter_mut() {
+        let Some(release) = find_tool_policy(&entry.name).and_then(|policy| policy.release) else {
+            continue;
+        };
+        let repository = release_repository_url(release);
+        let license = licenses.entry(repository.clone()).or_insert_with(|| {
+            match fetch_repository_license(release, za_config::ProxyScope::Tool) {
+                Ok(license) => license,
+                Err(err) => {
+                    eprintln!("⚠️  Could not read the license of {repository}: {err:#}");
+                    None
+                }
+            }
+        });
+        entry.license = license.clone();
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxBom<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    serial_number: String,
+    version: u32,
+    metadata: CycloneDxMetadata,
+    components: Vec<CycloneDxComponent<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxMetadata {
+    timestamp: String,
+    tools: CycloneDxTools,
+    properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxTools {
+    components: Vec<CycloneDxTool>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxTool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxComponent<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: &'a str,
+    version: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<CycloneDxHash<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<CycloneDxLicenseChoice<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_references: Vec<CycloneDxExternalReference<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxHash<'a> {
+    alg: &'static str,
+    content: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxLicenseChoice<'a> {
+    license: CycloneDxLicense<'a>,
+}
+
+/// Exactly one of `id` (a listed SPDX identifier) or `name` is set.
+#[derive(Debug, Serialize)]
+struct CycloneDxLicense<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxExternalReference<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    url: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<CycloneDxHash<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxProperty {
+    name: &'static str,
+    value: String,
+}
+
+/// CycloneDX 1.5 JSON document.
+pub(super) fn render_cyclonedx(entries: &[SbomEntry], info: &SbomDocumentInfo) -> Result<String> {
+    let components = entries
+        .iter()
+        .map(|entry| {
+            let mut external_references = Vec::new();
+            if let Some(repository) = entry.repository.as_deref() {
+                external_references.push(CycloneDxExternalReference {
+                    kind: "vcs",
+                    url: repository,
+                    hashes: Vec::new(),
+                });
+            }
+            if let Some(asset) = entry.asset.as_ref() {
+                external_references.push(CycloneDxExternalReference {
+                    kind: "distribution",
+                    url: &asset.url,
+                    hashes: vec![CycloneDxHash {
+                        alg: "SHA-256",
+                        content: &asset.sha256,
+                    }],
+                });
+            }
+            CycloneDxComponent {
+                kind: "application",
+                bom_ref: format!("{}@{}", entry.name, entry.version),
+                name: &entry.name,
+                version: &entry.version,
+                purl: entry.purl.as_deref(),
+                hashes: entry
+                    .sha256
+                    .as_deref()
+                    .map(|sha256| CycloneDxHash {
+                        alg: "SHA-256",
+                        content: sha256,
+                    })
+                    .into_iter()
+                    .collect(),
+                licenses: entry
+                    .license
+                    .as_deref()
+                    .map(|license| CycloneDxLicenseChoice {
+                        license: if is_spdx_license_id(license) {
+                            CycloneDxLicense {
+                                id: Some(license),
+                                name: None,
+                            }
+                        } else {
+                            CycloneDxLicense {
+                                id: None,
+                                name: Some(license),
+                            }
+                        },
+                    })
+                    .into_iter()
+                    .collect(),
+                external_references,
+                properties: entry
+                    .installed_at_unix_secs
+                    .map(|secs| CycloneDxProperty {
+                        name: "za:installed_at",
+                        value: format_sbom_timestamp(secs),
+                    })
+                    .into_iter()
+                    .collect(),
+            }
+        })
+        .collect();
+    let bom = CycloneDxBom {
+        bom_format: "CycloneDX",
+        spec_version: CYCLONEDX_SPEC_VERSION,
+        serial_number: format!("urn:uuid:{}", info.uuid),
+        version: 1,
+        metadata: CycloneDxMetadata {
+            timestamp: format_sbom_timestamp(info.created_unix_secs),
+            tools: CycloneDxTools {
+                components: vec![CycloneDxTool {
+                    kind: "application",
+                    name: "za",
+                    version: env!("CARGO_PKG_VERSION"),
+                }],
+            },
+            properties: vec![CycloneDxProperty {
+                name: "za:scope",
+                value: info.scope.to_string(),
+            }],
+        },
+        components,
+    };
+    serde_json::to_string_pretty(&bom).context("serialize CycloneDX SBOM")
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxDocument<'a> {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: String,
+    document_namespace: String,
+    creation_info: SpdxCreationInfo,
+    packages: Vec<SpdxPackage<'a>>,
+    relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpdxCreationInfo {
+    created: String,
+    creators: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage<'a> {
+    name: &'a str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    version_info: &'a str,
+    download_location: &'a str,
+    files_analyzed: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checksums: Vec<SpdxChecksum<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homepage: Option<&'a str>,
+    license_concluded: &'static str,
+    license_declared: &'a str,
+    copyright_text: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<SpdxExternalRef<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<SpdxAnnotation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxChecksum<'a> {
+    algorithm: &'static str,
+    checksum_value: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxExternalRef<'a> {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxAnnotation {
+    annotation_date: String,
+    annotation_type: &'static str,
+    annotator: String,
+    comment: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxRelationship {
+    spdx_element_id: String,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
+
+/// SPDX 2.3 JSON document. Each package is the downloaded release asset when one is recorded.
+pub(super) fn render_spdx(entries: &[SbomEntry], info: &SbomDocumentInfo) -> Result<String> {
+    let creator = format!("Tool: za-{}", env!("CARGO_PKG_VERSION"));
+    let packages = entries
+        .iter()
+        .map(|entry| {
+            let sha256 = entry
+                .asset
+                .as_ref()
+                .map(|asset| asset.sha256.as_str())
+                .or(entry.sha256.as_deref());
+            SpdxPackage {
+                name: &entry.name,
+                spdx_id: spdx_package_id(&entry.name),
+                version_info: &entry.version,
+                download_location: entry
+                    .asset
+                    .as_ref()
+                    .map_or(SPDX_NOASSERTION, |asset| asset.url.as_str()),
+                files_analyzed: false,
+                checksums: sha256
+                    .map(|checksum_value| SpdxChecksum {
+                        algorithm: "SHA256",
+                        checksum_value,
+                    })
+                    .into_iter()
+                    .collect(),
+                homepage: entry.repository.as_deref(),
+                license_concluded: SPDX_NOASSERTION,
+                license_declared: entry
+                    .license
+                    .as_deref()
+                    .filter(|license| is_spdx_license_id(license))
+                    .unwrap_or(SPDX_NOASSERTION),
+                copyright_text: SPDX_NOASSERTION,
+                external_refs: entry
+                    .purl
+                    .as_deref()
+                    .map(|purl| SpdxExternalRef {
+                        reference_category: "PACKAGE-MANAGER",
+                        reference_type: "purl",
+                        reference_locator: purl,
+                    })
+                    .into_iter()
+                    .collect(),
+                annotations: entry
+                    .installed_at_unix_secs
+                    .map(|secs| SpdxAnnotation {
+                        annotation_date: format_sbom_timestamp(secs),
+                        annotation_type: "OTHER",
+                        annotator: creator.clone(),
+                        comment: format!("Installed by za in {} scope", info.scope),
+                    })
+                    .into_iter()
+                    .collect(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let relationships = packages
+        .iter()
+        .map(|package| SpdxRelationship {
+            spdx_element_id: "SPDXRef-DOCUMENT".to_string(),
+            relationship_type: "DESCRIBES",
+            related_spdx_element: package.spdx_id.clone(),
+        })
+        .collect();
+    let document = SpdxDocument {
+        spdx_version: SPDX_VERSION,
+        data_license: "CC0-1.0",
+        spdx_id: "SPDXRef-DOCUMENT",
+        name: format!("za-{}-tools", info.scope),
+        document_namespace: format!(
+            "https://spdx.org/spdxdocs/za-{}-tools-{}",
+            info.scope, info.uuid
+        ),
+        creation_info: SpdxCreationInfo {
+            created: format_sbom_timestamp(info.created_unix_secs),
+            creators: vec![creator],
+        },
+        packages,
+        relationships,
+    };
+    serde_json::to_string_pretty(&document).context("serialize SPDX SBOM")
+}
+
+/// SPDX identifiers only allow letters, digits, `.` and `-`. A name that had to be rewritten
+/// gets a short digest of the original, so `foo_bar` and `foo-bar` keep distinct IDs.
+pub(super) fn spdx_package_id(name: &str) -> String {
+    let sanitized = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    if sanitized == name {
+        return format!("SPDXRef-Package-{sanitized}");
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    let suffix = hasher.finish().as_ref()[..4]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("SPDXRef-Package-{sanitized}-{suffix}")
+}
+
+/// Whether `license` reads as one SPDX license identifier such as `MIT` or `GPL-2.0+`, rather
+/// than a free-form license name.
+pub(super) fn is_spdx_license_id(license: &str) -> bool {
+    let id = license.strip_suffix('+').unwrap_or(license);
+    !id.is_empty()
+        && !id.starts_with("LicenseRef-")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+fn purl_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'-' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+    out
+}
+
+fn format_sbom_timestamp(unix_secs: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(unix_secs)).to_string()
+}
+
+/// Random-looking version 4 UUID that tells SBOMs of the same scope apart.
+fn document_uuid(entries: &[SbomEntry]) -> String {
+    let mut hasher = Sha256::new();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    hasher.update(format!("{nanos}:{}", std::process::id()).as_bytes());
+    for entry in entries {
+        hasher.update(format!("{}@{};", entry.name, entry.version).as_bytes());
+    }
+    let digest = hasher.finish();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest.as_ref()[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
@@ -1,5 +1,13 @@
-use super::policy::{GithubReleaseTrack, GithubReleaseVerification, ToolLayout};
+use super::policy::{
+    ExpectedAssetName, ReleaseBackend, ReleaseTrack, ReleaseVerification, ToolLayout,
+};
+use super::target::ToolTarget;
+use super::verify::{
+    MINISIGN_SIGNATURE_SUFFIX, MinisignPublicKey, find_checksum_asset_name, parse_checksum_file,
+    verify_minisign,
+};
 use super::*;
+use bzip2::read::BzDecoder;
 use flate2::read::GzDecoder;
 use semver::Version;
 use serde::de::DeserializeOwned;
@@ -13,6 +21,7 @@ use std::{
 use tar::Archive;
 use xz2::read::XzDecoder;
 use zip::ZipArchive;
+use zstd::stream::read::Decoder as ZstdDecoder;
 
 const TEMP_DIR_PREFIX_DOWNLOAD: &str = "za-tool-download";
 const TEMP_DIR_PREFIXES: [&str; 1] = [TEMP_DIR_PREFIX_DOWNLOAD];
@@ -103,15 +112,26 @@ struct ParallelDownloadPlan {
     parts: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct DownloadProbe {
     total_bytes: u64,
     range_supported: bool,
+    /// Strong ETag of the asset; required before staged parts are kept for a later resume.
+    etag: Option<String>,
+}
+
+/// One byte range of a parallel download and the part file that receives it.
+#[derive(Debug, Clone)]
+struct DownloadPart {
+    range: DownloadRange,
+    path: PathBuf,
+    /// Bytes already staged by an interrupted attempt.
+    staged: u64,
 }
 
 struct SingleStreamDownload<'a> {
     client: &'a Client,
-    path_and_query: &'a str,
+    url_parts: &'a UrlParts,
     url: &'a str,
     asset_path: &'a Path,
     total_bytes: Option<u64>,
@@ -151,16 +171,24 @@ pub(super) fn unregister_temp_dir(path: &Path) {
 }
 
 pub(super) fn cleanup_stale_temp_dirs() -> usize {
+    find_stale_temp_dirs()
+        .into_iter()
+        .filter(|path| fs::remove_dir_all(path).is_ok())
+        .count()
+}
+
+/// Temp dirs left behind by za processes that are no longer running.
+pub(super) fn find_stale_temp_dirs() -> Vec<PathBuf> {
     let base = env::temp_dir();
     let Ok(entries) = fs::read_dir(&base) else {
-        return 0;
+        return Vec::new();
     };
 
     let active = ACTIVE_TEMP_DIRS
         .lock()
         .map(|dirs| dirs.clone())
         .unwrap_or_default();
-    let mut removed = 0usize;
+    let mut stale = Vec::new();
     for entry in entries {
         let Ok(entry) = entry else {
             continue;
@@ -186,11 +214,9 @@ pub(super) fn cleanup_stale_temp_dirs() -> usize {
         if pid == std::process::id() || process_is_alive(pid) {
             continue;
         }
-        if fs::remove_dir_all(&path).is_ok() {
-            removed = removed.saturating_add(1);
-        }
+        stale.push(path);
     }
-    removed
+    stale
 }
 
 fn emit_download_progress(
@@ -266,15 +292,273 @@ pub(super) fn resolve_requested_version(
     let Some(policy) = find_tool_policy(name) else {
         bail!("{}", unsupported_tool_message(name));
     };
-    let Some(release) = policy.github_release else {
+    let Some(release) = policy.release else {
         bail!("latest version resolution is not defined for `{name}`");
     };
     fetch_latest_version_from_github_release(policy, release, proxy_scope)
 }
 
+/// Resolves the newest stable release of `name` that satisfies `requirement`.
+pub(super) fn resolve_version_in_range(
+    name: &str,
+    requirement: &VersionReq,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<String> {
+    ensure_not_interrupted()?;
+
+    let Some(policy) = find_tool_policy(name) else {
+        bail!("{}", unsupported_tool_message(name));
+    };
+    let Some(release) = policy.release else {
+        bail!("version range resolution is not defined for `{name}`");
+    };
+    if release.track != ReleaseTrack::VersionedTags {
+        bail!(
+            "`{}` publishes rolling release assets and does not support version ranges",
+            policy.canonical_name
+        );
+    }
+    let releases = fetch_versioned_release_candidates(
+        release,
+        ReleaseCandidateFilter::StableInRange(requirement),
+        proxy_scope,
+    )?;
+    latest_stable_version_in_range(&releases, release.tag_prefix, requirement).with_context(|| {
+        format!(
+            "resolve latest release for `{}` matching `{requirement}`",
+            policy.canonical_name
+        )
+    })
+}
+
+/// Fetches the notes of every release after `installed` up to and including `target`, newest first.
+pub(super) fn fetch_release_notes_between(
+    name: &str,
+    installed: &str,
+    target: &str,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<Vec<ReleaseNotes>> {
+    ensure_not_interrupted()?;
+
+    let Some(policy) = find_tool_policy(name) else {
+        bail!("{}", unsupported_tool_message(name));
+    };
+    let Some(release) = policy.release else {
+        bail!("release notes are not available for `{name}`");
+    };
+    if release.track != ReleaseTrack::VersionedTags {
+        bail!(
+            "`{}` publishes rolling release assets without per-version release notes",
+            policy.canonical_name
+        );
+    }
+    let installed = parse_release_semver(installed)
+        .with_context(|| format!("parse installed `{name}` version {installed}"))?;
+    let target = parse_release_semver(target)
+        .with_context(|| format!("parse target `{name}` version {target}"))?;
+    let releases = fetch_versioned_release_candidates(
+        release,
+        ReleaseCandidateFilter::Through(&installed),
+        proxy_scope,
+    )?;
+    Ok(release_notes_between(
+        releases,
+        release.tag_prefix,
+        &installed,
+        &target,
+    ))
+}
+
+/// Pre-releases are only included when the target itself is one.
+fn release_notes_between(
+    releases: Vec<GithubRelease>,
+    tag_prefix: &str,
+    installed: &Version,
+    target: &Version,
+) -> Vec<ReleaseNotes> {
+    let include_prereleases = !target.pre.is_empty();
+    let mut notes = releases
+        .into_iter()
+        .filter(|release| !release.draft && (include_prereleases || !release.prerelease))
+        .filter_map(|release| {
+            let version = parse_release_version(&release.tag_name, tag_prefix).ok()?;
+            let parsed = parse_release_semver(&version).ok()?;
+            if parsed <= *installed || parsed > *target {
+                return None;
+            }
+            if !include_prereleases && !parsed.pre.is_empty() {
+                return None;
+            }
+            Some((
+                parsed,
+                ReleaseNotes {
+                    version,
+                    published_at: release.published_at,
+                    body: release.body.unwrap_or_default().trim().to_string(),
+                },
+            ))
+        })
+        .collect::<Vec<_>>();
+    notes.sort_by(|(left, _), (right, _)| right.cmp(left));
+    notes.dedup_by(|(left, _), (right, _)| left == right);
+    notes.into_iter().map(|(_, notes)| notes).collect()
+}
+
+/// A newer release held back by the minimum release age, and when it stops being held back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct ReleaseCooldownSkip {
+    pub(super) version: String,
+    pub(super) eligible_on: String,
+}
+
+/// Steps `candidate` back to the newest release that has been public for the configured minimum
+/// release age, never below `installed`. Rolling-asset tools have no per-version publish dates
+/// and are returned unchanged.
+pub(super) fn apply_min_release_age(
+    name: &str,
+    installed: Option<&str>,
+    candidate: &str,
+    requirement: Option<&VersionReq>,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<(String, Option<ReleaseCooldownSkip>)> {
+    let min_age_days = za_config::load_tool_min_release_age_days(&canonical_tool_name(name))?;
+    if min_age_days == 0 {
+        return Ok((candidate.to_string(), None));
+    }
+    let Some(policy) = find_tool_policy(name) else {
+        bail!("{}", unsupported_tool_message(name));
+    };
+    let Some(release) = policy.release else {
+        return Ok((candidate.to_string(), None));
+    };
+    if release.track != ReleaseTrack::VersionedTags {
+        return Ok((candidate.to_string(), None));
+    }
+    ensure_not_interrupted()?;
+
+    let min_age = Duration::from_secs(min_age_days.saturating_mul(24 * 60 * 60));
+    let cutoff = SystemTime::now().checked_sub(min_age).unwrap_or(UNIX_EPOCH);
+    let releases = fetch_versioned_release_candidates(
+        release,
+        ReleaseCandidateFilter::PublishedBefore(cutoff),
+        proxy_scope,
+    )?;
+    select_release_past_cooldown(
+        &releases,
+        release.tag_prefix,
+        candidate,
+        installed,
+        requirement,
+        cutoff,
+        min_age,
+    )
+    .with_context(|| {
+        format!(
+            "apply the {min_age_days} day minimum release age to `{}`",
+            policy.canonical_name
+        )
+    })
+}
+
+fn select_release_past_cooldown(
+    releases: &[GithubRelease],
+    tag_prefix: &str,
+    candidate: &str,
+    installed: Option<&str>,
+    requirement: Option<&VersionReq>,
+    cutoff: SystemTime,
+    min_age: Duration,
+) -> Result<(String, Option<ReleaseCooldownSkip>)> {
+    let candidate_semver = parse_release_semver(candidate)?;
+    let installed = installed.and_then(|installed| {
+        parse_release_semver(installed)
+            .ok()
+            .map(|parsed| (parsed, installed))
+    });
+    let in_scope = releases
+        .iter()
+        .filter_map(|release| {
+            let parsed = stable_release_semver(release, tag_prefix)?;
+            let version = parse_release_version(&release.tag_name, tag_prefix).ok()?;
+            (parsed <= candidate_semver && requirement.is_none_or(|req| req.matches(&parsed)))
+                .then_some((parsed, version, release))
+        })
+        .collect::<Vec<_>>();
+    let held_back = in_scope
+        .iter()
+        .filter(|(_, _, release)| !release_published_before(release, cutoff))
+        .max_by(|(left, ..), (right, ..)| left.cmp(right));
+    let eligible = in_scope
+        .iter()
+        .filter(|(_, _, release)| release_published_before(release, cutoff))
+        .max_by(|(left, ..), (right, ..)| left.cmp(right));
+
+    let chosen = match (eligible, installed.as_ref()) {
+        (Some((parsed, version, _)), Some((floor, installed))) => Some(if parsed < floor {
+            (*installed).to_string()
+        } else {
+            version.clone()
+        }),
+        (Some((_, version, _)), None) => Some(version.clone()),
+        (None, Some((_, installed))) => Some((*installed).to_string()),
+        (None, None) => None,
+    };
+    let chosen_semver = chosen
+        .as_deref()
+        .and_then(|version| parse_release_semver(version).ok());
+
+    let skipped = held_back
+        .filter(|(held, ..)| chosen_semver.as_ref().is_none_or(|chosen| held > chosen))
+        .map(|(_, version, release)| ReleaseCooldownSkip {
+            version: version.clone(),
+            eligible_on: release_published_at(release)
+                .map(|published| {
+                    humantime::format_rfc3339_seconds(published + min_age)
+                        .to_string()
+                        .chars()
+                        .take(10)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        });
+    match chosen {
+        Some(version) => Ok((version, skipped)),
+        None => match skipped {
+            Some(skip) => bail!(
+                "no release is old enough yet; {} becomes eligible on {}",
+                skip.version,
+                skip.eligible_on
+            ),
+            None => Ok((candidate.to_string(), None)),
+        },
+    }
+}
+
+fn release_published_at(release: &GithubRelease) -> Option<SystemTime> {
+    humantime::parse_rfc3339_weak(release.published_at.as_deref()?).ok()
+}
+
+/// Releases without a readable publish date are never held back.
+fn release_published_before(release: &GithubRelease, cutoff: SystemTime) -> bool {
+    release_published_at(release).is_none_or(|published| published <= cutoff)
+}
+
+pub(super) fn ensure_version_satisfies(
+    name: &str,
+    version: &str,
+    requirement: &VersionReq,
+) -> Result<()> {
+    let parsed = parse_release_semver(version)?;
+    if !requirement.matches(&parsed) {
+        bail!("`{name}` version {version} does not satisfy `{requirement}`");
+    }
+    Ok(())
+}
+
 pub(super) fn resolve_install_source(
     tool: &ToolRef,
     proxy_scope: za_config::ProxyScope,
+    target: ToolTarget,
     display: DownloadDisplay,
     progress_sink: Option<DownloadProgressSink>,
 ) -> Result<PullSource> {
@@ -287,7 +571,7 @@ pub(super) fn resolve_install_source(
     let progress_reporter =
         progress_sink.map(|sink| DownloadProgressReporter::new(tool.name.clone(), sink));
 
-    let Some(release) = policy.github_release else {
+    let Some(release) = policy.release else {
         bail!(
             "GitHub Release source is not configured for `{}`",
             tool.name
@@ -299,15 +583,23 @@ pub(super) fn resolve_install_source(
         policy.layout,
         release,
         proxy_scope,
+        target,
         display,
         progress_reporter.as_ref(),
     )
-    .with_context(|| format!("resolve GitHub Release source for `{}`", tool.name))
+    .with_context(|| {
+        format!(
+            "resolve {} Release source for `{}`",
+            release.backend.label(),
+            tool.name
+        )
+    })
 }
 
 pub(super) fn preview_install_source(
     tool: &ToolRef,
     proxy_scope: za_config::ProxyScope,
+    target: ToolTarget,
 ) -> Result<InstallSource> {
     ensure_not_interrupted()?;
 
@@ -315,15 +607,63 @@ pub(super) fn preview_install_source(
         bail!("{}", unsupported_tool_message(&tool.name));
     };
 
-    let Some(release) = policy.github_release else {
+    let Some(release) = policy.release else {
         bail!(
             "GitHub Release source is not configured for `{}`",
             tool.name
         );
     };
 
-    preview_github_release_source(tool, release, proxy_scope)
-        .with_context(|| format!("preview GitHub Release source for `{}`", tool.name))
+    preview_github_release_source(tool, release, proxy_scope, target).with_context(|| {
+        format!(
+            "preview {} Release source for `{}`",
+            release.backend.label(),
+            tool.name
+        )
+    })
+}
+
+/// Downloads the exact asset pinned by a lockfile, skipping release discovery entirely.
+pub(super) fn resolve_locked_install_source(
+    tool: &ToolRef,
+    locked: &ReleaseAsset,
+    proxy_scope: za_config::ProxyScope,
+    display: DownloadDisplay,
+    progress_sink: Option<DownloadProgressSink>,
+) -> Result<PullSource> {
+    ensure_not_interrupted()?;
+
+    let Some(policy) = find_tool_policy(&tool.name) else {
+        bail!("{}", unsupported_tool_message(&tool.name));
+    };
+    let progress_reporter =
+        progress_sink.map(|sink| DownloadProgressReporter::new(tool.name.clone(), sink));
+    let extraction = match policy.layout {
+        ToolLayout::Binary => DownloadExtractionMode::PrimaryEntry(tool),
+        ToolLayout::Package => DownloadExtractionMode::KeepArchive,
+    };
+    let authorization = match policy.release {
+        Some(release) => resolve_download_authorization(release.backend)?,
+        None => None,
+    };
+    download_from_url(
+        &locked.url,
+        &AssetVerification::sha256(&locked.sha256),
+        authorization.as_ref(),
+        proxy_scope,
+        extraction,
+        DownloadFeedback::new(display, progress_reporter.as_ref()),
+    )
+    .with_context(|| format!("download locked asset for `{}`", tool.image()))
+}
+
+pub(super) fn preview_locked_install_source(locked: &ReleaseAsset) -> InstallSource {
+    InstallSource {
+        kind: SOURCE_KIND_DOWNLOAD,
+        detail: format!("URL {} (sha256={}; locked)", locked.url, locked.sha256),
+        unverified: false,
+        asset: Some(locked.clone()),
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -334,9 +674,22 @@ struct GithubRelease {
     #[serde(default)]
     draft: bool,
     #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+    #[serde(default)]
     assets: Vec<GithubReleaseAsset>,
 }
 
+/// Release notes of one version between the installed and the target release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct ReleaseNotes {
+    pub(super) version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) published_at: Option<String>,
+    pub(super) body: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct GithubReleaseAsset {
     name: String,
@@ -348,7 +701,7 @@ struct GithubReleaseAsset {
 
 pub(super) fn fetch_latest_version_from_github_release(
     tool_policy: ToolPolicy,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<String> {
     fetch_exact_latest_version_from_github_release(tool_policy, release_policy, proxy_scope)
@@ -356,27 +709,20 @@ pub(super) fn fetch_latest_version_from_github_release(
 
 pub(super) fn fetch_fast_latest_version_from_github_release(
     tool_policy: ToolPolicy,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<String> {
     match release_policy.track {
-        GithubReleaseTrack::VersionedTags => {
+        ReleaseTrack::VersionedTags => {
             fetch_latest_stable_version_from_github_latest(tool_policy, release_policy, proxy_scope)
         }
-        GithubReleaseTrack::RollingTagAssets {
+        ReleaseTrack::RollingTagAssets {
             tag,
             asset_prefix,
             asset_suffix,
             version_prefix,
         } => {
-            let release = fetch_github_release(
-                release_policy.project_label,
-                &format!(
-                    "/repos/{}/{}/releases/tags/{tag}",
-                    release_policy.owner, release_policy.repo
-                ),
-                proxy_scope,
-            )?;
+            let release = fetch_release_by_tag(release_policy, tag, proxy_scope)?;
             latest_rolling_asset_version(
                 &release,
                 tool_policy,
@@ -390,29 +736,22 @@ pub(super) fn fetch_fast_latest_version_from_github_release(
 
 fn fetch_exact_latest_version_from_github_release(
     tool_policy: ToolPolicy,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<String> {
     match release_policy.track {
-        GithubReleaseTrack::VersionedTags => fetch_latest_stable_version_from_github_release(
+        ReleaseTrack::VersionedTags => fetch_latest_stable_version_from_github_release(
             tool_policy,
             release_policy,
             proxy_scope,
         ),
-        GithubReleaseTrack::RollingTagAssets {
+        ReleaseTrack::RollingTagAssets {
             tag,
             asset_prefix,
             asset_suffix,
             version_prefix,
         } => {
-            let release = fetch_github_release(
-                release_policy.project_label,
-                &format!(
-                    "/repos/{}/{}/releases/tags/{tag}",
-                    release_policy.owner, release_policy.repo
-                ),
-                proxy_scope,
-            )?;
+            let release = fetch_release_by_tag(release_policy, tag, proxy_scope)?;
             latest_rolling_asset_version(
                 &release,
                 tool_policy,
@@ -426,10 +765,14 @@ fn fetch_exact_latest_version_from_github_release(
 
 fn fetch_latest_stable_version_from_github_release(
     tool_policy: ToolPolicy,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<String> {
-    let releases = fetch_versioned_release_candidates(release_policy, None, proxy_scope)?;
+    let releases = fetch_versioned_release_candidates(
+        release_policy,
+        ReleaseCandidateFilter::Stable,
+        proxy_scope,
+    )?;
     latest_stable_version_for_tags(&releases, release_policy.tag_prefix).with_context(|| {
         format!(
             "resolve latest stable release for `{}`",
@@ -440,17 +783,10 @@ fn fetch_latest_stable_version_from_github_release(
 
 fn fetch_latest_stable_version_from_github_latest(
     tool_policy: ToolPolicy,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<String> {
-    let release = fetch_github_release(
-        release_policy.project_label,
-        &format!(
-            "/repos/{}/{}/releases/latest",
-            release_policy.owner, release_policy.repo
-        ),
-        proxy_scope,
-    )?;
+    let release = fetch_latest_release(release_policy, proxy_scope)?;
     latest_stable_version_for_latest_release(&release, release_policy.tag_prefix).with_context(
         || {
             format!(
@@ -466,8 +802,8 @@ pub(super) fn fetch_latest_codex_alpha_version(
 ) -> Result<String> {
     let policy = find_tool_policy("codex").context("codex source policy is not registered")?;
     let release_policy = policy
-        .github_release
-        .context("codex source policy has no GitHub release policy")?;
+        .release
+        .context("codex source policy has no release policy")?;
     fetch_latest_prerelease_version_from_github_release(
         policy,
         release_policy,
@@ -478,18 +814,22 @@ pub(super) fn fetch_latest_codex_alpha_version(
 
 fn fetch_latest_prerelease_version_from_github_release(
     tool_policy: ToolPolicy,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     channel: &str,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<String> {
-    if release_policy.track != GithubReleaseTrack::VersionedTags {
+    if release_policy.track != ReleaseTrack::VersionedTags {
         bail!(
             "`{}` does not support semver pre-release channels",
             tool_policy.canonical_name
         );
     }
 
-    let releases = fetch_versioned_release_candidates(release_policy, Some(channel), proxy_scope)?;
+    let releases = fetch_versioned_release_candidates(
+        release_policy,
+        ReleaseCandidateFilter::Prerelease(channel),
+        proxy_scope,
+    )?;
     latest_prerelease_version_for_channel(&releases, release_policy.tag_prefix, channel)
         .with_context(|| {
             format!(
@@ -499,25 +839,47 @@ fn fetch_latest_prerelease_version_from_github_release(
         })
 }
 
+/// Which releases end the paginated scan once a page contains one of them.
+#[derive(Debug, Clone, Copy)]
+enum ReleaseCandidateFilter<'a> {
+    Stable,
+    Prerelease(&'a str),
+    StableInRange(&'a VersionReq),
+    /// Scans back until a page reaches the given (installed) version.
+    Through(&'a Version),
+    /// Scans back until a page has a stable release published at or before the cutoff.
+    PublishedBefore(SystemTime),
+}
+
 fn fetch_versioned_release_candidates(
-    release_policy: GithubReleasePolicy,
-    prerelease_channel: Option<&str>,
+    release_policy: ReleasePolicy,
+    filter: ReleaseCandidateFilter<'_>,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<Vec<GithubRelease>> {
     let mut releases = Vec::new();
     for page in 1..=GITHUB_RELEASE_SCAN_MAX_PAGES {
-        let path = format!(
-            "/repos/{}/{}/releases?per_page={GITHUB_RELEASE_SCAN_PER_PAGE}&page={page}",
-            release_policy.owner, release_policy.repo,
-        );
-        let mut page_releases =
-            fetch_github_releases(release_policy.project_label, &path, proxy_scope)?;
+        let mut page_releases = fetch_release_page(release_policy, page, proxy_scope)?;
         let is_last_page = page_releases.len() < GITHUB_RELEASE_SCAN_PER_PAGE;
-        let page_has_candidate = page_releases.iter().any(|release| {
-            if let Some(channel) = prerelease_channel {
+        let page_has_candidate = page_releases.iter().any(|release| match filter {
+            ReleaseCandidateFilter::Stable => {
+                release_matches_stable_versioned_tag(release, release_policy.tag_prefix)
+            }
+            ReleaseCandidateFilter::Prerelease(channel) => {
                 release_matches_prerelease_channel(release, release_policy.tag_prefix, channel)
-            } else {
+            }
+            ReleaseCandidateFilter::StableInRange(requirement) => {
+                stable_release_semver(release, release_policy.tag_prefix)
+                    .is_some_and(|parsed| requirement.matches(&parsed))
+            }
+            ReleaseCandidateFilter::PublishedBefore(cutoff) => {
                 release_matches_stable_versioned_tag(release, release_policy.tag_prefix)
+                    && release_published_before(release, cutoff)
+            }
+            ReleaseCandidateFilter::Through(oldest) => {
+                parse_release_version(&release.tag_name, release_policy.tag_prefix)
+                    .ok()
+                    .and_then(|version| parse_release_semver(&version).ok())
+                    .is_some_and(|parsed| parsed <= *oldest)
             }
         });
         releases.append(&mut page_releases);
@@ -543,6 +905,23 @@ fn latest_stable_version_for_tags(releases: &[GithubRelease], tag_prefix: &str)
         .ok_or_else(|| anyhow!("no stable release was found"))
 }
 
+fn latest_stable_version_in_range(
+    releases: &[GithubRelease],
+    tag_prefix: &str,
+    requirement: &VersionReq,
+) -> Result<String> {
+    releases
+        .iter()
+        .filter_map(|release| {
+            let parsed = stable_release_semver(release, tag_prefix)?;
+            let version = parse_release_version(&release.tag_name, tag_prefix).ok()?;
+            requirement.matches(&parsed).then_some((parsed, version))
+        })
+        .max_by(|(left, _), (right, _)| left.cmp(right))
+        .map(|(_, version)| version)
+        .ok_or_else(|| anyhow!("no stable release matches `{requirement}`"))
+}
+
 fn latest_stable_version_for_latest_release(
     release: &GithubRelease,
     tag_prefix: &str,
@@ -609,16 +988,16 @@ fn release_matches_prerelease_channel(
 }
 
 fn release_matches_stable_versioned_tag(release: &GithubRelease, tag_prefix: &str) -> bool {
+    stable_release_semver(release, tag_prefix).is_some()
+}
+
+fn stable_release_semver(release: &GithubRelease, tag_prefix: &str) -> Option<Version> {
     if release.prerelease || release.draft {
-        return false;
+        return None;
     }
-    let Ok(version) = parse_release_version(&release.tag_name, tag_prefix) else {
-        return false;
-    };
-    let Ok(parsed) = parse_release_semver(&version) else {
-        return false;
-    };
-    parsed.pre.is_empty()
+    let version = parse_release_version(&release.tag_name, tag_prefix).ok()?;
+    let parsed = parse_release_semver(&version).ok()?;
+    parsed.pre.is_empty().then_some(parsed)
 }
 
 fn prerelease_channel_matches(version: &Version, channel: &str) -> bool {
@@ -633,21 +1012,25 @@ fn prerelease_channel_matches(version: &Version, channel: &str) -> bool {
 fn download_from_github_release(
     tool: &ToolRef,
     layout: ToolLayout,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
+    target: ToolTarget,
     display: DownloadDisplay,
     progress_reporter: Option<&DownloadProgressReporter>,
 ) -> Result<PullSource> {
-    let (asset, expected_sha256) = resolve_github_release_asset(tool, release_policy, proxy_scope)?;
+    let (asset, verification) =
+        resolve_github_release_asset(tool, release_policy, proxy_scope, target)?;
 
     let extraction = match layout {
         ToolLayout::Binary => DownloadExtractionMode::PrimaryEntry(tool),
         ToolLayout::Package => DownloadExtractionMode::KeepArchive,
     };
     let feedback = DownloadFeedback::new(display, progress_reporter);
+    let authorization = resolve_download_authorization(release_policy.backend)?;
     download_from_url(
         &asset.browser_download_url,
-        expected_sha256.as_deref(),
+        &verification,
+        authorization.as_ref(),
         proxy_scope,
         extraction,
         feedback,
@@ -656,73 +1039,94 @@ fn download_from_github_release(
 
 fn preview_github_release_source(
     tool: &ToolRef,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
+    target: ToolTarget,
 ) -> Result<InstallSource> {
-    let (asset, expected_sha256) = resolve_github_release_asset(tool, release_policy, proxy_scope)?;
+    let (asset, verification) =
+        resolve_github_release_asset(tool, release_policy, proxy_scope, target)?;
     Ok(InstallSource {
         kind: SOURCE_KIND_DOWNLOAD,
-        detail: match expected_sha256 {
-            Some(expected) => format!("URL {} (sha256={expected})", asset.browser_download_url),
-            None => format!("URL {}", asset.browser_download_url),
-        },
+        detail: verification.describe(&asset.browser_download_url),
+        unverified: verification.is_unverified(),
+        asset: verification.expected_sha256.map(|sha256| ReleaseAsset {
+            url: asset.browser_download_url,
+            sha256,
+        }),
     })
 }
 
 fn resolve_github_release_asset(
     tool: &ToolRef,
-    release_policy: GithubReleasePolicy,
+    release_policy: ReleasePolicy,
     proxy_scope: za_config::ProxyScope,
-) -> Result<(GithubReleaseAsset, Option<String>)> {
+    target: ToolTarget,
+) -> Result<(GithubReleaseAsset, AssetVerification)> {
     let version = normalize_version(&tool.version);
-    let asset = match release_policy.track {
-        GithubReleaseTrack::VersionedTags => {
-            let expected_asset_name = (release_policy.expected_asset_name.ok_or_else(|| {
+    let (asset, release_assets) = match release_policy.track {
+        ReleaseTrack::VersionedTags => {
+            let expected = release_policy.expected_asset_name.ok_or_else(|| {
                 anyhow!(
                     "release policy for `{}` has no expected asset resolver",
                     tool.name
                 )
-            })?)(&version)?;
+            })?;
             let tag = format!("{}{}", release_policy.tag_prefix, version);
-            let path = format!(
-                "/repos/{}/{}/releases/tags/{tag}",
-                release_policy.owner, release_policy.repo
-            );
-            let release = fetch_github_release(release_policy.project_label, &path, proxy_scope)?;
-            release
+            let (release, expected_asset_name) = match expected {
+                ExpectedAssetName::HostMatch => {
+                    let release = fetch_release_by_tag(release_policy, &tag, proxy_scope)?;
+                    let asset_name = pick_release_asset_for_target(
+                        release.assets.iter().map(|asset| asset.name.as_str()),
+                        target,
+                    )
+                    .with_context(|| format!("pick a release asset from `{tag}`"))?
+                    .to_string();
+                    (release, asset_name)
+                }
+                expected => {
+                    let asset_name = expected.resolve(&version, target)?;
+                    target.ensure_asset_compatible(release_policy.project_label, &asset_name)?;
+                    (
+                        fetch_release_by_tag(release_policy, &tag, proxy_scope)?,
+                        asset_name,
+                    )
+                }
+            };
+            let asset = release
                 .assets
-                .into_iter()
+                .iter()
                 .find(|asset| asset.name == expected_asset_name)
+                .cloned()
                 .ok_or_else(|| {
                     anyhow!(
                         "release `{tag}` does not contain expected asset `{expected_asset_name}`"
                     )
-                })?
+                })?;
+            (asset, release.assets)
         }
-        GithubReleaseTrack::RollingTagAssets {
+        ReleaseTrack::RollingTagAssets {
             tag,
             asset_prefix,
             asset_suffix,
             version_prefix,
         } => {
-            let path = format!(
-                "/repos/{}/{}/releases/tags/{tag}",
-                release_policy.owner, release_policy.repo
-            );
-            let release = fetch_github_release(release_policy.project_label, &path, proxy_scope)?;
-            find_rolling_asset_for_version(
+            let release = fetch_release_by_tag(release_policy, tag, proxy_scope)?;
+            let asset = find_rolling_asset_for_version(
                 &release,
                 &version,
                 asset_prefix,
                 asset_suffix,
                 version_prefix,
             )?
-            .clone()
+            .clone();
+            (asset, release.assets)
         }
     };
 
-    let expected_sha256 = match release_policy.verification {
-        GithubReleaseVerification::RequiredSha256Digest => Some(
+    let authorization = resolve_download_authorization(release_policy.backend)?;
+    let authorization = authorization.as_ref();
+    let verification = match release_policy.verification {
+        ReleaseVerification::RequiredSha256Digest => AssetVerification::sha256(
             asset
                 .digest
                 .as_deref()
@@ -731,10 +1135,269 @@ fn resolve_github_release_asset(
                     anyhow!("release asset `{}` missing valid sha256 digest", asset.name)
                 })?,
         ),
-        GithubReleaseVerification::NoSha256Digest => None,
+        ReleaseVerification::ChecksumFile => {
+            resolve_checksum_file_verification(&asset, &release_assets, authorization, proxy_scope)?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "release does not publish a checksum file for `{}`",
+                        asset.name
+                    )
+                })?
+        }
+        ReleaseVerification::Minisign { public_key } => resolve_minisign_verification(
+            &asset,
+            &release_assets,
+            public_key,
+            authorization,
+            proxy_scope,
+        )?,
+        // Best effort: use a published checksum file when the release happens to ship one.
+        ReleaseVerification::NoSha256Digest => {
+            resolve_checksum_file_verification(&asset, &release_assets, authorization, proxy_scope)?
+                .unwrap_or_default()
+        }
+        ReleaseVerification::AvailableDigest { required } => {
+            let verification = match asset.digest.as_deref().and_then(parse_github_sha256_digest) {
+                Some(digest) => Some(AssetVerification::sha256(digest)),
+                None => resolve_checksum_file_verification(
+                    &asset,
+                    &release_assets,
+                    authorization,
+                    proxy_scope,
+                )?,
+            };
+            match verification {
+                Some(verification) => verification,
+                None if required => bail!(
+                    "release asset `{}` has no sha256 digest or checksum file, and `{}` requires one",
+                    asset.name,
+                    tool.name
+                ),
+                None => {
+                    eprintln!(
+                        "⚠️  {} {}: release asset `{}` has no sha256 digest or checksum file; it will be installed unverified (`--require-digest` refuses such assets)",
+                        tool.name, version, asset.name
+                    );
+                    AssetVerification::default()
+                }
+            }
+        }
     };
 
-    Ok((asset, expected_sha256))
+    Ok((asset, verification))
+}
+
+/// Picks the release asset built for `target`, for tools without a declared asset name.
+///
+/// Only archives za can unpack and bare binaries are considered; among those the best
+/// OS/arch/libc match wins, and archives beat bare binaries so bundled files come along.
+/// Equally good matches are settled by [`release_asset_preference`].
+pub(super) fn pick_release_asset_for_target<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    target: ToolTarget,
+) -> Result<&'a str> {
+    let mut best_score = 0;
+    let mut best = Vec::new();
+    for name in names {
+        let Some(score) = release_asset_score(name, target) else {
+            continue;
+        };
+        match score.cmp(&best_score) {
+            std::cmp::Ordering::Greater => {
+                best_score = score;
+                best = vec![name];
+            }
+            std::cmp::Ordering::Equal => best.push(name),
+            std::cmp::Ordering::Less => {}
+        }
+    }
+    best.into_iter()
+        .min_by_key(|name| release_asset_preference(name))
+        .ok_or_else(|| anyhow!("no release asset matches {target}"))
+}
+
+/// Tie-break among equally good matches (libc is already part of the score): tarballs before
+/// zips before bare files, then the shortest and alphabetically first name.
+fn release_asset_preference(name: &str) -> (u8, usize, &str) {
+    let format = match detect_archive_kind(name) {
+        Some(ArchiveKind::TarGz) => 0,
+        Some(ArchiveKind::TarXz) => 1,
+        Some(ArchiveKind::TarZst) => 2,
+        Some(ArchiveKind::TarBz2) => 3,
+        Some(ArchiveKind::Zip) => 4,
+        Some(ArchiveKind::Gz) => 5,
+        None => 6,
+    };
+    (format, name.len(), name)
+}
+
+fn release_asset_score(name: &str, target: ToolTarget) -> Option<u32> {
+    let lower = name.to_ascii_lowercase();
+    let archive = detect_archive_kind(&lower).is_some();
+    // `tool-linux-amd64` is a bare binary; `.deb`, `.sha256` or `.sig` files are not.
+    if !archive
+        && lower
+            .rsplit(['-', '_'])
+            .next()
+            .is_some_and(|last| last.contains('.'))
+    {
+        return None;
+    }
+    if lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|part| matches!(part, "debug" | "dbg" | "symbols" | "sbom"))
+    {
+        return None;
+    }
+    let score = target.asset_name_score(name)?;
+    Some(score * 2 + u32::from(archive))
+}
+
+/// Checks a download must pass, resolved before the transfer starts.
+#[derive(Debug, Clone, Default)]
+pub(super) struct AssetVerification {
+    expected_sha256: Option<String>,
+    /// Release asset the expected digest was read from; `None` for API or lockfile digests.
+    checksum_source: Option<String>,
+    minisign: Option<MinisignCheck>,
+}
+
+#[derive(Debug, Clone)]
+struct MinisignCheck {
+    public_key: MinisignPublicKey,
+    signature_name: String,
+    signature: String,
+}
+
+impl AssetVerification {
+    pub(super) fn sha256(expected: impl Into<String>) -> Self {
+        Self {
+            expected_sha256: Some(expected.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Neither a digest nor a signature checks the download.
+    pub(super) fn is_unverified(&self) -> bool {
+        self.expected_sha256.is_none() && self.minisign.is_none()
+    }
+
+    /// Source detail recorded in the manifest, e.g. `URL <url> (sha256=<hex> from SHA256SUMS)`.
+    pub(super) fn describe(&self, url: &str) -> String {
+        let mut checks = Vec::new();
+        if let Some(expected) = &self.expected_sha256 {
+            checks.push(match &self.checksum_source {
+                Some(source) => format!("sha256={expected} from {source}"),
+                None => format!("sha256={expected}"),
+            });
+        }
+        if let Some(minisign) = &self.minisign {
+            checks.push(format!(
+                "minisign {} key {}",
+                minisign.signature_name,
+                minisign.public_key.key_id_hex()
+            ));
+        }
+        if checks.is_empty() {
+            format!("URL {url} (unverified)")
+        } else {
+            format!("URL {url} ({})", checks.join("; "))
+        }
+    }
+}
+
+fn resolve_checksum_file_verification(
+    asset: &GithubReleaseAsset,
+    release_assets: &[GithubReleaseAsset],
+    authorization: Option<&DownloadAuthorization>,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<Option<AssetVerification>> {
+    let Some(checksum_name) = find_checksum_asset_name(
+        &asset.name,
+        release_assets
+            .iter()
+            .map(|candidate| candidate.name.as_str()),
+    ) else {
+        return Ok(None);
+    };
+    let checksum_asset = release_assets
+        .iter()
+        .find(|candidate| candidate.name == checksum_name)
+        .ok_or_else(|| anyhow!("checksum asset `{checksum_name}` is missing from the release"))?;
+    let content = fetch_release_text_asset(
+        &checksum_asset.browser_download_url,
+        authorization,
+        proxy_scope,
+    )?;
+    let expected = parse_checksum_file(&content, &asset.name)
+        .with_context(|| format!("read `{checksum_name}`"))?;
+    Ok(Some(AssetVerification {
+        expected_sha256: Some(expected),
+        checksum_source: Some(checksum_name.to_string()),
+        minisign: None,
+    }))
+}
+
+fn resolve_minisign_verification(
+    asset: &GithubReleaseAsset,
+    release_assets: &[GithubReleaseAsset],
+    public_key: &str,
+    authorization: Option<&DownloadAuthorization>,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<AssetVerification> {
+    let public_key = MinisignPublicKey::parse(public_key)?;
+    let signature_name = format!("{}{MINISIGN_SIGNATURE_SUFFIX}", asset.name);
+    let signature_asset = release_assets
+        .iter()
+        .find(|candidate| candidate.name == signature_name)
+        .ok_or_else(|| anyhow!("release does not publish signature `{signature_name}`"))?;
+    let signature = fetch_release_text_asset(
+        &signature_asset.browser_download_url,
+        authorization,
+        proxy_scope,
+    )?;
+    Ok(AssetVerification {
+        minisign: Some(MinisignCheck {
+            public_key,
+            signature_name,
+            signature,
+        }),
+        ..AssetVerification::default()
+    })
+}
+
+/// Fetches a small text asset such as a checksum file or detached signature.
+fn fetch_release_text_asset(
+    url: &str,
+    authorization: Option<&DownloadAuthorization>,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<String> {
+    ensure_not_interrupted()?;
+    let url = za_config::github_download_url(url)?;
+    let url_parts = parse_url_parts(&url)?.with_authorization(authorization);
+    let client = build_http_client(&url_parts.base_url, "za-tool-manager", true, proxy_scope)
+        .context("build HTTP client")?;
+    let interactive = io::stderr().is_terminal();
+    retry_transient_http_operation(interactive, "verify", &url_parts.file_name, || {
+        let mut req = client.get(&url_parts.path_and_query);
+        if let Some(authorization) = url_parts.authorization.as_deref() {
+            req = req
+                .try_header("authorization", authorization)
+                .context("set download authorization header")?;
+        }
+        let response = req
+            .send_response()
+            .with_context(|| format!("download `{url}` ({PROXY_HINT})"))?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!(
+                "download `{url}` failed: status {} body {}",
+                status,
+                truncate_for_log(&response.text_lossy(), 200)
+            );
+        }
+        Ok(response.text_lossy())
+    })
 }
 
 fn latest_rolling_asset_version(
@@ -1052,32 +1715,252 @@ fn build_http_client(
         .with_context(|| format!("build HTTP client for `{base_url}`"))
 }
 
-fn fetch_github_release(
-    project_label: &str,
-    path: &str,
+/// GitLab's release payload; converted into the GitHub shape the rest of this module reads.
+#[derive(Debug, Deserialize)]
+struct GitlabRelease {
+    tag_name: String,
+    #[serde(default)]
+    upcoming_release: bool,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    released_at: Option<String>,
+    #[serde(default)]
+    assets: GitlabReleaseAssets,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GitlabReleaseAssets {
+    #[serde(default)]
+    links: Vec<GitlabReleaseLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabReleaseLink {
+    name: String,
+    url: String,
+    #[serde(default)]
+    direct_asset_url: Option<String>,
+}
+
+impl GitlabRelease {
+    /// GitLab has no pre-release flag, so the tag's semver pre-release part stands in for it.
+    fn into_github_release(self, tag_prefix: &str) -> GithubRelease {
+        let prerelease = parse_release_version(&self.tag_name, tag_prefix)
+            .ok()
+            .and_then(|version| parse_release_semver(&version).ok())
+            .is_some_and(|parsed| !parsed.pre.is_empty());
+        GithubRelease {
+            tag_name: self.tag_name,
+            prerelease,
+            draft: self.upcoming_release,
+            body: self.description,
+            published_at: self.released_at,
+            assets: self
+                .assets
+                .links
+                .into_iter()
+                .map(|link| GithubReleaseAsset {
+                    name: link.name,
+                    browser_download_url: link.direct_asset_url.unwrap_or(link.url),
+                    digest: None,
+                    updated_at: String::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn fetch_release_by_tag(
+    release_policy: ReleasePolicy,
+    tag: &str,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<GithubRelease> {
-    fetch_github_json(project_label, path, proxy_scope)
+    let (owner, repo) = (release_policy.owner, release_policy.repo);
+    match release_policy.backend {
+        ReleaseBackend::Github | ReleaseBackend::Gitea { .. } => fetch_release_json(
+            release_policy,
+            &format!("/repos/{owner}/{repo}/releases/tags/{tag}"),
+            proxy_scope,
+        ),
+        ReleaseBackend::Gitlab { .. } => fetch_release_json::<GitlabRelease>(
+            release_policy,
+            &format!(
+                "/projects/{}/releases/{}",
+                gitlab_project_id(release_policy),
+                tag.replace('/', "%2F")
+            ),
+            proxy_scope,
+        )
+        .map(|release| release.into_github_release(release_policy.tag_prefix)),
+    }
 }
 
-fn fetch_github_releases(
-    project_label: &str,
-    path: &str,
+/// Tag of the release the forge marks as latest.
+pub(super) fn fetch_latest_release_tag(
+    release_policy: ReleasePolicy,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<String> {
+    fetch_latest_release(release_policy, proxy_scope).map(|release| release.tag_name)
+}
+
+/// Web address of the repository a release policy publishes from.
+pub(super) fn release_repository_url(release_policy: ReleasePolicy) -> String {
+    let (owner, repo) = (release_policy.owner, release_policy.repo);
+    match release_policy.backend {
+        ReleaseBackend::Github => format!("https://github.com/{owner}/{repo}"),
+        ReleaseBackend::Gitlab { base_url } | ReleaseBackend::Gitea { base_url } => {
+            format!("{}/{owner}/{repo}", base_url.trim_end_matches('/'))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRepository {
+    #[serde(default)]
+    license: Option<GithubRepositoryLicense>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRepositoryLicense {
+    #[serde(default)]
+    spdx_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaRepository {
+    #[serde(default)]
+    licenses: Vec<String>,
+}
+
+/// SPDX identifier of the license the repository declares. GitLab only reports its own license
+/// keys, so it never yields one.
+pub(super) fn fetch_repository_license(
+    release_policy: ReleasePolicy,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<Option<String>> {
+    let path = format!("/repos/{}/{}", release_policy.owner, release_policy.repo);
+    let license = match release_policy.backend {
+        ReleaseBackend::Github => {
+            fetch_release_json::<GithubRepository>(release_policy, &path, proxy_scope)?
+                .license
+                .and_then(|license| license.spdx_id)
+        }
+        ReleaseBackend::Gitea { .. } => {
+            let mut licenses =
+                fetch_release_json::<GiteaRepository>(release_policy, &path, proxy_scope)?.licenses;
+            (licenses.len() == 1).then(|| licenses.remove(0))
+        }
+        ReleaseBackend::Gitlab { .. } => None,
+    };
+    // GitHub reports `NOASSERTION` for licenses it cannot classify.
+    Ok(license.filter(|id| !id.is_empty() && id != "NOASSERTION"))
+}
+
+fn fetch_latest_release(
+    release_policy: ReleasePolicy,
+    proxy_scope: za_config::ProxyScope,
+) -> Result<GithubRelease> {
+    let (owner, repo) = (release_policy.owner, release_policy.repo);
+    match release_policy.backend {
+        ReleaseBackend::Github | ReleaseBackend::Gitea { .. } => fetch_release_json(
+            release_policy,
+            &format!("/repos/{owner}/{repo}/releases/latest"),
+            proxy_scope,
+        ),
+        ReleaseBackend::Gitlab { .. } => fetch_release_json::<GitlabRelease>(
+            release_policy,
+            &format!(
+                "/projects/{}/releases/permalink/latest",
+                gitlab_project_id(release_policy)
+            ),
+            proxy_scope,
+        )
+        .map(|release| release.into_github_release(release_policy.tag_prefix)),
+    }
+}
+
+fn fetch_release_page(
+    release_policy: ReleasePolicy,
+    page: usize,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<Vec<GithubRelease>> {
-    fetch_github_json(project_label, path, proxy_scope)
+    let (owner, repo) = (release_policy.owner, release_policy.repo);
+    match release_policy.backend {
+        ReleaseBackend::Github => fetch_release_json(
+            release_policy,
+            &format!(
+                "/repos/{owner}/{repo}/releases?per_page={GITHUB_RELEASE_SCAN_PER_PAGE}&page={page}"
+            ),
+            proxy_scope,
+        ),
+        ReleaseBackend::Gitea { .. } => fetch_release_json(
+            release_policy,
+            &format!(
+                "/repos/{owner}/{repo}/releases?limit={GITHUB_RELEASE_SCAN_PER_PAGE}&page={page}"
+            ),
+            proxy_scope,
+        ),
+        ReleaseBackend::Gitlab { .. } => fetch_release_json::<Vec<GitlabRelease>>(
+            release_policy,
+            &format!(
+                "/projects/{}/releases?per_page={GITHUB_RELEASE_SCAN_PER_PAGE}&page={page}",
+                gitlab_project_id(release_policy)
+            ),
+            proxy_scope,
+        )
+        .map(|releases| {
+            releases
+                .into_iter()
+                .map(|release| release.into_github_release(release_policy.tag_prefix))
+                .collect()
+        }),
+    }
 }
 
-fn fetch_github_json<T: DeserializeOwned>(
-    project_label: &str,
+/// GitLab addresses projects by URL-encoded path, which keeps nested groups in `owner` working.
+fn gitlab_project_id(release_policy: ReleasePolicy) -> String {
+    format!("{}/{}", release_policy.owner, release_policy.repo).replace('/', "%2F")
+}
+
+/// Origin and API path prefix for a release backend, e.g. `("https://git.example.com", "/api/v1")`.
+fn release_api_base(backend: ReleaseBackend) -> Result<(String, String)> {
+    let (base_url, api_path) = match backend {
+        ReleaseBackend::Github => (za_config::load_github_api_base()?, ""),
+        ReleaseBackend::Gitlab { base_url } => (base_url.to_string(), "/api/v4"),
+        ReleaseBackend::Gitea { base_url } => (base_url.to_string(), "/api/v1"),
+    };
+    let (scheme, rest) = base_url
+        .split_once("://")
+        .ok_or_else(|| anyhow!("invalid release API base URL `{base_url}`"))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    Ok((
+        format!("{scheme}://{authority}"),
+        format!("{}{api_path}", path.trim_end_matches('/')),
+    ))
+}
+
+fn fetch_release_json<T: DeserializeOwned>(
+    release_policy: ReleasePolicy,
     path: &str,
     proxy_scope: za_config::ProxyScope,
 ) -> Result<T> {
     ensure_not_interrupted()?;
 
-    let client = build_http_client(GITHUB_API_BASE, "za-tool-manager", false, proxy_scope)
-        .context("build GitHub API client")?;
-    let github_token = resolve_github_token()?;
+    let backend = release_policy.backend;
+    let project_label = release_policy.project_label;
+    let (api_origin, api_prefix) = release_api_base(backend)?;
+    let client = build_http_client(&api_origin, "za-tool-manager", false, proxy_scope)
+        .with_context(|| format!("build {} API client", backend.label()))?;
+    let authorization = resolve_release_authorization(backend)?;
+    let accept = match backend {
+        ReleaseBackend::Github => "application/vnd.github+json",
+        ReleaseBackend::Gitlab { .. } | ReleaseBackend::Gitea { .. } => "application/json",
+    };
+    let path = format!("{api_prefix}{path}");
     let interactive = io::stderr().is_terminal();
 
     retry_transient_http_operation(
@@ -1085,17 +1968,17 @@ fn fetch_github_json<T: DeserializeOwned>(
         "source",
         &format!("{project_label} release metadata"),
         || {
-            let mut req = client.get(path);
+            let mut req = client.get(&path);
             req = req
                 .try_header("user-agent", HTTP_USER_AGENT)
-                .context("set GitHub user-agent")?;
+                .context("set release API user-agent")?;
             req = req
-                .try_header("accept", "application/vnd.github+json")
-                .context("set GitHub accept header")?;
-            if let Some(token) = github_token.as_deref() {
+                .try_header("accept", accept)
+                .context("set release API accept header")?;
+            if let Some((name, value)) = authorization.as_ref() {
                 req = req
-                    .try_header("authorization", &format!("Bearer {token}"))
-                    .context("set GitHub authorization header")?;
+                    .try_header(name, value)
+                    .with_context(|| format!("set {} authorization header", backend.label()))?;
             }
 
             let response = req.send_response().with_context(|| {
@@ -1116,6 +1999,69 @@ fn fetch_github_json<T: DeserializeOwned>(
     )
 }
 
+/// Header carrying the token for a release backend, if one is configured.
+fn resolve_release_authorization(
+    backend: ReleaseBackend,
+) -> Result<Option<(&'static str, String)>> {
+    Ok(match backend {
+        ReleaseBackend::Github => {
+            resolve_github_token()?.map(|token| ("authorization", format!("Bearer {token}")))
+        }
+        ReleaseBackend::Gitlab { .. } => {
+            first_env_token(&["GITLAB_TOKEN"]).map(|token| ("private-token", token))
+        }
+        ReleaseBackend::Gitea { .. } => first_env_token(&["GITEA_TOKEN", "FORGEJO_TOKEN"])
+            .map(|token| ("authorization", format!("token {token}"))),
+    })
+}
+
+/// Release backend token for asset, checksum and signature downloads on the backend's own host.
+///
+/// It always travels as `authorization`, which the HTTP client drops on cross-origin redirects,
+/// so object-storage redirects never see it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DownloadAuthorization {
+    origin: String,
+    value: String,
+}
+
+impl DownloadAuthorization {
+    /// Header value for a URL on `base_url`, or `None` when it lives on another host.
+    fn header_for(&self, base_url: &str) -> Option<&str> {
+        self.origin
+            .eq_ignore_ascii_case(base_url)
+            .then_some(self.value.as_str())
+    }
+}
+
+fn resolve_download_authorization(
+    backend: ReleaseBackend,
+) -> Result<Option<DownloadAuthorization>> {
+    let value = match backend {
+        ReleaseBackend::Github => resolve_github_token()?.map(|token| format!("Bearer {token}")),
+        ReleaseBackend::Gitlab { .. } => {
+            first_env_token(&["GITLAB_TOKEN"]).map(|token| format!("Bearer {token}"))
+        }
+        ReleaseBackend::Gitea { .. } => {
+            first_env_token(&["GITEA_TOKEN", "FORGEJO_TOKEN"]).map(|token| format!("token {token}"))
+        }
+    };
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let (origin, _) = release_api_base(backend)?;
+    Ok(Some(DownloadAuthorization { origin, value }))
+}
+
+fn first_env_token(names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        env::var(name)
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+    })
+}
+
 pub(super) fn parse_release_version(tag_name: &str, tag_prefix: &str) -> Result<String> {
     let version = tag_name
         .strip_prefix(tag_prefix)
@@ -1164,17 +2110,8 @@ fn is_two_segment_numeric_version(core: &str) -> bool {
 }
 
 fn resolve_github_token() -> Result<Option<String>> {
-    if let Ok(token) = env::var("GITHUB_TOKEN") {
-        let token = token.trim();
-        if !token.is_empty() {
-            return Ok(Some(token.to_string()));
-        }
-    }
-    if let Ok(token) = env::var("GH_TOKEN") {
-        let token = token.trim();
-        if !token.is_empty() {
-            return Ok(Some(token.to_string()));
-        }
+    if let Some(token) = first_env_token(&["GITHUB_TOKEN", "GH_TOKEN"]) {
+        return Ok(Some(token));
     }
     za_config::load_github_token()
 }
@@ -1194,112 +2131,86 @@ pub(super) fn parse_github_sha256_digest(digest: &str) -> Option<String> {
 
 fn download_from_url(
     url: &str,
-    expected_sha256: Option<&str>,
+    verification: &AssetVerification,
+    authorization: Option<&DownloadAuthorization>,
     proxy_scope: za_config::ProxyScope,
     extraction: DownloadExtractionMode<'_>,
     feedback: DownloadFeedback<'_>,
 ) -> Result<PullSource> {
     ensure_not_interrupted()?;
+    let url = za_config::github_download_url(url)?;
+    let url = url.as_str();
     let download_root = unique_temp_dir(TEMP_DIR_PREFIX_DOWNLOAD)?;
     let run = (|| -> Result<PullSource> {
-        let url_parts = parse_url_parts(url)?;
+        let url_parts = parse_url_parts(url)?.with_authorization(authorization);
         let asset_name = url_parts.file_name.clone();
         let asset_path = download_root.join(&asset_name);
         let interactive = feedback.interactive();
 
-        let client = build_http_client(&url_parts.base_url, "za-tool-manager", true, proxy_scope)
-            .context("build HTTP client")?;
-        if feedback.is_detailed() {
-            print_download_stage(
-                interactive,
-                "download",
-                format!("probing server capabilities for `{asset_name}`"),
-            );
-        }
-        let probe = probe_parallel_download_support(&client, &url_parts.path_and_query).unwrap_or(
-            DownloadProbe {
-                total_bytes: 0,
-                range_supported: false,
-            },
-        );
-        let total_bytes = (probe.total_bytes > 0).then_some(probe.total_bytes);
-
-        if let Some(plan) = build_parallel_download_plan(probe.total_bytes, probe.range_supported) {
-            if feedback.is_detailed() {
-                print_download_stage(
-                    interactive,
-                    "download",
-                    format!(
-                        "parallel transfer ({} parts, {})",
-                        plan.parts,
-                        format_bytes_u64(plan.total_bytes)
-                    ),
-                );
+        let cache_root = cache::tool_cache_dir().ok();
+        let cached = match (
+            cache_root.as_deref(),
+            verification.expected_sha256.as_deref(),
+        ) {
+            (Some(root), Some(expected)) => {
+                cache::restore_cached_artifact(root, expected, &asset_path).unwrap_or_default()
             }
-            match download_to_path_parallel(
-                &url_parts,
-                url,
-                &asset_path,
-                proxy_scope,
-                plan,
-                feedback,
-            ) {
-                Ok(()) => {}
-                Err(ParallelDownloadError::Unsupported) => {
-                    retry_single_stream_after_parallel_failure(
-                        interactive,
-                        format!(
-                            "range transfer unavailable for `{asset_name}`; falling back to single stream"
-                        ),
-                        SingleStreamDownload {
-                            client: &client,
-                            path_and_query: &url_parts.path_and_query,
-                            url,
-                            asset_path: &asset_path,
-                            total_bytes,
-                        },
-                        feedback,
-                    )?;
-                }
-                Err(ParallelDownloadError::Failed(err)) => {
-                    retry_single_stream_after_parallel_failure(
+            _ => None,
+        };
+        match &cached {
+            Some(entry) => {
+                if feedback.is_detailed() {
+                    print_download_stage(
                         interactive,
+                        "cache",
                         format!(
-                            "parallel transfer interrupted for `{asset_name}` after {}; falling back to single stream",
-                            summarize_retryable_error(&err)
+                            "reusing cached `{asset_name}` ({})",
+                            format_bytes_u64(entry.size_bytes)
                         ),
-                        SingleStreamDownload {
-                            client: &client,
-                            path_and_query: &url_parts.path_and_query,
-                            url,
-                            asset_path: &asset_path,
-                            total_bytes,
-                        },
-                        feedback,
-                    )?;
+                    );
                 }
+                emit_download_progress(
+                    feedback.reporter(),
+                    entry.size_bytes,
+                    Some(entry.size_bytes),
+                    Duration::ZERO,
+                    true,
+                );
             }
-        } else {
-            if feedback.is_detailed() {
-                print_download_stage(interactive, "download", "single-stream transfer");
-            }
-            download_to_path_single(
-                &client,
-                &url_parts.path_and_query,
-                url,
-                &asset_path,
-                total_bytes,
-                feedback,
-            )?;
+            None => transfer_asset(url, &url_parts, &asset_path, proxy_scope, feedback)?,
         }
         ensure_not_interrupted()?;
 
-        if let Some(expected_sha256) = expected_sha256 {
-            verify_sha256_file(&asset_path, expected_sha256)?;
+        let actual_sha256 = sha256_file(&asset_path)?;
+        if let Some(expected_sha256) = verification.expected_sha256.as_deref() {
+            ensure_sha256_matches(&asset_path, expected_sha256, &actual_sha256)?;
             if feedback.is_detailed() {
                 print_download_stage(interactive, "verify", "sha256 ok");
             }
         }
+        if let Some(minisign) = &verification.minisign {
+            verify_minisign(minisign.public_key, &minisign.signature, &asset_path)
+                .with_context(|| format!("verify `{}`", minisign.signature_name))?;
+            if feedback.is_detailed() {
+                print_download_stage(
+                    interactive,
+                    "verify",
+                    format!("minisign ok (key {})", minisign.public_key.key_id_hex()),
+                );
+            }
+        }
+        if cached.is_none()
+            && !verification.is_unverified()
+            && let Some(root) = cache_root.as_deref()
+            && let Err(err) = cache::store_artifact(root, &asset_path, url, &actual_sha256)
+            && feedback.is_detailed()
+        {
+            print_download_stage(
+                interactive,
+                "cache",
+                format!("skipped caching `{asset_name}`: {err:#}"),
+            );
+        }
 
         let (artifact, resolved_path) = match extraction {
             DownloadExtractionMode::KeepArchive => {
@@ -1325,12 +2236,14 @@ fn download_from_url(
             SOURCE_KIND_DOWNLOAD,
             artifact,
             resolved_path,
-            match expected_sha256 {
-                Some(expected) => format!("URL {url} (sha256={expected})"),
-                None => format!("URL {url}"),
-            },
+            verification.describe(url),
             download_root.clone(),
-        ))
+        )
+        .with_asset(ReleaseAsset {
+            url: url.to_string(),
+            sha256: actual_sha256,
+        })
+        .with_unverified(verification.is_unverified()))
     })();
 
     if run.is_err() {
@@ -1338,7 +2251,131 @@ fn download_from_url(
         unregister_temp_dir(&download_root);
         let _ = fs::remove_dir_all(&download_root);
     }
-    run
+    run
+}
+
+/// Fetches `url` into `asset_path`, preferring parallel range requests when the server allows them.
+fn transfer_asset(
+    url: &str,
+    url_parts: &UrlParts,
+    asset_path: &Path,
+    proxy_scope: za_config::ProxyScope,
+    feedback: DownloadFeedback<'_>,
+) -> Result<()> {
+    let asset_name = &url_parts.file_name;
+    let interactive = feedback.interactive();
+    let client = build_http_client(&url_parts.base_url, "za-tool-manager", true, proxy_scope)
+        .context("build HTTP client")?;
+    if feedback.is_detailed() {
+        print_download_stage(
+            interactive,
+            "download",
+            format!("probing server capabilities for `{asset_name}`"),
+        );
+    }
+    let probe = probe_parallel_download_support(&client, url_parts).unwrap_or(DownloadProbe {
+        total_bytes: 0,
+        range_supported: false,
+        etag: None,
+    });
+    let total_bytes = (probe.total_bytes > 0).then_some(probe.total_bytes);
+
+    if let Some(plan) = build_parallel_download_plan(probe.total_bytes, probe.range_supported) {
+        let mut staging = open_partial_download(url, probe.etag.as_deref(), plan);
+        if feedback.is_detailed() {
+            let mode = if staging.as_ref().is_some_and(|staging| staging.resumed()) {
+                "resuming parallel transfer"
+            } else {
+                "parallel transfer"
+            };
+            print_download_stage(
+                interactive,
+                "download",
+                format!(
+                    "{mode} ({} parts, {})",
+                    plan.parts,
+                    format_bytes_u64(plan.total_bytes)
+                ),
+            );
+        }
+        let result = match download_to_path_parallel(
+            url_parts,
+            url,
+            asset_path,
+            proxy_scope,
+            plan,
+            feedback,
+            staging.as_ref(),
+        ) {
+            Ok(()) => Ok(()),
+            Err(ParallelDownloadError::Unsupported) => {
+                if let Some(staging) = staging.take() {
+                    staging.discard();
+                }
+                retry_single_stream_after_parallel_failure(
+                    interactive,
+                    format!(
+                        "range transfer unavailable for `{asset_name}`; falling back to single stream"
+                    ),
+                    SingleStreamDownload {
+                        client: &client,
+                        url_parts,
+                        url,
+                        asset_path,
+                        total_bytes,
+                    },
+                    feedback,
+                )
+            }
+            // Staged parts stay on disk so the next attempt resumes instead of restarting.
+            Err(ParallelDownloadError::Failed(err)) if is_interrupt_requested() => Err(err),
+            Err(ParallelDownloadError::Failed(err)) => retry_single_stream_after_parallel_failure(
+                interactive,
+                format!(
+                    "parallel transfer interrupted for `{asset_name}` after {}; falling back to single stream",
+                    summarize_retryable_error(&err)
+                ),
+                SingleStreamDownload {
+                    client: &client,
+                    url_parts,
+                    url,
+                    asset_path,
+                    total_bytes,
+                },
+                feedback,
+            ),
+        };
+        if result.is_ok()
+            && let Some(staging) = staging
+        {
+            staging.discard();
+        }
+        result?;
+    } else {
+        if feedback.is_detailed() {
+            print_download_stage(interactive, "download", "single-stream transfer");
+        }
+        download_to_path_single(&client, url_parts, url, asset_path, total_bytes, feedback)?;
+    }
+    Ok(())
+}
+
+/// Resumable staging for a parallel download, or `None` when the server sent no strong ETag, the
+/// cache is unavailable or another process is already downloading the same URL.
+fn open_partial_download(
+    url: &str,
+    etag: Option<&str>,
+    plan: ParallelDownloadPlan,
+) -> Option<cache::PartialDownload> {
+    let etag = etag?;
+    let root = cache::tool_cache_dir().ok()?;
+    let meta = cache::PartialDownloadMeta {
+        url: url.to_string(),
+        etag: etag.to_string(),
+        total_bytes: plan.total_bytes,
+        parts: plan.parts,
+    };
+    cache::PartialDownload::open(&root, &meta).ok().flatten()
 }
 
 fn retry_single_stream_after_parallel_failure(
@@ -1358,7 +2395,7 @@ fn retry_single_stream_after_parallel_failure(
     }
     download_to_path_single(
         request.client,
-        request.path_and_query,
+        request.url_parts,
         request.url,
         request.asset_path,
         request.total_bytes,
@@ -1368,15 +2405,20 @@ fn retry_single_stream_after_parallel_failure(
 
 fn build_download_request<'a>(
     client: &'a Client,
-    path_and_query: &str,
+    url_parts: &UrlParts,
     range: Option<DownloadRange>,
 ) -> Result<reqx::blocking::RequestBuilder<'a>> {
     let mut req = client
-        .get(path_and_query.to_string())
+        .get(url_parts.path_and_query.clone())
         .auto_accept_encoding(false);
     req = req
         .try_header("user-agent", HTTP_USER_AGENT)
         .context("set download user-agent")?;
+    if let Some(authorization) = url_parts.authorization.as_deref() {
+        req = req
+            .try_header("authorization", authorization)
+            .context("set download authorization header")?;
+    }
     if let Some(range) = range {
         req = req
             .try_header("range", &format!("bytes={}-{}", range.start, range.end))
@@ -1385,15 +2427,16 @@ fn build_download_request<'a>(
     Ok(req)
 }
 
-fn probe_parallel_download_support(client: &Client, path_and_query: &str) -> Option<DownloadProbe> {
+fn probe_parallel_download_support(client: &Client, url_parts: &UrlParts) -> Option<DownloadProbe> {
     let range = DownloadRange { start: 0, end: 0 };
-    let req = build_download_request(client, path_and_query, Some(range)).ok()?;
+    let req = build_download_request(client, url_parts, Some(range)).ok()?;
     let resp = req.send_response_stream().ok()?;
     let status = resp.status();
     if status != 206 {
         return Some(DownloadProbe {
             total_bytes: 0,
             range_supported: false,
+            etag: None,
         });
     }
     let total_bytes = resp
@@ -1401,9 +2444,18 @@ fn probe_parallel_download_support(client: &Client, path_and_query: &str) -> Opt
         .get("content-range")
         .and_then(|value| value.to_str().ok())
         .and_then(parse_content_range_total)?;
+    // Weak validators cannot guard a byte-range resume, so only strong ETags are kept.
+    let etag = resp
+        .headers()
+        .get("etag")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|etag| !etag.is_empty() && !etag.starts_with("W/"))
+        .map(str::to_string);
     Some(DownloadProbe {
         total_bytes,
         range_supported: true,
+        etag,
     })
 }
 
@@ -1447,7 +2499,7 @@ fn split_download_ranges(plan: ParallelDownloadPlan) -> Vec<DownloadRange> {
 
 fn download_to_path_single(
     client: &Client,
-    path_and_query: &str,
+    url_parts: &UrlParts,
     url: &str,
     asset_path: &Path,
     known_total_bytes: Option<u64>,
@@ -1463,7 +2515,7 @@ fn download_to_path_single(
     retry_transient_http_operation(interactive, "download", &format!("`{asset_label}`"), || {
         download_to_path_single_once(
             client,
-            path_and_query,
+            url_parts,
             url,
             asset_path,
             known_total_bytes,
@@ -1474,13 +2526,13 @@ fn download_to_path_single(
 
 fn download_to_path_single_once(
     client: &Client,
-    path_and_query: &str,
+    url_parts: &UrlParts,
     url: &str,
     asset_path: &Path,
     known_total_bytes: Option<u64>,
     feedback: DownloadFeedback<'_>,
 ) -> Result<()> {
-    let req = build_download_request(client, path_and_query, None)?;
+    let req = build_download_request(client, url_parts, None)?;
     let mut resp = req
         .send_response_stream()
         .with_context(|| format!("download from `{url}` ({PROXY_HINT})"))?;
@@ -1588,20 +2640,33 @@ fn download_to_path_parallel(
     proxy_scope: za_config::ProxyScope,
     plan: ParallelDownloadPlan,
     feedback: DownloadFeedback<'_>,
+    staging: Option<&cache::PartialDownload>,
 ) -> Result<(), ParallelDownloadError> {
-    let part_paths = split_download_ranges(plan)
+    let parts = split_download_ranges(plan)
         .into_iter()
         .enumerate()
-        .map(|(index, range)| {
-            (
-                index,
+        .map(|(index, range)| match staging {
+            Some(staging) => {
+                let path = staging.part_path(index);
+                let staged = staged_part_len(&path, range);
+                DownloadPart {
+                    range,
+                    path,
+                    staged,
+                }
+            }
+            None => DownloadPart {
                 range,
-                asset_path.with_extension(format!("part-{index}")),
-            )
+                path: asset_path.with_extension(format!("part-{index}")),
+                staged: 0,
+            },
         })
         .collect::<Vec<_>>();
+    let if_range = staging.map(|staging| staging.etag().to_string());
     let start = Instant::now();
-    let progress = Arc::new(AtomicU64::new(0));
+    let progress = Arc::new(AtomicU64::new(
+        parts.iter().map(|part| part.staged).sum::<u64>(),
+    ));
     let reporter_stop = Arc::new(AtomicBool::new(false));
     let total_bytes = Some(plan.total_bytes);
     let print_progress = feedback.shows_progress();
@@ -1620,41 +2685,42 @@ fn download_to_path_parallel(
         )
     });
 
-    let handles = part_paths
-        .clone()
-        .into_iter()
-        .map(|(index, range, part_path)| {
-            let base_url = url_parts.base_url.clone();
-            let path_and_query = url_parts.path_and_query.clone();
+    let handles = parts
+        .iter()
+        .cloned()
+        .map(|part| {
+            let url_parts = url_parts.clone();
+            let if_range = if_range.clone();
             let progress = Arc::clone(&progress);
             let reporter_stop = Arc::clone(&reporter_stop);
             thread::spawn(move || {
                 let result = download_range_part(
-                    &base_url,
-                    &path_and_query,
-                    range,
-                    &part_path,
+                    &url_parts,
+                    &part,
+                    if_range.as_deref(),
                     proxy_scope,
                     progress,
                 );
                 if result.is_err() {
                     reporter_stop.store(true, Ordering::SeqCst);
                 }
-                result.map(|_| (index, part_path))
+                result
             })
         })
         .collect::<Vec<_>>();
 
-    let mut ordered_parts = vec![PathBuf::new(); part_paths.len()];
     for handle in handles {
         match handle.join() {
-            Ok(Ok((index, part_path))) => ordered_parts[index] = part_path,
+            Ok(Ok(())) => {}
             Ok(Err(err)) => {
                 reporter_stop.store(true, Ordering::SeqCst);
                 if let Some(reporter) = reporter.take() {
                     let _ = reporter.join();
                 }
-                cleanup_parallel_part_files(&part_paths);
+                // A resumable transfer keeps its parts unless the server stopped honoring ranges.
+                if staging.is_none() || matches!(err, ParallelDownloadError::Unsupported) {
+                    cleanup_parallel_part_files(&parts);
+                }
                 return Err(err);
             }
             Err(_) => {
@@ -1662,7 +2728,9 @@ fn download_to_path_parallel(
                 if let Some(reporter) = reporter.take() {
                     let _ = reporter.join();
                 }
-                cleanup_parallel_part_files(&part_paths);
+                if staging.is_none() {
+                    cleanup_parallel_part_files(&parts);
+                }
                 return Err(ParallelDownloadError::Failed(anyhow!(
                     "parallel download worker panicked for `{url}`"
                 )));
@@ -1690,8 +2758,8 @@ fn download_to_path_parallel(
         );
     }
 
-    merge_parallel_part_files(&ordered_parts, asset_path).map_err(ParallelDownloadError::Failed)?;
-    cleanup_parallel_part_files(&part_paths);
+    merge_parallel_part_files(&parts, asset_path).map_err(ParallelDownloadError::Failed)?;
+    cleanup_parallel_part_files(&parts);
     Ok(())
 }
 
@@ -1743,19 +2811,50 @@ fn spawn_parallel_download_reporter(
     })
 }
 
+/// Length of a staged part that can be resumed, or 0 when it is missing or longer than its range.
+fn staged_part_len(path: &Path, range: DownloadRange) -> u64 {
+    let expected = range.end.saturating_sub(range.start).saturating_add(1);
+    fs::metadata(path)
+        .map(|meta| meta.len())
+        .ok()
+        .filter(|len| *len <= expected)
+        .unwrap_or(0)
+}
+
 fn download_range_part(
-    base_url: &str,
-    path_and_query: &str,
-    range: DownloadRange,
-    part_path: &Path,
+    url_parts: &UrlParts,
+    part: &DownloadPart,
+    if_range: Option<&str>,
     proxy_scope: za_config::ProxyScope,
     progress: Arc<AtomicU64>,
 ) -> Result<(), ParallelDownloadError> {
     ensure_not_interrupted().map_err(ParallelDownloadError::Failed)?;
-    let client = build_http_client(base_url, "za-tool-manager", true, proxy_scope)
+    let part_path = part.path.as_path();
+    let expected = part
+        .range
+        .end
+        .saturating_sub(part.range.start)
+        .saturating_add(1)
+        .saturating_sub(part.staged);
+    if expected == 0 {
+        return Ok(());
+    }
+    let range = DownloadRange {
+        start: part.range.start + part.staged,
+        end: part.range.end,
+    };
+    let path_and_query = &url_parts.path_and_query;
+    let client = build_http_client(&url_parts.base_url, "za-tool-manager", true, proxy_scope)
         .map_err(ParallelDownloadError::Failed)?;
-    let req = build_download_request(&client, path_and_query, Some(range))
+    let mut req = build_download_request(&client, url_parts, Some(range))
         .map_err(ParallelDownloadError::Failed)?;
+    if let Some(etag) = if_range {
+        // A changed asset answers with the full body instead of 206, which abandons the resume.
+        req = req
+            .try_header("if-range", etag)
+            .context("set HTTP if-range header")
+            .map_err(ParallelDownloadError::Failed)?;
+    }
     let mut resp = req.send_response_stream().map_err(|err| {
         ParallelDownloadError::Failed(anyhow!(err).context(format!(
             "download byte range {}-{} from `{}` ({PROXY_HINT})",
@@ -1766,12 +2865,16 @@ fn download_range_part(
         return Err(ParallelDownloadError::Unsupported);
     }
 
-    let mut out = File::create(part_path)
+    let mut out = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(part.staged > 0)
+        .truncate(part.staged == 0)
+        .open(part_path)
         .with_context(|| format!("create partial file {}", part_path.display()))
         .map_err(ParallelDownloadError::Failed)?;
     let mut chunk = [0_u8; DOWNLOAD_READ_CHUNK_SIZE];
     let mut written = 0_u64;
-    let expected = range.end.saturating_sub(range.start).saturating_add(1);
     loop {
         ensure_not_interrupted().map_err(ParallelDownloadError::Failed)?;
         let read = resp
@@ -1801,11 +2904,11 @@ fn download_range_part(
     Ok(())
 }
 
-fn merge_parallel_part_files(part_paths: &[PathBuf], asset_path: &Path) -> Result<()> {
+fn merge_parallel_part_files(parts: &[DownloadPart], asset_path: &Path) -> Result<()> {
     let mut out = File::create(asset_path)
         .with_context(|| format!("create downloaded file {}", asset_path.display()))?;
     let mut chunk = [0_u8; DOWNLOAD_READ_CHUNK_SIZE];
-    for part_path in part_paths {
+    for part_path in parts.iter().map(|part| &part.path) {
         let mut part = File::open(part_path)
             .with_context(|| format!("open partial file {}", part_path.display()))?;
         loop {
@@ -1824,9 +2927,9 @@ fn merge_parallel_part_files(part_paths: &[PathBuf], asset_path: &Path) -> Resul
     Ok(())
 }
 
-fn cleanup_parallel_part_files(parts: &[(usize, DownloadRange, PathBuf)]) {
-    for (_, _, part_path) in parts {
-        let _ = fs::remove_file(part_path);
+fn cleanup_parallel_part_files(parts: &[DownloadPart]) {
+    for part in parts {
+        let _ = fs::remove_file(&part.path);
     }
 }
 
@@ -1835,11 +2938,22 @@ pub(super) fn download_filename(url: &str) -> Result<String> {
     Ok(parse_url_parts(url)?.file_name)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct UrlParts {
     base_url: String,
     path_and_query: String,
     file_name: String,
+    /// `authorization` header sent with every request for this URL.
+    authorization: Option<String>,
+}
+
+impl UrlParts {
+    fn with_authorization(mut self, authorization: Option<&DownloadAuthorization>) -> Self {
+        self.authorization = authorization
+            .and_then(|authorization| authorization.header_for(&self.base_url))
+            .map(str::to_string);
+        self
+    }
 }
 
 fn parse_url_parts(url: &str) -> Result<UrlParts> {
@@ -1885,10 +2999,11 @@ fn parse_url_parts(url: &str) -> Result<UrlParts> {
             path_and_query
         },
         file_name,
+        authorization: None,
     })
 }
 
-fn format_bytes_u64(bytes: u64) -> String {
+pub(super) fn format_bytes_u64(bytes: u64) -> String {
     format_bytes_f64(bytes as f64)
 }
 
@@ -1924,6 +3039,7 @@ fn download_stage_icon(stage: &str) -> &'static str {
         "download" => "⬇️",
         "verify" => "🔐",
         "extract" => "📦",
+        "cache" => "🗃️",
         _ => "•",
     }
 }
@@ -2032,8 +3148,7 @@ fn finish_download_tty_line() {
     let _ = io::stderr().flush();
 }
 
-fn verify_sha256_file(path: &Path, expected_hex: &str) -> Result<()> {
-    let actual_hex = sha256_file(path)?;
+fn ensure_sha256_matches(path: &Path, expected_hex: &str, actual_hex: &str) -> Result<()> {
     if !actual_hex.eq_ignore_ascii_case(expected_hex) {
         bail!(
             "sha256 mismatch for {}: expected {}, got {}",
@@ -2061,7 +3176,11 @@ pub(super) fn truncate_for_log(input: &str, max_chars: usize) -> String {
 enum ArchiveKind {
     TarGz,
     TarXz,
+    TarZst,
+    TarBz2,
     Zip,
+    /// A single gzip-compressed file, usually a bare executable.
+    Gz,
 }
 
 fn detect_archive_kind(name: &str) -> Option<ArchiveKind> {
@@ -2070,8 +3189,14 @@ fn detect_archive_kind(name: &str) -> Option<ArchiveKind> {
         Some(ArchiveKind::TarGz)
     } else if lower.ends_with(".tar.xz") || lower.ends_with(".txz") {
         Some(ArchiveKind::TarXz)
+    } else if lower.ends_with(".tar.zst") || lower.ends_with(".tzst") {
+        Some(ArchiveKind::TarZst)
+    } else if lower.ends_with(".tar.bz2") || lower.ends_with(".tbz2") || lower.ends_with(".tbz") {
+        Some(ArchiveKind::TarBz2)
     } else if lower.ends_with(".zip") {
         Some(ArchiveKind::Zip)
+    } else if lower.ends_with(".gz") {
+        Some(ArchiveKind::Gz)
     } else {
         None
     }
@@ -2096,13 +3221,12 @@ pub(super) fn extract_archive_into_dir(archive_path: &Path, dst: &Path) -> Resul
     remove_path_if_exists(dst)?;
     fs::create_dir_all(dst).with_context(|| format!("create {}", dst.display()))?;
 
-    let archive_kind = detect_archive_kind(
-        archive_path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or_default(),
-    )
-    .ok_or_else(|| anyhow!("unsupported archive format `{}`", archive_path.display()))?;
+    let archive_name = archive_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    let archive_kind = detect_archive_kind(archive_name)
+        .ok_or_else(|| anyhow!("unsupported archive format `{}`", archive_path.display()))?;
 
     let file = File::open(archive_path)
         .with_context(|| format!("open archive {}", archive_path.display()))?;
@@ -2121,6 +3245,21 @@ pub(super) fn extract_archive_into_dir(archive_path: &Path, dst: &Path) -> Resul
                 .unpack(dst)
                 .with_context(|| format!("extract archive {}", archive_path.display()))?;
         }
+        ArchiveKind::TarZst => {
+            let zst = ZstdDecoder::new(file)
+                .with_context(|| format!("read zstd archive {}", archive_path.display()))?;
+            let mut archive = Archive::new(zst);
+            archive
+                .unpack(dst)
+                .with_context(|| format!("extract archive {}", archive_path.display()))?;
+        }
+        ArchiveKind::TarBz2 => {
+            let bz2 = BzDecoder::new(file);
+            let mut archive = Archive::new(bz2);
+            archive
+                .unpack(dst)
+                .with_context(|| format!("extract archive {}", archive_path.display()))?;
+        }
         ArchiveKind::Zip => {
             let mut archive = ZipArchive::new(file)
                 .with_context(|| format!("read zip archive {}", archive_path.display()))?;
@@ -2128,6 +3267,21 @@ pub(super) fn extract_archive_into_dir(archive_path: &Path, dst: &Path) -> Resul
                 .extract(dst)
                 .with_context(|| format!("extract archive {}", archive_path.display()))?;
         }
+        ArchiveKind::Gz => {
+            // `tool-linux-amd64.gz` unpacks to `tool-linux-amd64`; the payload is assumed runnable.
+            let stem = &archive_name[..archive_name.len() - ".gz".len()];
+            if stem.is_empty() {
+                bail!("unsupported archive format `{}`", archive_path.display());
+            }
+            let out_path = dst.join(stem);
+            let mut out = File::create(&out_path)
+                .with_context(|| format!("create {}", out_path.display()))?;
+            io::copy(&mut GzDecoder::new(file), &mut out)
+                .with_context(|| format!("extract archive {}", archive_path.display()))?;
+            #[cfg(unix)]
+            fs::set_permissions(&out_path, fs::Permissions::from_mode(0o755))
+                .with_context(|| format!("set executable bit on {}", out_path.display()))?;
+        }
     }
 
     Ok(())
@@ -2185,7 +3339,7 @@ fn select_executable_from_dir(tool: &ToolRef, dir: &Path) -> Result<PathBuf> {
     )
 }
 
-fn collect_files_recursive(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+pub(super) fn collect_files_recursive(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
     for entry in fs::read_dir(dir).with_context(|| format!("read dir {}", dir.display()))? {
         let entry = entry?;
         let path = entry.path();
@@ -2226,16 +3380,27 @@ fn unique_temp_dir(prefix: &str) -> Result<PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::{
-        DownloadDisplay, DownloadExtractionMode, DownloadFeedback, DownloadRange, GithubRelease,
-        ParallelDownloadPlan, TEMP_DIR_PREFIX_DOWNLOAD, build_parallel_download_plan,
-        download_from_url, latest_prerelease_version_for_channel,
+        ArchiveKind, DownloadAuthorization, detect_archive_kind, download_to_path_parallel,
+        extract_archive_into_dir, parse_url_parts,
+    };
+    use super::{
+        AssetVerification, DownloadDisplay, DownloadExtractionMode, DownloadFeedback,
+        DownloadRange, GithubRelease, ParallelDownloadPlan, TEMP_DIR_PREFIX_DOWNLOAD,
+        build_parallel_download_plan, download_from_url, latest_prerelease_version_for_channel,
         latest_stable_version_for_latest_release, latest_stable_version_for_tags,
-        matched_temp_prefix, parse_content_range_total, parse_temp_dir_pid,
-        prerelease_channel_matches, process_is_alive, retry_transient_http_operation,
-        split_download_ranges,
+        latest_stable_version_in_range, matched_temp_prefix, parse_content_range_total,
+        parse_temp_dir_pid, prerelease_channel_matches, process_is_alive, release_notes_between,
+        retry_transient_http_operation, select_release_past_cooldown, split_download_ranges,
+    };
+    use super::{
+        ReleaseBackend, ReleasePolicy, ReleaseTrack, ReleaseVerification, ToolLayout, ToolPolicy,
+        ToolRef, ToolTarget, fetch_exact_latest_version_from_github_release,
+        fetch_fast_latest_version_from_github_release, resolve_github_release_asset,
     };
+    use crate::command::tool::cache::{PartialDownload, PartialDownloadMeta};
+    use crate::command::tool::policy::{AssetTemplate, ExpectedAssetName, ToolOrigin};
     use crate::command::za_config;
-    use semver::Version;
+    use semver::{Version, VersionReq};
     use std::{
         io::{self, Read, Write},
         net::{Shutdown, TcpListener, TcpStream},
@@ -2244,6 +3409,7 @@ mod tests {
             atomic::{AtomicBool, AtomicUsize, Ordering},
         },
         thread,
+        thread::JoinHandle,
         time::Duration,
     };
 
@@ -2300,6 +3466,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn latest_stable_version_in_range_stays_inside_requirement() {
+        let releases = vec![
+            github_release("14.0.3", false, false),
+            github_release("14.1.1", false, false),
+            github_release("14.2.0-rc.1", true, false),
+            github_release("15.0.0", false, false),
+            github_release("14.3.0", false, true),
+        ];
+
+        let tilde = VersionReq::parse("~14").expect("requirement");
+        assert_eq!(
+            latest_stable_version_in_range(&releases, "", &tilde).expect("in range"),
+            "14.1.1"
+        );
+        let bounded = VersionReq::parse(">=14.0, <14.1").expect("requirement");
+        assert_eq!(
+            latest_stable_version_in_range(&releases, "", &bounded).expect("in range"),
+            "14.0.3"
+        );
+        let missing = VersionReq::parse("^16").expect("requirement");
+        assert!(latest_stable_version_in_range(&releases, "", &missing).is_err());
+    }
+
+    #[test]
+    fn release_notes_between_keeps_newer_stable_releases_up_to_target() {
+        let mut releases = vec![
+            github_release("v1.4.0", false, false),
+            github_release("v1.3.0", false, false),
+            github_release("v1.3.0-rc.1", true, false),
+            github_release("v1.2.1", false, true),
+            github_release("v1.2.0", false, false),
+            github_release("v1.1.0", false, false),
+        ];
+        releases[1].body = Some("\n## Fixes\n- faster search\n".to_string());
+        releases[1].published_at = Some("2026-01-02T00:00:00Z".to_string());
+
+        let notes = release_notes_between(
+            releases,
+            "v",
+            &Version::parse("1.1.0").expect("installed"),
+            &Version::parse("1.3.0").expect("target"),
+        );
+
+        assert_eq!(
+            notes
+                .iter()
+                .map(|notes| notes.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1.3.0", "1.2.0"]
+        );
+        assert_eq!(notes[0].body, "## Fixes\n- faster search");
+        assert_eq!(
+            notes[0].published_at.as_deref(),
+            Some("2026-01-02T00:00:00Z")
+        );
+        assert_eq!(notes[1].body, "");
+    }
+
+    #[test]
+    fn min_release_age_steps_back_to_the_newest_old_enough_release() {
+        let mut releases = vec![
+            github_release("v1.3.0", false, false),
+            github_release("v1.2.0", false, false),
+            github_release("v1.1.0", false, false),
+        ];
+        releases[0].published_at = Some("2026-03-09T12:00:00Z".to_string());
+        releases[1].published_at = Some("2026-03-05T00:00:00Z".to_string());
+        releases[2].published_at = Some("2026-02-01T00:00:00Z".to_string());
+        let now = humantime::parse_rfc3339("2026-03-10T00:00:00Z").expect("now");
+        let min_age = Duration::from_secs(7 * 24 * 60 * 60);
+        let cutoff = now - min_age;
+
+        let (version, skipped) =
+            select_release_past_cooldown(&releases, "v", "1.3.0", None, None, cutoff, min_age)
+                .expect("select");
+        assert_eq!(version, "1.1.0");
+        let skipped = skipped.expect("held back release");
+        assert_eq!(skipped.version, "1.3.0");
+        assert_eq!(skipped.eligible_on, "2026-03-16");
+
+        let requirement = VersionReq::parse(">=1.2").expect("requirement");
+        let err = select_release_past_cooldown(
+            &releases,
+            "v",
+            "1.3.0",
+            None,
+            Some(&requirement),
+            cutoff,
+            min_age,
+        )
+        .expect_err("nothing in range is old enough");
+        assert!(
+            err.to_string()
+                .contains("1.3.0 becomes eligible on 2026-03-16")
+        );
+
+        let (version, skipped) =
+            select_release_past_cooldown(&releases, "v", "1.1.0", None, None, cutoff, min_age)
+                .expect("old candidate");
+        assert_eq!((version.as_str(), skipped), ("1.1.0", None));
+
+        let (version, skipped) = select_release_past_cooldown(
+            &releases,
+            "v",
+            "1.3.0",
+            Some("1.2.0"),
+            None,
+            cutoff,
+            min_age,
+        )
+        .expect("never below installed");
+        assert_eq!(version, "1.2.0");
+        assert_eq!(skipped.map(|skip| skip.version), Some("1.3.0".to_string()));
+    }
+
     #[test]
     fn latest_stable_version_accepts_two_segment_release_tags() {
         let releases = vec![
@@ -2349,10 +3631,37 @@ mod tests {
             tag_name: tag_name.to_string(),
             prerelease,
             draft,
+            body: None,
+            published_at: None,
             assets: Vec::new(),
         }
     }
 
+    #[test]
+    fn download_authorization_only_applies_to_backend_origin() {
+        let authorization = DownloadAuthorization {
+            origin: "https://gitlab.example.com".to_string(),
+            value: "Bearer secret".to_string(),
+        };
+
+        let same_host = parse_url_parts(
+            "https://gitlab.example.com/group/tool/-/releases/v1.0.0/downloads/tool.tar.gz",
+        )
+        .expect("url parts")
+        .with_authorization(Some(&authorization));
+        assert_eq!(same_host.authorization.as_deref(), Some("Bearer secret"));
+
+        let other_host = parse_url_parts("https://objects.example.net/tool.tar.gz")
+            .expect("url parts")
+            .with_authorization(Some(&authorization));
+        assert_eq!(other_host.authorization, None);
+
+        let other_port = parse_url_parts("https://gitlab.example.com:8443/tool.tar.gz")
+            .expect("url parts")
+            .with_authorization(Some(&authorization));
+        assert_eq!(other_port.authorization, None);
+    }
+
     #[test]
     fn parse_temp_dir_pid_accepts_expected_layout() {
         let name = "za-tool-download-1-123456789-4242";
@@ -2498,6 +3807,7 @@ mod tests {
         let url = format!("http://127.0.0.1:{}/artifact.tar.gz", addr.port());
         let pull = download_from_url(
             &url,
+            &AssetVerification::default(),
             None,
             za_config::ProxyScope::Tool,
             DownloadExtractionMode::KeepArchive,
@@ -2545,6 +3855,7 @@ mod tests {
         let url = format!("http://127.0.0.1:{}/artifact.tar.gz", addr.port());
         let pull = download_from_url(
             &url,
+            &AssetVerification::default(),
             None,
             za_config::ProxyScope::Tool,
             DownloadExtractionMode::KeepArchive,
@@ -2559,6 +3870,197 @@ mod tests {
         server.join().expect("join test server");
     }
 
+    #[test]
+    fn extract_archive_into_dir_handles_zstd_bzip2_and_bare_gzip() {
+        assert_eq!(
+            detect_archive_kind("tool-1.0.tar.zst"),
+            Some(ArchiveKind::TarZst)
+        );
+        assert_eq!(detect_archive_kind("TOOL.TZST"), Some(ArchiveKind::TarZst));
+        assert_eq!(
+            detect_archive_kind("tool-1.0.tar.bz2"),
+            Some(ArchiveKind::TarBz2)
+        );
+        assert_eq!(detect_archive_kind("tool.tbz"), Some(ArchiveKind::TarBz2));
+        assert_eq!(
+            detect_archive_kind("tool-linux-amd64.gz"),
+            Some(ArchiveKind::Gz)
+        );
+        assert_eq!(detect_archive_kind("tool.tar.gz"), Some(ArchiveKind::TarGz));
+        assert_eq!(detect_archive_kind("tool.zst"), None);
+
+        let root = std::env::temp_dir().join(format!(
+            "za-test-archive-formats-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("clock")
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&root).expect("create test root");
+        let tarball = |writer: &mut dyn Write| {
+            let mut builder = tar::Builder::new(writer);
+            let mut header = tar::Header::new_gnu();
+            header.set_size(5);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "tool-1.0/tool", &b"#!sh\n"[..])
+                .expect("append tar entry");
+            builder.finish().expect("finish tar");
+        };
+
+        let zst_path = root.join("tool-1.0.tar.zst");
+        let mut zst = zstd::stream::write::Encoder::new(
+            std::fs::File::create(&zst_path).expect("create zst"),
+            0,
+        )
+        .expect("zstd encoder");
+        tarball(&mut zst);
+        zst.finish().expect("finish zstd");
+
+        let bz2_path = root.join("tool-1.0.tar.bz2");
+        let mut bz2 = bzip2::write::BzEncoder::new(
+            std::fs::File::create(&bz2_path).expect("create bz2"),
+            bzip2::Compression::fast(),
+        );
+        tarball(&mut bz2);
+        bz2.finish().expect("finish bzip2");
+
+        for archive in [&zst_path, &bz2_path] {
+            let dst = root.join("unpack");
+            extract_archive_into_dir(archive, &dst).expect("extract tarball");
+            assert_eq!(
+                std::fs::read(dst.join("tool-1.0/tool")).expect("read extracted tool"),
+                b"#!sh\n"
+            );
+        }
+
+        let gz_path = root.join("tool-linux-amd64.gz");
+        let mut gz = flate2::write::GzEncoder::new(
+            std::fs::File::create(&gz_path).expect("create gz"),
+            flate2::Compression::fast(),
+        );
+        gz.write_all(b"binary").expect("write gz");
+        gz.finish().expect("finish gzip");
+        let dst = root.join("unpack-gz");
+        extract_archive_into_dir(&gz_path, &dst).expect("extract gzip");
+        let extracted = dst.join("tool-linux-amd64");
+        assert_eq!(std::fs::read(&extracted).expect("read binary"), b"binary");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&extracted)
+                .expect("stat binary")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parallel_download_resumes_staged_parts_with_matching_etag() {
+        let payload = Arc::new(
+            (0..2_400_000_u32)
+                .map(|i| (i % 251) as u8)
+                .collect::<Vec<_>>(),
+        );
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind test listener");
+        listener
+            .set_nonblocking(true)
+            .expect("configure nonblocking listener");
+        let addr = listener.local_addr().expect("listener addr");
+        let stop = Arc::new(AtomicBool::new(false));
+        let ranges = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let server_payload = Arc::clone(&payload);
+        let server_stop = Arc::clone(&stop);
+        let server_ranges = Arc::clone(&ranges);
+        let server = thread::spawn(move || {
+            while !server_stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        let request = read_http_request(&mut stream);
+                        let (start, end) =
+                            parse_http_range_header(&request).expect("range request");
+                        assert!(request.to_ascii_lowercase().contains("if-range: \"v1\""));
+                        server_ranges.lock().expect("ranges").push((start, end));
+                        let body = &server_payload[start as usize..=end as usize];
+                        write_http_response(
+                            &mut stream,
+                            206,
+                            &[(
+                                "Content-Range",
+                                format!("bytes {start}-{end}/{}", server_payload.len()),
+                            )],
+                            body,
+                            Some(body.len()),
+                        );
+                    }
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        let root = std::env::temp_dir().join(format!(
+            "za-test-partial-download-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("clock")
+                .as_nanos()
+        ));
+        let url = format!("http://127.0.0.1:{}/artifact.tar.gz", addr.port());
+        let plan = ParallelDownloadPlan {
+            total_bytes: payload.len() as u64,
+            parts: 2,
+        };
+        let meta = PartialDownloadMeta {
+            url: url.clone(),
+            etag: "\"v1\"".to_string(),
+            total_bytes: plan.total_bytes,
+            parts: plan.parts,
+        };
+        // An interrupted attempt left part 0 half written and part 1 complete.
+        let staging = PartialDownload::open(&root, &meta)
+            .expect("open staging")
+            .expect("staging unlocked");
+        std::fs::write(staging.part_path(0), &payload[..600_000]).expect("seed part 0");
+        std::fs::write(staging.part_path(1), &payload[1_200_000..]).expect("seed part 1");
+        drop(staging);
+
+        let staging = PartialDownload::open(&root, &meta)
+            .expect("reopen staging")
+            .expect("staging unlocked");
+        assert!(staging.resumed());
+        let asset_path = root.join("artifact.tar.gz");
+        let result = download_to_path_parallel(
+            &parse_url_parts(&url).expect("url parts"),
+            &url,
+            &asset_path,
+            za_config::ProxyScope::Tool,
+            plan,
+            DownloadFeedback::new(DownloadDisplay::Quiet, None),
+            Some(&staging),
+        );
+        assert!(result.is_ok(), "resumed download should succeed");
+        assert_eq!(std::fs::read(&asset_path).expect("read asset"), *payload);
+        assert_eq!(
+            *ranges.lock().expect("ranges"),
+            vec![(600_000, 1_199_999)],
+            "only the missing tail of part 0 is requested"
+        );
+
+        stop.store(true, Ordering::SeqCst);
+        server.join().expect("join test server");
+        staging.discard();
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
     fn handle_single_stream_retry_request(
         mut stream: TcpStream,
         payload: Arc<Vec<u8>>,
@@ -2656,6 +4158,214 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gitlab_backend_resolves_tags_assets_and_checksum_files() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind test listener");
+        let origin = format!(
+            "http://127.0.0.1:{}",
+            listener.local_addr().expect("listener addr").port()
+        );
+        let digest = "ab".repeat(32);
+        let project = "/gitlab/api/v4/projects/platform%2Ftools%2Fteamcli";
+        let release = |tag: &str, upcoming: bool| {
+            format!(
+                r#"{{"tag_name":"{tag}","upcoming_release":{upcoming},"assets":{{"links":[
+                    {{"name":"teamcli-1.2.0.tar.gz","url":"{origin}/ui/teamcli-1.2.0.tar.gz","direct_asset_url":"{origin}/downloads/teamcli-1.2.0.tar.gz"}},
+                    {{"name":"SHA256SUMS","url":"{origin}/downloads/SHA256SUMS"}}
+                ]}}}}"#
+            )
+        };
+        let (stop, server) = spawn_route_server(
+            listener,
+            vec![
+                (
+                    format!("{project}/releases/v1.2.0"),
+                    release("v1.2.0", false),
+                ),
+                (
+                    format!("{project}/releases?per_page=30&page=1"),
+                    format!(
+                        "[{},{},{}]",
+                        release("v1.4.0", true),
+                        release("v1.3.0-rc.1", false),
+                        release("v1.2.0", false)
+                    ),
+                ),
+                (
+                    "/downloads/SHA256SUMS".to_string(),
+                    format!("{digest}  teamcli-1.2.0.tar.gz\n"),
+                ),
+            ],
+        );
+
+        let (tool_policy, release_policy) = test_release_policy(ReleaseBackend::Gitlab {
+            base_url: Box::leak(format!("{origin}/gitlab/").into_boxed_str()),
+        });
+        let latest = fetch_exact_latest_version_from_github_release(
+            tool_policy,
+            release_policy,
+            za_config::ProxyScope::Tool,
+        )
+        .expect("latest stable release");
+        assert_eq!(latest, "1.2.0");
+
+        let tool = ToolRef {
+            name: "teamcli".to_string(),
+            version: "1.2.0".to_string(),
+        };
+        let (asset, verification) = resolve_github_release_asset(
+            &tool,
+            release_policy,
+            za_config::ProxyScope::Tool,
+            ToolTarget::host(),
+        )
+        .expect("resolve release asset");
+        assert_eq!(
+            asset.browser_download_url,
+            format!("{origin}/downloads/teamcli-1.2.0.tar.gz")
+        );
+        assert_eq!(
+            verification.describe(&asset.browser_download_url),
+            format!(
+                "URL {origin}/downloads/teamcli-1.2.0.tar.gz (sha256={digest} from SHA256SUMS)"
+            )
+        );
+
+        stop.store(true, Ordering::SeqCst);
+        server.join().expect("join test server");
+    }
+
+    #[test]
+    fn gitea_backend_reads_latest_and_paged_releases() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind test listener");
+        let origin = format!(
+            "http://127.0.0.1:{}",
+            listener.local_addr().expect("listener addr").port()
+        );
+        let release = |tag: &str, prerelease: bool| {
+            format!(
+                r#"{{"tag_name":"{tag}","prerelease":{prerelease},"draft":false,"assets":[
+                    {{"name":"teamcli-1.2.0.tar.gz","browser_download_url":"{origin}/teamcli-1.2.0.tar.gz"}}
+                ]}}"#
+            )
+        };
+        let repo = "/api/v1/repos/platform/teamcli";
+        let (stop, server) = spawn_route_server(
+            listener,
+            vec![
+                (format!("{repo}/releases/latest"), release("v1.3.0", false)),
+                (
+                    format!("{repo}/releases?limit=30&page=1"),
+                    format!(
+                        "[{},{}]",
+                        release("v1.4.0-beta.1", true),
+                        release("v1.3.0", false)
+                    ),
+                ),
+            ],
+        );
+
+        let (tool_policy, mut release_policy) = test_release_policy(ReleaseBackend::Gitea {
+            base_url: Box::leak(origin.clone().into_boxed_str()),
+        });
+        release_policy.owner = "platform";
+        assert_eq!(
+            fetch_fast_latest_version_from_github_release(
+                tool_policy,
+                release_policy,
+                za_config::ProxyScope::Tool,
+            )
+            .expect("latest release"),
+            "1.3.0"
+        );
+        assert_eq!(
+            fetch_exact_latest_version_from_github_release(
+                tool_policy,
+                release_policy,
+                za_config::ProxyScope::Tool,
+            )
+            .expect("paged releases"),
+            "1.3.0"
+        );
+
+        stop.store(true, Ordering::SeqCst);
+        server.join().expect("join test server");
+    }
+
+    fn test_release_policy(backend: ReleaseBackend) -> (ToolPolicy, ReleasePolicy) {
+        let release = ReleasePolicy {
+            project_label: "teamcli",
+            backend,
+            owner: "platform/tools",
+            repo: "teamcli",
+            tag_prefix: "v",
+            expected_asset_name: Some(ExpectedAssetName::Template(AssetTemplate {
+                project_label: "teamcli",
+                template: "teamcli-{version}.tar.gz",
+                targets: &[],
+            })),
+            verification: ReleaseVerification::ChecksumFile,
+            track: ReleaseTrack::VersionedTags,
+        };
+        let tool = ToolPolicy {
+            canonical_name: "teamcli",
+            aliases: &[],
+            source_label: "test",
+            layout: ToolLayout::Binary,
+            package: None,
+            shell_init: &[],
+            completions: None,
+            exposed: &[],
+            origin: ToolOrigin::Catalog,
+            release: Some(release),
+        };
+        (tool, release)
+    }
+
+    /// Serves fixed bodies by request path until `stop` is set; unknown paths get a 404.
+    fn spawn_route_server(
+        listener: TcpListener,
+        routes: Vec<(String, String)>,
+    ) -> (Arc<AtomicBool>, JoinHandle<()>) {
+        listener
+            .set_nonblocking(true)
+            .expect("configure nonblocking listener");
+        let stop = Arc::new(AtomicBool::new(false));
+        let server_stop = Arc::clone(&stop);
+        let server = thread::spawn(move || {
+            while !server_stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        stream
+                            .set_nonblocking(false)
+                            .expect("configure blocking stream");
+                        let request = read_http_request(&mut stream);
+                        let path = request
+                            .lines()
+                            .next()
+                            .and_then(|line| line.split_whitespace().nth(1))
+                            .unwrap_or_default();
+                        match routes.iter().find(|(route, _)| route == path) {
+                            Some((_, body)) => write_http_response(
+                                &mut stream,
+                                200,
+                                &[],
+                                body.as_bytes(),
+                                Some(body.len()),
+                            ),
+                            None => write_http_response(&mut stream, 404, &[], b"", Some(0)),
+                        }
+                    }
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+        (stop, server)
+    }
+
     fn read_http_request(stream: &mut TcpStream) -> String {
         let mut buf = Vec::new();
         let mut chunk = [0_u8; 1024];
@@ -1,3 +1,6 @@
+use super::admin::{AdminToolPolicy, installed_tool_source, load_admin_policy};
+use super::history::{ToolHistoryAction, ToolHistoryEvent, record_tool_event};
+use super::target::ToolTarget;
 use super::*;
 use graviola::hashing::{Hash, HashContext, Sha256};
 
@@ -5,6 +8,8 @@ pub(super) fn write_manifest(
     home: &ToolHome,
     tool: &ToolRef,
     source: &InstallSource,
+    target: Option<ToolTarget>,
+    requirement: Option<&VersionReq>,
 ) -> Result<()> {
     let install_path = home.install_path(tool);
     let meta = fs::metadata(&install_path)
@@ -22,6 +27,12 @@ pub(super) fn write_manifest(
         source_detail: source.detail.clone(),
         sha256: digest,
         size_bytes: meta.len(),
+        target: target.map(|target| target.to_string()),
+        requirement: requirement.map(ToString::to_string),
+        asset_url: source.asset.as_ref().map(|asset| asset.url.clone()),
+        asset_sha256: source.asset.as_ref().map(|asset| asset.sha256.clone()),
+        adhoc: super::adhoc::adhoc_source(&tool.name),
+        unverified: source.unverified,
     };
 
     let manifest_path = home.manifest_path(tool);
@@ -42,8 +53,187 @@ pub(super) fn ensure_manifest(home: &ToolHome, tool: &ToolRef) -> Result<()> {
     let source = InstallSource {
         kind: SOURCE_KIND_SYNTHESIZED,
         detail: "legacy install inferred from store layout".to_string(),
+        asset: None,
+        unverified: false,
     };
-    write_manifest(home, tool, &source)
+    write_manifest(home, tool, &source, None, None)
+}
+
+/// Remembers the range an existing install was requested with so updates stay inside it.
+pub(super) fn record_manifest_requirement(
+    home: &ToolHome,
+    tool: &ToolRef,
+    requirement: &VersionReq,
+) -> Result<()> {
+    let Some(mut manifest) = read_manifest(home, tool)? else {
+        return Ok(());
+    };
+    let requirement = requirement.to_string();
+    if manifest.requirement.as_deref() == Some(requirement.as_str()) {
+        return Ok(());
+    }
+    manifest.requirement = Some(requirement);
+    let manifest_path = home.manifest_path(tool);
+    let content = serde_json::to_vec_pretty(&manifest).context("serialize tool manifest")?;
+    write_file_atomically(&manifest_path, content)
+        .with_context(|| format!("write manifest {}", manifest_path.display()))
+}
+
+/// Range recorded for the active version, if it was installed from one.
+pub(super) fn read_active_requirement(home: &ToolHome, name: &str) -> Result<Option<VersionReq>> {
+    let Some(version) = read_current_version(home, name)? else {
+        return Ok(None);
+    };
+    let tool = ToolRef {
+        name: name.to_string(),
+        version,
+    };
+    let Some(raw) = read_manifest(home, &tool)?.and_then(|manifest| manifest.requirement) else {
+        return Ok(None);
+    };
+    VersionReq::parse(&raw)
+        .map(Some)
+        .with_context(|| format!("parse recorded version requirement `{raw}` for `{name}`"))
+}
+
+/// Host-runnable target recorded for the active version, so updates keep its libc choice.
+pub(super) fn read_active_target(home: &ToolHome, name: &str) -> Result<Option<ToolTarget>> {
+    let Some(version) = read_current_version(home, name)? else {
+        return Ok(None);
+    };
+    let tool = ToolRef {
+        name: name.to_string(),
+        version,
+    };
+    let Some(raw) = read_manifest(home, &tool)?.and_then(|manifest| manifest.target) else {
+        return Ok(None);
+    };
+    Ok(ToolTarget::parse(&raw)
+        .ok()
+        .filter(|target| target.runs_on_host()))
+}
+
+pub(super) fn read_manifest(home: &ToolHome, tool: &ToolRef) -> Result<Option<ToolManifest>> {
+    let manifest_path = home.manifest_path(tool);
+    let raw = match fs::read_to_string(&manifest_path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| format!("read manifest {}", manifest_path.display()));
+        }
+    };
+    serde_json::from_str(&raw)
+        .map(Some)
+        .with_context(|| format!("parse manifest {}", manifest_path.display()))
+}
+
+/// Refuses to reuse a store entry that was downloaded for a different target.
+pub(super) fn ensure_installed_target_matches(
+    home: &ToolHome,
+    tool: &ToolRef,
+    target: ToolTarget,
+) -> Result<()> {
+    let Ok(Some(manifest)) = read_manifest(home, tool) else {
+        return Ok(());
+    };
+    let installed_target = manifest
+        .target
+        .unwrap_or_else(|| ToolTarget::host().to_string());
+    let installed = ToolTarget::parse(&installed_target).ok();
+    let compatible = match installed {
+        Some(installed) => {
+            installed.os == target.os
+                && installed.arch == target.arch
+                && (target.libc.is_none()
+                    || installed.libc.is_none()
+                    || installed.libc == target.libc)
+        }
+        None => false,
+    };
+    if !compatible {
+        bail!(
+            "{} is already installed for {installed_target} in {} scope; uninstall it first to install it for {target}",
+            tool.image(),
+            home.scope.label()
+        );
+    }
+    Ok(())
+}
+
+/// Refuses to reuse a store entry whose recorded download differs from the lockfile pin.
+pub(super) fn ensure_installed_asset_matches_lock(
+    home: &ToolHome,
+    tool: &ToolRef,
+    locked: &ReleaseAsset,
+) -> Result<()> {
+    let Ok(Some(manifest)) = read_manifest(home, tool) else {
+        return Ok(());
+    };
+    if let Some(installed) = manifest.asset_sha256.as_deref()
+        && !installed.eq_ignore_ascii_case(&locked.sha256)
+    {
+        bail!(
+            "{} in {} scope was installed from an asset with sha256={installed}, but the lockfile pins sha256={}; uninstall it to install the locked asset",
+            tool.image(),
+            home.scope.label(),
+            locked.sha256
+        );
+    }
+    Ok(())
+}
+
+/// Asset an earlier install of this exact version recorded for `target`, when its download is
+/// still in the cache. Reinstalls pin it so they skip release discovery and work offline. The
+/// manifest is consulted first; after an uninstall or prune the cache's own record is used.
+pub(super) fn cached_recorded_asset(
+    home: &ToolHome,
+    tool: &ToolRef,
+    target: ToolTarget,
+) -> Option<ReleaseAsset> {
+    let root = cache::tool_cache_dir().ok()?;
+    let target = target.to_string();
+    let recorded = read_manifest(home, tool)
+        .ok()
+        .flatten()
+        .filter(|manifest| manifest.target.as_deref() == Some(target.as_str()))
+        .and_then(|manifest| {
+            Some(ReleaseAsset {
+                url: manifest.asset_url?,
+                sha256: manifest.asset_sha256?,
+            })
+        })
+        .filter(|asset| cache::contains_artifact(&root, &asset.sha256));
+    recorded.or_else(|| {
+        cache::find_cached_release(
+            &root,
+            &cache::CachedRelease {
+                tool: tool.name.clone(),
+                version: tool.version.clone(),
+                target,
+            },
+        )
+    })
+}
+
+/// Remembers in the cache which release a verified asset installed, so a later reinstall of the
+/// same version can reuse it even after the manifest is gone.
+pub(super) fn record_cached_install(tool: &ToolRef, source: &InstallSource, target: ToolTarget) {
+    let Some(asset) = source.asset.as_ref().filter(|_| !source.unverified) else {
+        return;
+    };
+    let Ok(root) = cache::tool_cache_dir() else {
+        return;
+    };
+    // Losing this record only costs a release lookup on the next reinstall.
+    let _ = cache::record_cached_release(
+        &root,
+        &asset.sha256,
+        cache::CachedRelease {
+            tool: tool.name.clone(),
+            version: tool.version.clone(),
+            target: target.to_string(),
+        },
+    );
 }
 
 pub(super) fn manifest_source_label(home: &ToolHome, tool: &ToolRef) -> Result<String> {
@@ -107,6 +297,12 @@ pub(super) fn adopt_tool(home: &ToolHome, tool: &str, dry_run: bool) -> Result<(
 
 pub(super) fn uninstall(home: &ToolHome, mut requested: ToolSpec) -> Result<()> {
     requested.name = canonical_tool_name(&requested.name);
+    if let Some(requirement) = requested.requirement {
+        bail!("`za tool uninstall` expects an exact version, not the range `{requirement}`");
+    }
+    if home.shares_store() {
+        return unpin_shared_tool(home, &requested);
+    }
     match requested.version {
         Some(version) => uninstall_version(
             home,
@@ -119,6 +315,36 @@ pub(super) fn uninstall(home: &ToolHome, mut requested: ToolSpec) -> Result<()>
     }
 }
 
+/// Clears a project's activation of a tool while leaving the shared store untouched.
+fn unpin_shared_tool(home: &ToolHome, requested: &ToolSpec) -> Result<()> {
+    let name = &requested.name;
+    let Some(active) = read_current_version(home, name)? else {
+        println!("🗑  Not pinned in {} scope: {name}", home.scope.label());
+        return Ok(());
+    };
+    if let Some(version) = requested.version.as_deref()
+        && normalize_version(version) != active
+    {
+        println!(
+            "🗑  Not pinned in {} scope: {name}:{version}",
+            home.scope.label()
+        );
+        return Ok(());
+    }
+    remove_file_if_exists(&home.current_file(name))?;
+    remove_active_entry(home, name)?;
+    record_tool_event(
+        home,
+        ToolHistoryEvent::new(ToolHistoryAction::Uninstall, name, Some(&active))
+            .detail("unpinned from the shared store"),
+    );
+    println!(
+        "🗑  Unpinned {name}:{active} from {} scope",
+        home.scope.label()
+    );
+    Ok(())
+}
+
 fn uninstall_version(home: &ToolHome, tool: &ToolRef) -> Result<()> {
     let version_dir = home.version_dir(tool);
     if !version_dir.exists() {
@@ -135,6 +361,19 @@ fn uninstall_version(home: &ToolHome, tool: &ToolRef) -> Result<()> {
     if collect_dir_names(&home.name_dir(&tool.name))?.is_empty() {
         let _ = fs::remove_dir(home.name_dir(&tool.name));
     }
+    let event = ToolHistoryEvent::new(
+        ToolHistoryAction::Uninstall,
+        &tool.name,
+        Some(&tool.version),
+    );
+    record_tool_event(
+        home,
+        if was_current {
+            event.detail("cleared active version")
+        } else {
+            event
+        },
+    );
 
     if was_current {
         remove_file_if_exists(&home.current_file(&tool.name))?;
@@ -156,20 +395,32 @@ fn uninstall_all_versions(home: &ToolHome, name: &str) -> Result<()> {
         return Ok(());
     }
 
-    let versions = collect_dir_names(&name_dir)?;
+    let mut versions = collect_dir_names(&name_dir)?;
+    versions.sort();
     let removed_count = versions.len();
+    let active = read_current_version(home, name)?;
     fs::remove_dir_all(&name_dir).with_context(|| format!("remove {}", name_dir.display()))?;
     remove_file_if_exists(&home.current_file(name))?;
     remove_active_entry(home, name)?;
     cleanup_post_uninstall_integrations(home, name)?;
+    record_tool_event(
+        home,
+        ToolHistoryEvent::new(ToolHistoryAction::Uninstall, name, active.as_deref())
+            .detail(format!("removed {}", versions.join(", "))),
+    );
 
     println!("🗑  Removed {name} ({removed_count} version(s)) and cleared active entry");
     Ok(())
 }
 
-pub(crate) fn prune_non_active_versions(home: &ToolHome, active: &ToolRef) -> Result<Vec<String>> {
+/// Removes store versions beyond the newest `retain` installs, never touching the active one.
+pub(crate) fn prune_non_active_versions(
+    home: &ToolHome,
+    active: &ToolRef,
+    retain: usize,
+) -> Result<Vec<String>> {
     let mut removed = Vec::new();
-    for version in stale_versions_to_prune(home, active)? {
+    for version in stale_versions_to_prune(home, active, retain)? {
         let stale = ToolRef {
             name: active.name.clone(),
             version: version.clone(),
@@ -185,21 +436,137 @@ pub(crate) fn prune_non_active_versions(home: &ToolHome, active: &ToolRef) -> Re
     Ok(removed)
 }
 
-pub(super) fn stale_versions_to_prune(home: &ToolHome, active: &ToolRef) -> Result<Vec<String>> {
-    let name_dir = home.name_dir(&active.name);
-    if !name_dir.exists() {
-        return Ok(Vec::new());
-    }
-
+pub(super) fn stale_versions_to_prune(
+    home: &ToolHome,
+    active: &ToolRef,
+    retain: usize,
+) -> Result<Vec<String>> {
     let active_version = normalize_version(&active.version);
-    let mut stale = collect_dir_names(&name_dir)?
+    let mut stale = installed_versions_by_recency(home, &active.name)?
         .into_iter()
         .filter(|version| normalize_version(version) != active_version)
+        .skip(retain.saturating_sub(1))
         .collect::<Vec<_>>();
     stale.sort();
     Ok(stale)
 }
 
+/// Store versions of `name`, most recently installed first according to their manifests.
+pub(super) fn installed_versions_by_recency(home: &ToolHome, name: &str) -> Result<Vec<String>> {
+    let mut versions = collect_dir_names(&home.name_dir(name))?
+        .into_iter()
+        .map(|version| {
+            let tool = ToolRef {
+                name: name.to_string(),
+                version,
+            };
+            let installed_at = read_manifest(home, &tool)
+                .ok()
+                .flatten()
+                .map_or(0, |manifest| manifest.installed_at_unix_secs);
+            (installed_at, tool.version)
+        })
+        .collect::<Vec<_>>();
+    versions.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    Ok(versions.into_iter().map(|(_, version)| version).collect())
+}
+
+/// Version `za tool rollback` re-activates by default: the newest retained non-active install.
+pub(super) fn rollback_candidate(home: &ToolHome, name: &str) -> Result<Option<String>> {
+    let active = read_current_version(home, name)?;
+    Ok(installed_versions_by_recency(home, name)?
+        .into_iter()
+        .filter(|version| Some(version.as_str()) != active.as_deref())
+        .find(|version| {
+            home.install_path(&ToolRef {
+                name: name.to_string(),
+                version: version.clone(),
+            })
+            .exists()
+        }))
+}
+
+pub(super) fn rollback_tool(home: &ToolHome, tool: &str, to: Option<&str>) -> Result<()> {
+    let admin_policy = load_admin_policy(home)?;
+    rollback_tool_with_policy(home, tool, to, admin_policy.as_ref())
+}
+
+/// Rolls back like [`rollback_tool`], refusing versions the admin policy does not allow.
+pub(super) fn rollback_tool_with_policy(
+    home: &ToolHome,
+    tool: &str,
+    to: Option<&str>,
+    admin_policy: Option<&AdminToolPolicy>,
+) -> Result<()> {
+    let name = canonical_tool_name(&ToolSpec::from_args(tool, None)?.name);
+    let Some(active) = read_current_version(home, &name)? else {
+        bail!(
+            "`{}` has no active managed version in {} scope",
+            name,
+            home.scope.label()
+        );
+    };
+    let version = match to {
+        Some(version) => {
+            let version = normalize_version(version);
+            if version == active {
+                bail!(
+                    "`{name}:{version}` is already active in {} scope",
+                    home.scope.label()
+                );
+            }
+            let target = ToolRef {
+                name: name.clone(),
+                version,
+            };
+            if !home.install_path(&target).exists() {
+                let retained = installed_versions_by_recency(home, &name)?
+                    .into_iter()
+                    .filter(|version| *version != active)
+                    .collect::<Vec<_>>();
+                bail!(
+                    "`{}` is not retained in {} scope; retained versions: {}",
+                    target.image(),
+                    home.scope.label(),
+                    if retained.is_empty() {
+                        "none".to_string()
+                    } else {
+                        retained.join(", ")
+                    }
+                );
+            }
+            target.version
+        }
+        None => rollback_candidate(home, &name)?.ok_or_else(|| {
+            anyhow!(
+                "no retained version of `{name}` to roll back to in {} scope; keep more versions with `za config set tool-retain-versions <N>`",
+                home.scope.label()
+            )
+        })?,
+    };
+    let target = ToolRef { name, version };
+    if let Some(admin_policy) = admin_policy {
+        admin_policy.check_tool(&target.name, installed_tool_source(home, &target))?;
+        admin_policy.check_version(&target.name, &target.version)?;
+    }
+    ensure_manifest(home, &target)?;
+    activate_tool(home, &target)?;
+    record_tool_event(
+        home,
+        ToolHistoryEvent::new(
+            ToolHistoryAction::Rollback,
+            &target.name,
+            Some(&target.version),
+        )
+        .previous_version(Some(&active)),
+    );
+    println!(
+        "↩️  Rolled back {}: {active} -> {}",
+        target.name, target.version
+    );
+    Ok(())
+}
+
 pub(crate) fn command_candidates(name: &str) -> Vec<String> {
     let mut out = vec![name.to_string()];
     #[cfg(windows)]
@@ -301,6 +668,12 @@ pub(super) fn read_current_version(home: &ToolHome, name: &str) -> Result<Option
     if version.is_empty() {
         return Ok(None);
     }
+    validate_version_segment(&version).with_context(|| {
+        format!(
+            "current version file {} names an invalid `{name}` version",
+            p.display()
+        )
+    })?;
     Ok(Some(version))
 }
 
@@ -393,6 +766,10 @@ fn sync_active_entry(home: &ToolHome, tool: &ToolRef) -> Result<()> {
     if !src.exists() {
         bail!("tool version not installed: {}", tool.image());
     }
+    // Staged up front so a missing exposed file or an unwritable plugin dir fails before the
+    // primary entry changes; the staged links are renamed in only after it is active.
+    let exposed = stage_exposed_links(&exposed_entry_links(home, tool)?)
+        .with_context(|| format!("stage exposed entries of {}", tool.image()))?;
 
     match tool_layout_for_name(&tool.name) {
         ToolLayout::Binary => {
@@ -408,7 +785,6 @@ fn sync_active_entry(home: &ToolHome, tool: &ToolRef) -> Result<()> {
                     )
                 })?;
             }
-            Ok(())
         }
         ToolLayout::Package => {
             let src_dir = home.package_payload_dir(tool);
@@ -419,19 +795,156 @@ fn sync_active_entry(home: &ToolHome, tool: &ToolRef) -> Result<()> {
                     tool.image(),
                     src_dir.display()
                 )
-            })
+            })?;
+        }
+    }
+
+    exposed
+        .commit()
+        .with_context(|| format!("expose {}", tool.image()))
+}
+
+/// Extra entry links created next to their final paths. Dropping them before `commit` removes
+/// whatever is still staged, so a failed activation leaves no stray links behind.
+struct StagedLinks {
+    links: Vec<(PathBuf, PathBuf)>,
+}
+
+impl StagedLinks {
+    fn commit(mut self) -> Result<()> {
+        for (tmp, dst) in std::mem::take(&mut self.links) {
+            if let Err(err) = fs::rename(&tmp, &dst) {
+                let _ = remove_file_if_exists(&tmp);
+                return Err(err).with_context(|| format!("activate link {}", dst.display()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for StagedLinks {
+    fn drop(&mut self) {
+        for (tmp, _) in &self.links {
+            let _ = remove_file_if_exists(tmp);
+        }
+    }
+}
+
+fn stage_exposed_links(links: &[(PathBuf, PathBuf)]) -> Result<StagedLinks> {
+    let mut staged = StagedLinks { links: Vec::new() };
+    for (src, dst) in links {
+        let tmp = stage_link(src, dst)?;
+        staged.links.push((tmp, dst.clone()));
+    }
+    Ok(staged)
+}
+
+fn exposed_entry_source(home: &ToolHome, tool: &ToolRef, entry: &ExposedEntry) -> PathBuf {
+    if entry.relpath.is_empty() {
+        home.install_path(tool)
+    } else {
+        home.package_payload_dir(tool).join(entry.relpath)
+    }
+}
+
+/// Exposed links of the active version that are missing or point somewhere else. Files za does
+/// not manage are left out, as activation never replaces them.
+pub(super) fn stale_exposed_links(home: &ToolHome, tool: &ToolRef) -> Result<Vec<PathBuf>> {
+    let mut stale = Vec::new();
+    for entry in exposed_entries_for_name(&tool.name) {
+        let Some(dst) = home.exposed_entry_path(entry.placement)? else {
+            continue;
+        };
+        if is_unmanaged_path(home, &dst) {
+            continue;
+        }
+        let src = exposed_entry_source(home, tool, entry);
+        let expected = fs::canonicalize(&src).unwrap_or(src);
+        let linked = fs::read_link(&dst)
+            .ok()
+            .map(|target| fs::canonicalize(&target).unwrap_or(target));
+        if linked.as_deref() != Some(expected.as_path()) {
+            stale.push(dst);
+        }
+    }
+    Ok(stale)
+}
+
+/// Source and link path of every extra entry the policy exposes. Links over files za does not
+/// manage, such as a Docker Desktop plugin, are skipped rather than replaced.
+fn exposed_entry_links(home: &ToolHome, tool: &ToolRef) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut out = Vec::new();
+    for entry in exposed_entries_for_name(&tool.name) {
+        let Some(dst) = home.exposed_entry_path(entry.placement)? else {
+            continue;
+        };
+        let src = exposed_entry_source(home, tool, entry);
+        if !is_executable_file(&src) {
+            bail!(
+                "exposed entry of {} is not an executable file: {}",
+                tool.image(),
+                src.display()
+            );
+        }
+        if is_unmanaged_path(home, &dst) {
+            eprintln!(
+                "⚠️  Skipped {}: existing file is not managed by za",
+                dst.display()
+            );
+            continue;
         }
+        out.push((src, dst));
     }
+    Ok(out)
 }
 
 fn remove_active_entry(home: &ToolHome, name: &str) -> Result<()> {
     remove_path_if_exists(&home.bin_path(name))?;
     remove_path_if_exists(&home.current_package_path(name))?;
+    for entry in exposed_entries_for_name(name) {
+        if let Some(dst) = home.exposed_entry_path(entry.placement)?
+            && !is_unmanaged_path(home, &dst)
+        {
+            remove_path_if_exists(&dst)?;
+        }
+    }
     Ok(())
 }
 
+/// Anything at `path` other than a symlink into this scope's store.
+fn is_unmanaged_path(home: &ToolHome, path: &Path) -> bool {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return false;
+    };
+    if !meta.file_type().is_symlink() {
+        return true;
+    }
+    let Ok(target) = fs::read_link(path) else {
+        return true;
+    };
+    let store = fs::canonicalize(&home.store_dir).unwrap_or_else(|_| home.store_dir.clone());
+    !(target.starts_with(&home.store_dir) || target.starts_with(&store))
+}
+
 #[cfg(unix)]
 fn link_executable(src: &Path, dst: &Path) -> Result<()> {
+    let tmp = stage_link(src, dst)?;
+    if let Err(err) = fs::rename(&tmp, dst) {
+        let _ = remove_file_if_exists(&tmp);
+        return Err(err)
+            .with_context(|| format!("activate link {} -> {}", dst.display(), src.display()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn link_executable(_src: &Path, _dst: &Path) -> Result<()> {
+    bail!("symlink activation is not supported on this platform")
+}
+
+/// Creates a symlink to `src` beside `dst` and returns its path, ready to be renamed over `dst`.
+#[cfg(unix)]
+fn stage_link(src: &Path, dst: &Path) -> Result<PathBuf> {
     use std::os::unix::fs::symlink;
 
     if let Some(parent) = dst.parent() {
@@ -442,16 +955,11 @@ fn link_executable(src: &Path, dst: &Path) -> Result<()> {
     remove_file_if_exists(&tmp)?;
     symlink(&src, &tmp)
         .with_context(|| format!("symlink {} -> {}", tmp.display(), src.display()))?;
-    if let Err(err) = fs::rename(&tmp, dst) {
-        let _ = remove_file_if_exists(&tmp);
-        return Err(err)
-            .with_context(|| format!("activate link {} -> {}", dst.display(), src.display()));
-    }
-    Ok(())
+    Ok(tmp)
 }
 
 #[cfg(not(unix))]
-fn link_executable(_src: &Path, _dst: &Path) -> Result<()> {
+fn stage_link(_src: &Path, _dst: &Path) -> Result<PathBuf> {
     bail!("symlink activation is not supported on this platform")
 }
 
@@ -597,7 +1105,11 @@ fn collect_current_state_names(root: &Path) -> Result<Vec<String>> {
 }
 
 pub(crate) fn collect_managed_tool_names(home: &ToolHome) -> Result<Vec<String>> {
-    let mut names: HashSet<String> = collect_dir_names(&home.store_dir)?.into_iter().collect();
+    let mut names: HashSet<String> = if home.shares_store() {
+        HashSet::new()
+    } else {
+        collect_dir_names(&home.store_dir)?.into_iter().collect()
+    };
     for file in collect_current_state_names(&home.current_dir)? {
         names.insert(file);
     }
@@ -613,17 +1125,47 @@ fn is_current_state_file_name(name: &str) -> bool {
 }
 
 pub(crate) fn cleanup_legacy_current_dir_artifacts(home: &ToolHome) -> Result<()> {
-    let mut removed = 0usize;
-
-    removed += cleanup_legacy_files_in_dir(&home.current_dir)?;
-    removed += cleanup_legacy_files_in_dir(&home.self_update_backup_dir())?;
-
+    let removed = remove_legacy_current_dir_artifacts(home)?;
     if removed > 0 {
         eprintln!("🧹 Cleaned {removed} legacy tool state artifact(s)");
     }
     Ok(())
 }
 
+pub(super) fn remove_legacy_current_dir_artifacts(home: &ToolHome) -> Result<usize> {
+    Ok(cleanup_legacy_files_in_dir(&home.current_dir)?
+        + cleanup_legacy_files_in_dir(&home.self_update_backup_dir())?)
+}
+
+/// Legacy artifacts that `remove_legacy_current_dir_artifacts` would delete.
+pub(super) fn find_legacy_current_dir_artifacts(home: &ToolHome) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    for root in [home.current_dir.clone(), home.self_update_backup_dir()] {
+        let entries = match fs::read_dir(&root) {
+            Ok(entries) => entries,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied
+                ) =>
+            {
+                continue;
+            }
+            Err(err) => return Err(err).with_context(|| format!("read {}", root.display())),
+        };
+        for entry in entries {
+            let entry = entry?;
+            if entry.file_type()?.is_file()
+                && is_legacy_current_artifact_name(&entry.file_name().to_string_lossy())
+            {
+                found.push(entry.path());
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
 fn cleanup_legacy_files_in_dir(root: &Path) -> Result<usize> {
     if !root.exists() {
         return Ok(0);
@@ -0,0 +1,218 @@
+//! Platform targets used to pick release assets.
+
+use anyhow::{Result, bail};
+use std::{env, fmt};
+
+const SUPPORTED_TARGET_HINT: &str = "x86_64-unknown-linux-gnu, x86_64-unknown-linux-musl, aarch64-unknown-linux-gnu, aarch64-unknown-linux-musl, x86_64-apple-darwin, aarch64-apple-darwin, linux/amd64, linux/arm64";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ToolLibc {
+    Gnu,
+    Musl,
+}
+
+impl ToolLibc {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Gnu => "gnu",
+            Self::Musl => "musl",
+        }
+    }
+}
+
+/// Operating system, architecture and optional libc flavor of the machine a tool is installed for.
+///
+/// `os` and `arch` use the same spelling as `std::env::consts`. A `None` libc means any Linux
+/// asset is acceptable; the host target never pins one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ToolTarget {
+    pub(super) os: &'static str,
+    pub(super) arch: &'static str,
+    pub(super) libc: Option<ToolLibc>,
+}
+
+impl ToolTarget {
+    pub(super) fn host() -> Self {
+        Self {
+            os: env::consts::OS,
+            arch: env::consts::ARCH,
+            libc: None,
+        }
+    }
+
+    /// Parses a Rust target triple, a Docker platform (`linux/arm64`) or a za target label
+    /// (`aarch64-linux-musl`).
+    pub(super) fn parse(raw: &str) -> Result<Self> {
+        let normalized = raw.trim().to_ascii_lowercase();
+        let target = match normalized.as_str() {
+            "x86_64-unknown-linux-gnu" | "x86_64-linux-gnu" => {
+                Self::linux("x86_64", Some(ToolLibc::Gnu))
+            }
+            "x86_64-unknown-linux-musl" | "x86_64-linux-musl" => {
+                Self::linux("x86_64", Some(ToolLibc::Musl))
+            }
+            "aarch64-unknown-linux-gnu" | "aarch64-linux-gnu" => {
+                Self::linux("aarch64", Some(ToolLibc::Gnu))
+            }
+            "aarch64-unknown-linux-musl" | "aarch64-linux-musl" => {
+                Self::linux("aarch64", Some(ToolLibc::Musl))
+            }
+            "x86_64-linux" | "linux/amd64" | "linux/x86_64" => Self::linux("x86_64", None),
+            "aarch64-linux" | "linux/arm64" | "linux/aarch64" => Self::linux("aarch64", None),
+            "x86_64-apple-darwin" | "x86_64-macos" | "darwin/amd64" => Self {
+                os: "macos",
+                arch: "x86_64",
+                libc: None,
+            },
+            "aarch64-apple-darwin" | "aarch64-macos" | "darwin/arm64" => Self {
+                os: "macos",
+                arch: "aarch64",
+                libc: None,
+            },
+            _ => bail!("unsupported tool target `{raw}`; expected one of: {SUPPORTED_TARGET_HINT}"),
+        };
+        if target.os != env::consts::OS {
+            bail!(
+                "tool target `{raw}` is for {} but this host runs {}; only cross-architecture targets on the same OS are supported",
+                target.os,
+                env::consts::OS
+            );
+        }
+        Ok(target)
+    }
+
+    fn linux(arch: &'static str, libc: Option<ToolLibc>) -> Self {
+        Self {
+            os: "linux",
+            arch,
+            libc,
+        }
+    }
+
+    pub(super) fn is_host(self) -> bool {
+        self == Self::host()
+    }
+
+    /// Whether binaries built for this target execute here; a pinned libc still runs on the host.
+    pub(super) fn runs_on_host(self) -> bool {
+        let host = Self::host();
+        self.os == host.os && self.arch == host.arch
+    }
+
+    /// Key used by registry `targets` tables, e.g. `linux-aarch64`.
+    pub(super) fn platform_key(self) -> String {
+        format!("{}-{}", self.os, self.arch)
+    }
+
+    /// Default Rust target triple for this target; Linux prefers static musl builds.
+    pub(super) fn rust_triple(self) -> Result<String> {
+        let arch = self.arch;
+        match self.os {
+            "linux" => Ok(format!(
+                "{arch}-unknown-linux-{}",
+                self.libc.unwrap_or(ToolLibc::Musl).label()
+            )),
+            "macos" => Ok(format!("{arch}-apple-darwin")),
+            "windows" => Ok(format!("{arch}-pc-windows-msvc")),
+            os => bail!("no default target triple for {arch}-{os}"),
+        }
+    }
+
+    /// Picks the Linux asset variant matching the requested libc; an unpinned target prefers
+    /// the static musl build and falls back to gnu.
+    pub(super) fn linux_libc_asset(
+        self,
+        project_label: &str,
+        musl: Option<&'static str>,
+        gnu: Option<&'static str>,
+    ) -> Result<&'static str> {
+        let picked = match self.libc {
+            None => musl.or(gnu),
+            Some(ToolLibc::Musl) => musl,
+            Some(ToolLibc::Gnu) => gnu,
+        };
+        match (picked, self.libc) {
+            (Some(asset), _) => Ok(asset),
+            (None, Some(libc)) => {
+                bail!("no {} asset for {project_label} on {self}", libc.label())
+            }
+            (None, None) => bail!("no release asset for {project_label} on {self}"),
+        }
+    }
+
+    /// Rejects assets linked against the other libc when a target pins one explicitly.
+    pub(super) fn ensure_asset_compatible(
+        self,
+        project_label: &str,
+        asset_name: &str,
+    ) -> Result<()> {
+        let Some(libc) = self.libc else {
+            return Ok(());
+        };
+        let other = match libc {
+            ToolLibc::Gnu => ToolLibc::Musl,
+            ToolLibc::Musl => ToolLibc::Gnu,
+        };
+        if asset_name.contains(&format!("linux-{}", other.label())) {
+            bail!(
+                "no {} asset for {project_label}: the only release asset for {self} is `{asset_name}`",
+                libc.label()
+            );
+        }
+        Ok(())
+    }
+
+    /// Scores how well a release asset file name fits this target from its OS, architecture
+    /// and libc tokens; `None` means the asset is built for another platform.
+    pub(super) fn asset_name_score(self, asset_name: &str) -> Option<u32> {
+        let name = asset_name.to_ascii_lowercase();
+        let has = |token: &str| contains_name_token(&name, token);
+        let os_tokens: &[&str] = match self.os {
+            "linux" => &["linux"],
+            "macos" => &["darwin", "macos", "osx", "mac"],
+            "windows" => &["windows", "win64", "win"],
+            _ => &[],
+        };
+        if !os_tokens.iter().any(|token| has(token)) || has("android") || has("ios") {
+            return None;
+        }
+        let arch_tokens: &[&str] = match self.arch {
+            "x86_64" => &["x86_64", "x86-64", "amd64", "x64"],
+            "aarch64" => &["aarch64", "arm64"],
+            _ => &[],
+        };
+        let arch = if arch_tokens.iter().any(|token| has(token)) {
+            10
+        } else if self.os == "macos" && has("universal") {
+            5
+        } else {
+            return None;
+        };
+        let libc = match (self.libc, has("musl"), has("gnu") || has("glibc")) {
+            (Some(ToolLibc::Musl), _, true) => return None,
+            (Some(ToolLibc::Gnu), _, true) | (None | Some(ToolLibc::Musl), true, _) => 3,
+            (_, true, _) | (_, _, true) => 2,
+            _ => 1,
+        };
+        Some(arch + libc)
+    }
+}
+
+/// Whole-token match, so `win` does not match inside `darwin`.
+fn contains_name_token(name: &str, token: &str) -> bool {
+    name.match_indices(token).any(|(start, _)| {
+        let before = name[..start].chars().next_back();
+        let after = name[start + token.len()..].chars().next();
+        !before.is_some_and(|c| c.is_ascii_alphanumeric())
+            && !after.is_some_and(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+impl fmt::Display for ToolTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.libc {
+            Some(libc) => write!(f, "{}-{}-{}", self.arch, self.os, libc.label()),
+            None => write!(f, "{}-{}", self.arch, self.os),
+        }
+    }
+}
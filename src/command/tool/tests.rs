@@ -1,25 +1,44 @@
+use super::adhoc::{
+    AdhocToolSource, adhoc_source, parse_adhoc_repo, register_installed_adhoc_tools,
+    release_tag_prefix,
+};
+use super::admin::{AdminToolSource, parse_admin_policy, resolution_requirement};
+use super::cache::{
+    CachePrunePolicy, CachedArtifact, CachedRelease, PartialDownload, PartialDownloadMeta,
+    contains_artifact, find_cached_release, list_cache_entries, record_cached_release,
+    restore_cached_artifact, select_prunable_entries, store_artifact,
+};
 use super::integrations::{
-    blesh_bash_init_bottom_block, blesh_bash_init_top_block, ide_terminal_bash_helper_block,
+    ide_terminal_bash_helper_block, shell_init_block, shell_init_markers,
+    shell_init_snippet_markers,
+};
+use super::policy::{
+    EntryPlacement, IntegrationShell, ReleaseBackend, ReleaseVerification, ShellInitPosition,
+    ShellInitSnippet, ToolLayout, register_adhoc_policy, tool_policies,
+};
+use super::registry::{ToolRegistryOrigin, parse_tool_registries};
+use super::state::sha256_file;
+use super::target::{ToolLibc, ToolTarget};
+use super::verify::{
+    MinisignPublicKey, blake2b512_file, find_checksum_asset_name, parse_checksum_file,
+    verify_minisign,
 };
-use super::policy::GithubReleaseVerification;
 use super::{
-    BLESH_BASH_INIT_BOTTOM_END_MARKER, BLESH_BASH_INIT_BOTTOM_START_MARKER,
-    BLESH_BASH_INIT_TOP_END_MARKER, BLESH_BASH_INIT_TOP_START_MARKER, BatchProgressStatus,
-    IDE_TERMINAL_BASH_HELPER_END_MARKER, IDE_TERMINAL_BASH_HELPER_START_MARKER, InstallOutcome,
-    LatestCheck, LatestResolutionMode, ManagedBlockPosition, ManagedFileChange,
-    STARSHIP_BASH_INIT_END_MARKER, STARSHIP_BASH_INIT_START_MARKER, TOOL_UPDATE_CACHE_TTL_SECS,
-    ToolBatchKind, ToolBatchSummary, ToolHome, ToolRef, ToolScope, ToolScopeRequest, ToolSpec,
-    ToolUpdateChannel, canonical_tool_name, classify_tool_executable_scope,
-    cleanup_legacy_current_dir_artifacts, collect_managed_tool_names, command_candidates,
-    compact_install_plan, extract_version_from_text, find_tool_policy,
+    BLESH_BASH_INIT_BOTTOM_START_MARKER, BatchProgressStatus, IDE_TERMINAL_BASH_HELPER_END_MARKER,
+    IDE_TERMINAL_BASH_HELPER_START_MARKER, InstallOutcome, LatestCheck, LatestResolutionMode,
+    ManagedBlockPosition, ManagedFileChange, TOOL_UPDATE_CACHE_TTL_SECS, ToolBatchKind,
+    ToolBatchSummary, ToolHome, ToolRef, ToolScope, ToolScopeRequest, ToolSpec, ToolUpdateChannel,
+    canonical_tool_name, classify_tool_executable_scope, cleanup_legacy_current_dir_artifacts,
+    collect_managed_tool_names, command_candidates, compact_install_plan,
+    extract_version_from_text, find_stored_executable, find_tool_policy,
     latest_check_progress_message, latest_resolution_mode_for_batch, list_update_status,
-    load_sync_specs_from_manifest, normalize_requested_tool_names, normalize_version,
+    load_sync_entries_from_manifest, normalize_requested_tool_names, normalize_version,
     prune_non_active_versions, render_batch_progress_header, render_batch_progress_line,
     render_batch_summary, resolve_update_channel_request, should_parallel_materialize_batch,
-    source, split_supported_managed_tool_names, starship_bash_init_block, supported_tool_names_csv,
-    tool_update_cache_entry_is_fresh, unsupported_tool_message, upsert_managed_block,
+    source, split_supported_managed_tool_names, supported_tool_names_csv,
+    tool_update_cache_entry_is_fresh, uninstall, unsupported_tool_message, upsert_managed_block,
 };
-use std::{fs, time::Duration};
+use std::{fs, path::Path, time::Duration};
 
 #[test]
 fn parse_tool_ref_ok() {
@@ -304,7 +323,11 @@ tools = [
     )
     .expect("write manifest");
 
-    let specs = load_sync_specs_from_manifest(&manifest_path).expect("parse manifest");
+    let specs = load_sync_entries_from_manifest(&manifest_path)
+        .expect("parse manifest")
+        .into_iter()
+        .map(|entry| entry.spec)
+        .collect::<Vec<_>>();
     assert_eq!(specs, vec!["codex", "rg", "docker-compose:5.1.0"]);
 
     let _ = fs::remove_dir_all(&root);
@@ -324,7 +347,7 @@ fn load_sync_specs_rejects_empty_tools() {
     let manifest_path = root.join("za.tools.toml");
     fs::write(&manifest_path, "tools = []\n").expect("write manifest");
 
-    let err = load_sync_specs_from_manifest(&manifest_path).expect_err("must fail");
+    let err = load_sync_entries_from_manifest(&manifest_path).expect_err("must fail");
     assert!(err.to_string().contains("has no tools"));
 
     let _ = fs::remove_dir_all(&root);
@@ -593,8 +616,8 @@ fn tool_policy_matches_alias_and_canonical() {
     assert_eq!(motdyn.canonical_name, "motdyn");
     assert_eq!(motdyn.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        motdyn.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        motdyn.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let bottom_alias = find_tool_policy("bottom").expect("alias policy");
     let bottom = find_tool_policy("btm").expect("canonical policy");
@@ -602,25 +625,22 @@ fn tool_policy_matches_alias_and_canonical() {
     assert_eq!(bottom.canonical_name, "btm");
     assert_eq!(bottom.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        bottom.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        bottom.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let bpftop = find_tool_policy("bpftop").expect("canonical policy");
     assert_eq!(bpftop.canonical_name, "bpftop");
     assert_eq!(bpftop.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        bpftop.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        bpftop.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let hyperfine = find_tool_policy("hyperfine").expect("canonical policy");
     assert_eq!(hyperfine.canonical_name, "hyperfine");
     assert_eq!(hyperfine.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        hyperfine
-            .github_release
-            .expect("github policy")
-            .verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        hyperfine.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let dust = find_tool_policy("dust").expect("canonical policy");
     assert_eq!(dust.canonical_name, "dust");
@@ -630,18 +650,15 @@ fn tool_policy_matches_alias_and_canonical() {
     assert_eq!(actionlint.canonical_name, "actionlint");
     assert_eq!(actionlint.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        actionlint
-            .github_release
-            .expect("github policy")
-            .verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        actionlint.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let sccache = find_tool_policy("sccache").expect("canonical policy");
     assert_eq!(sccache.canonical_name, "sccache");
     assert_eq!(sccache.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        sccache.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        sccache.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let protobuf_alias = find_tool_policy("protobuf").expect("alias policy");
     let protoc = find_tool_policy("protoc").expect("canonical policy");
@@ -649,25 +666,22 @@ fn tool_policy_matches_alias_and_canonical() {
     assert_eq!(protoc.canonical_name, "protoc");
     assert_eq!(protoc.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        protoc.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        protoc.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let starship = find_tool_policy("starship").expect("canonical policy");
     assert_eq!(starship.canonical_name, "starship");
     assert_eq!(starship.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        starship.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        starship.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let git_cliff = find_tool_policy("git-cliff").expect("canonical policy");
     assert_eq!(git_cliff.canonical_name, "git-cliff");
     assert_eq!(git_cliff.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        git_cliff
-            .github_release
-            .expect("github policy")
-            .verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        git_cliff.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let cargo_release = find_tool_policy("cargo-release").expect("canonical policy");
     assert_eq!(cargo_release.canonical_name, "cargo-release");
@@ -676,28 +690,22 @@ fn tool_policy_matches_alias_and_canonical() {
         "GitHub Release (SHA-256 verified)"
     );
     assert_eq!(
-        cargo_release
-            .github_release
-            .expect("github policy")
-            .verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        cargo_release.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let nextest = find_tool_policy("cargo-nextest").expect("canonical policy");
     assert_eq!(nextest.canonical_name, "cargo-nextest");
     assert_eq!(nextest.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        nextest.github_release.expect("github policy").verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        nextest.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let cargo_fuzz = find_tool_policy("cargo-fuzz").expect("canonical policy");
     assert_eq!(cargo_fuzz.canonical_name, "cargo-fuzz");
     assert_eq!(cargo_fuzz.source_label, "GitHub Release (SHA-256 verified)");
     assert_eq!(
-        cargo_fuzz
-            .github_release
-            .expect("github policy")
-            .verification,
-        GithubReleaseVerification::RequiredSha256Digest
+        cargo_fuzz.release.expect("release policy").verification,
+        ReleaseVerification::RequiredSha256Digest
     );
     let cross = find_tool_policy("cross").expect("canonical policy");
     assert_eq!(cross.canonical_name, "cross");
@@ -706,8 +714,8 @@ fn tool_policy_matches_alias_and_canonical() {
         "GitHub Release (SHA-256 unavailable; unverified)"
     );
     assert_eq!(
-        cross.github_release.expect("github policy").verification,
-        GithubReleaseVerification::NoSha256Digest
+        cross.release.expect("release policy").verification,
+        ReleaseVerification::NoSha256Digest
     );
     let blesh_alias = find_tool_policy("blesh").expect("alias policy");
     let blesh = find_tool_policy("ble.sh").expect("canonical policy");
@@ -718,8 +726,8 @@ fn tool_policy_matches_alias_and_canonical() {
         "GitHub nightly rolling release (commit-tracked; SHA-256 unavailable)"
     );
     assert_eq!(
-        blesh.github_release.expect("github policy").verification,
-        GithubReleaseVerification::NoSha256Digest
+        blesh.release.expect("release policy").verification,
+        ReleaseVerification::NoSha256Digest
     );
     assert!(find_tool_policy("unknown-tool").is_none());
 }
@@ -822,6 +830,97 @@ fn ide_terminal_bash_helper_supports_jetbrains_and_zed() {
     assert!(block.contains("_za_is_supported_ide_terminal()"));
 }
 
+const STARSHIP_BASH_INIT_START_MARKER: &str = "# >>> za starship (bash) >>>";
+const STARSHIP_BASH_INIT_END_MARKER: &str = "# <<< za starship (bash) <<<";
+const BLESH_BASH_INIT_TOP_START_MARKER: &str = "# >>> za ble.sh (bash top) >>>";
+const BLESH_BASH_INIT_TOP_END_MARKER: &str = "# <<< za ble.sh (bash top) <<<";
+const BLESH_BASH_INIT_BOTTOM_END_MARKER: &str = "# <<< za ble.sh (bash bottom) <<<";
+
+fn declared_snippet(
+    name: &str,
+    shell: IntegrationShell,
+    part: Option<&str>,
+) -> &'static ShellInitSnippet {
+    find_tool_policy(name)
+        .expect("catalog tool")
+        .shell_init
+        .iter()
+        .find(|snippet| snippet.shell == shell && snippet.part == part)
+        .expect("declared snippet")
+}
+
+fn starship_bash_init_block(path: &Path) -> String {
+    shell_init_block(
+        declared_snippet("starship", IntegrationShell::Bash, None),
+        path,
+    )
+}
+
+fn blesh_bash_init_top_block(path: &Path) -> String {
+    shell_init_block(
+        declared_snippet("ble.sh", IntegrationShell::Bash, Some("top")),
+        path,
+    )
+}
+
+fn blesh_bash_init_bottom_block() -> String {
+    shell_init_block(
+        declared_snippet("ble.sh", IntegrationShell::Bash, Some("bottom")),
+        Path::new("/tmp/blesh/ble.sh"),
+    )
+}
+
+#[test]
+fn starship_and_blesh_shell_init_come_from_catalog_declarations() {
+    let starship = find_tool_policy("starship").expect("starship policy");
+    let shells = starship
+        .shell_init
+        .iter()
+        .map(|snippet| snippet.shell)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        shells,
+        vec![
+            IntegrationShell::Bash,
+            IntegrationShell::Zsh,
+            IntegrationShell::Fish
+        ]
+    );
+    let starship_bash = declared_snippet("starship", IntegrationShell::Bash, None);
+    assert_eq!(starship_bash.position, ShellInitPosition::BeforeBleshAttach);
+    assert_eq!(
+        shell_init_snippet_markers("starship", starship_bash),
+        (
+            STARSHIP_BASH_INIT_START_MARKER.to_string(),
+            STARSHIP_BASH_INIT_END_MARKER.to_string()
+        )
+    );
+
+    let top = declared_snippet("ble.sh", IntegrationShell::Bash, Some("top"));
+    assert_eq!(top.position, ShellInitPosition::AfterIdeTerminalHelper);
+    assert_eq!(
+        shell_init_snippet_markers("ble.sh", top),
+        (
+            BLESH_BASH_INIT_TOP_START_MARKER.to_string(),
+            BLESH_BASH_INIT_TOP_END_MARKER.to_string()
+        )
+    );
+    let bottom = declared_snippet("ble.sh", IntegrationShell::Bash, Some("bottom"));
+    assert_eq!(
+        shell_init_snippet_markers("ble.sh", bottom),
+        (
+            BLESH_BASH_INIT_BOTTOM_START_MARKER.to_string(),
+            BLESH_BASH_INIT_BOTTOM_END_MARKER.to_string()
+        )
+    );
+
+    let zsh = shell_init_block(
+        declared_snippet("starship", IntegrationShell::Zsh, None),
+        Path::new("/tmp/starship"),
+    );
+    assert!(zsh.contains(r#"eval "$('/tmp/starship' init zsh)""#));
+}
+
 #[test]
 fn starship_bash_init_block_uses_supported_ide_terminal_helper() {
     let block = starship_bash_init_block(std::path::Path::new("/home/alice/.local/bin/starship"));
@@ -1188,10 +1287,13 @@ legacy starship block
 fn starship_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("starship")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("1.24.2").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("1.24.2", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1206,10 +1308,13 @@ fn starship_policy_expected_asset_name_matches_supported_tarball() {
 fn sccache_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("sccache")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("0.15.0").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("0.15.0", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1227,10 +1332,13 @@ fn sccache_policy_expected_asset_name_matches_supported_tarball() {
 fn protoc_policy_expected_asset_name_matches_supported_zip() {
     let policy = find_tool_policy("protoc")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("35.1").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("35.1", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "linux-x86_64",
         ("linux", "aarch64") => "linux-aarch_64",
@@ -1247,10 +1355,13 @@ fn protoc_policy_expected_asset_name_matches_supported_zip() {
 fn actionlint_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("actionlint")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("1.7.12").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("1.7.12", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "linux_amd64",
         ("linux", "aarch64") => "linux_arm64",
@@ -1268,10 +1379,13 @@ fn actionlint_policy_expected_asset_name_matches_supported_tarball() {
 fn git_cliff_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("git-cliff")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("2.12.0").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("2.12.0", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1289,10 +1403,13 @@ fn git_cliff_policy_expected_asset_name_matches_supported_tarball() {
 fn cargo_release_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("cargo-release")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("1.1.1").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("1.1.1", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1310,10 +1427,13 @@ fn cargo_release_policy_expected_asset_name_matches_supported_tarball() {
 fn nextest_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("cargo-nextest")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("0.9.132").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("0.9.132", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1333,10 +1453,13 @@ fn nextest_policy_expected_asset_name_matches_supported_tarball() {
 fn cargo_fuzz_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("cargo-fuzz")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("0.13.1").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("0.13.1", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("macos", "x86_64") => "x86_64-apple-darwin",
@@ -1352,10 +1475,13 @@ fn cargo_fuzz_policy_expected_asset_name_matches_supported_tarball() {
 fn motdyn_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("motdyn")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("1.0.8").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("1.0.8", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1368,10 +1494,13 @@ fn motdyn_policy_expected_asset_name_matches_supported_tarball() {
 fn bottom_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("btm")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("0.12.3").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("0.12.3", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-musl",
@@ -1386,10 +1515,13 @@ fn bottom_policy_expected_asset_name_matches_supported_tarball() {
 fn bpftop_policy_expected_asset_name_matches_supported_binary() {
     let policy = find_tool_policy("bpftop")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("0.7.1").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("0.7.1", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
         ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
@@ -1402,10 +1534,13 @@ fn bpftop_policy_expected_asset_name_matches_supported_binary() {
 fn hyperfine_policy_expected_asset_name_matches_supported_tarball() {
     let policy = find_tool_policy("hyperfine")
         .expect("policy")
-        .github_release
-        .expect("github policy");
-    let asset_name =
-        (policy.expected_asset_name.expect("asset resolver"))("1.20.0").expect("asset name");
+        .release
+        .expect("release policy");
+    let asset_name = policy
+        .expected_asset_name
+        .expect("asset resolver")
+        .resolve("1.20.0", ToolTarget::host())
+        .expect("asset name");
     let expected_target = match (std::env::consts::OS, std::env::consts::ARCH) {
         ("linux", "x86_64") => "x86_64-unknown-linux-musl",
         ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
@@ -1448,7 +1583,7 @@ fn prune_non_active_versions_keeps_only_target_version() {
     fs::create_dir_all(home.version_dir(&old)).expect("create old version dir");
     fs::create_dir_all(home.version_dir(&active)).expect("create active version dir");
 
-    let removed = prune_non_active_versions(&home, &active).expect("prune versions");
+    let removed = prune_non_active_versions(&home, &active, 1).expect("prune versions");
     assert_eq!(removed, vec!["0.104.0".to_string()]);
     assert!(!home.version_dir(&old).exists());
     assert!(home.version_dir(&active).exists());
@@ -1544,6 +1679,47 @@ fn collect_managed_tool_names_ignores_legacy_current_artifacts() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn project_scope_uninstall_only_unpins_from_the_shared_store() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-project-scope-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::Project,
+        store_dir: root.join("shared-store"),
+        current_dir: root.join("project/.za/tools/current"),
+        bin_dir: root.join("project/.za/bin"),
+    };
+    let tool = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+
+    fs::create_dir_all(&home.current_dir).expect("create current dir");
+    fs::create_dir_all(home.version_dir(&tool)).expect("create shared version dir");
+    fs::create_dir_all(home.name_dir("codex")).expect("create unpinned shared dir");
+    fs::write(home.current_file("rg"), "14.1.0\n").expect("write current version");
+
+    let names = collect_managed_tool_names(&home).expect("collect names");
+    assert_eq!(names, vec!["rg".to_string()]);
+
+    uninstall(&home, ToolSpec::parse("rg").expect("parse spec")).expect("unpin rg");
+    assert!(!home.current_file("rg").exists());
+    assert!(home.version_dir(&tool).exists());
+    assert!(
+        collect_managed_tool_names(&home)
+            .expect("collect names")
+            .is_empty()
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn cleanup_legacy_current_dir_artifacts_removes_backup_and_temp_files() {
     let root = std::env::temp_dir().join(format!(
@@ -1581,3 +1757,2614 @@ fn cleanup_legacy_current_dir_artifacts_removes_backup_and_temp_files() {
 
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn doctor_fix_relinks_regenerates_manifest_and_removes_orphaned_versions() {
+    use super::doctor::{DoctorFixMode, fix_tool};
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-doctor-fix-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let active = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+    let retained = ToolRef {
+        name: "rg".to_string(),
+        version: "13.0.0".to_string(),
+    };
+    let orphaned = ToolRef {
+        name: "rg".to_string(),
+        version: "12.0.0".to_string(),
+    };
+    for tool in [&active, &retained] {
+        fs::create_dir_all(home.version_dir(tool)).expect("create version dir");
+        fs::write(home.install_path(tool), "#!/bin/sh\n").expect("write binary");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(home.install_path(tool), fs::Permissions::from_mode(0o755))
+                .expect("chmod binary");
+        }
+    }
+    fs::create_dir_all(home.version_dir(&orphaned)).expect("create orphaned dir");
+    fs::create_dir_all(&home.current_dir).expect("create current dir");
+    fs::write(home.current_file("rg"), "14.1.0\n").expect("write current version");
+
+    let planned = fix_tool(&home, "rg", DoctorFixMode::DryRun).expect("plan fixes");
+    assert_eq!(planned.len(), 3, "{planned:?}");
+    assert!(planned.iter().all(|change| change.starts_with("would ")));
+    assert!(!home.bin_path("rg").exists());
+    assert!(!home.manifest_path(&active).exists());
+    assert!(home.version_dir(&orphaned).exists());
+
+    let applied = fix_tool(&home, "rg", DoctorFixMode::Apply).expect("apply fixes");
+    assert_eq!(applied.len(), 3, "{applied:?}");
+    assert!(applied[0].starts_with("re-linked active entry"));
+    assert!(applied[1].starts_with("regenerated manifest"));
+    assert!(applied[2].starts_with("removed orphaned version dir"));
+    assert!(home.bin_path("rg").exists());
+    assert!(home.manifest_path(&active).exists());
+    assert!(home.install_path(&retained).exists());
+    assert!(!home.version_dir(&orphaned).exists());
+
+    assert!(
+        fix_tool(&home, "rg", DoctorFixMode::Apply)
+            .expect("rerun fixes")
+            .is_empty()
+    );
+
+    let asset = super::ReleaseAsset {
+        url: "https://example.com/ripgrep-14.1.0.tar.gz".to_string(),
+        sha256: "ab".repeat(32),
+    };
+    super::state::write_manifest(
+        &home,
+        &retained,
+        &super::InstallSource {
+            kind: "download",
+            detail: "test".to_string(),
+            asset: Some(asset.clone()),
+            unverified: false,
+        },
+        Some(ToolTarget::host()),
+        None,
+    )
+    .expect("write mismatched manifest");
+    fs::rename(home.manifest_path(&retained), home.manifest_path(&active))
+        .expect("move manifest under the active version");
+    let applied = fix_tool(&home, "rg", DoctorFixMode::Apply).expect("repair manifest");
+    assert_eq!(applied.len(), 1, "{applied:?}");
+    assert!(applied[0].starts_with("repaired manifest"));
+    let repaired = super::state::read_manifest(&home, &active)
+        .expect("read manifest")
+        .expect("manifest exists");
+    assert_eq!(repaired.version, "14.1.0");
+    assert_eq!(repaired.asset_url.as_deref(), Some(asset.url.as_str()));
+    assert_eq!(
+        repaired.asset_sha256.as_deref(),
+        Some(asset.sha256.as_str())
+    );
+    assert_eq!(repaired.target, Some(ToolTarget::host().to_string()));
+
+    fs::write(home.manifest_path(&active), "{ not json").expect("corrupt manifest");
+    assert!(
+        fix_tool(&home, "rg", DoctorFixMode::Apply)
+            .expect("plan fixes for corrupt manifest")
+            .is_empty()
+    );
+    assert_eq!(
+        fs::read_to_string(home.manifest_path(&active)).expect("read manifest"),
+        "{ not json"
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn archived_completions_are_staged_into_the_version_dir() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-stage-completions-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let tool = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+    let download_root = root.join("download");
+    let unpack = download_root
+        .join("unpack")
+        .join("ripgrep-14.1.0-x86_64-unknown-linux-musl");
+    fs::create_dir_all(unpack.join("complete")).expect("create unpack dir");
+    fs::write(unpack.join("rg"), "#!/bin/sh\n").expect("write binary");
+    fs::write(
+        unpack.join("complete").join("rg.bash"),
+        "complete -F _rg rg\n",
+    )
+    .expect("write bash completion");
+    fs::write(unpack.join("complete").join("_rg"), "#compdef rg\n").expect("write zsh completion");
+    fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+
+    let pulled = super::PullSource::temp(
+        "download",
+        super::PullArtifactKind::File,
+        unpack.join("rg"),
+        "test".to_string(),
+        download_root.clone(),
+    );
+    super::completions::stage_archived_completions(&home, &tool, &pulled)
+        .expect("stage completions");
+    drop(pulled);
+
+    let staged = home.version_dir(&tool).join("completions");
+    assert_eq!(
+        fs::read_to_string(staged.join("rg.bash")).expect("staged bash completion"),
+        "complete -F _rg rg\n"
+    );
+    assert!(staged.join("_rg").exists());
+    assert!(!staged.join("rg.fish").exists());
+    assert!(!download_root.exists());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn stored_versions_are_found_in_store_order_without_an_active_pointer() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-stored-executable-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let shared = root.join("shared-store");
+    let user = root.join("user-store");
+    let stores = [shared.clone(), user.clone()];
+    for store in &stores {
+        fs::create_dir_all(store.join("rg").join("13.0.0")).expect("create version dir");
+    }
+    fs::write(user.join("rg").join("13.0.0").join("rg"), b"user").expect("write user binary");
+
+    assert_eq!(
+        find_stored_executable(&stores, "rg", "v13.0.0"),
+        Some(user.join("rg").join("13.0.0").join("rg"))
+    );
+    fs::write(shared.join("rg").join("13.0.0").join("rg"), b"shared").expect("write shared binary");
+    assert_eq!(
+        find_stored_executable(&stores, "rg", "13.0.0"),
+        Some(shared.join("rg").join("13.0.0").join("rg"))
+    );
+    assert_eq!(find_stored_executable(&stores, "rg", "14.1.0"), None);
+
+    let foreign_arch = if std::env::consts::ARCH == "x86_64" {
+        "aarch64"
+    } else {
+        "x86_64"
+    };
+    fs::write(
+        shared.join("rg").join("13.0.0").join("manifest.json"),
+        format!(
+            r#"{{"schema_version":1,"name":"rg","version":"13.0.0","installed_at_unix_secs":0,"source_kind":"download","source_detail":"test","sha256":"","size_bytes":6,"target":"{foreign_arch}-{}"}}"#,
+            std::env::consts::OS
+        ),
+    )
+    .expect("write foreign manifest");
+    assert_eq!(
+        find_stored_executable(&stores, "rg", "13.0.0"),
+        Some(user.join("rg").join("13.0.0").join("rg")),
+        "foreign-target store entries must not be executed"
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_registry_entries_resolve_templated_assets_and_aliases() {
+    let raw = r#"
+[tools.kubectx]
+owner = "ahmetb"
+repo = "kubectx"
+tag_prefix = "v"
+asset = "kubectx_v{version}_{os}_{target}.tar.gz"
+aliases = ["kctx"]
+targets = { linux-x86_64 = "x86_64", linux-aarch64 = "arm64", macos-x86_64 = "x86_64", macos-aarch64 = "arm64" }
+
+[tools.helix]
+owner = "helix-editor"
+repo = "helix"
+asset = "helix-{version}-{arch}-{os}.tar.xz"
+layout = "package"
+entry = "hx"
+verification = "none"
+"#;
+    let policies = parse_tool_registries(
+        &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    assert_eq!(policies.len(), 2);
+
+    let helix = policies[0];
+    assert_eq!(helix.canonical_name, "helix");
+    assert_eq!(helix.layout, ToolLayout::Package);
+    assert_eq!(helix.package.expect("package policy").entry_relpath, "hx");
+    assert_eq!(
+        helix.source_label,
+        "GitHub Release (SHA-256 unavailable; unverified; user registry)"
+    );
+    let release = helix.release.expect("release policy");
+    assert_eq!(release.verification, ReleaseVerification::NoSha256Digest);
+    assert_eq!(
+        release
+            .expected_asset_name
+            .expect("asset resolver")
+            .resolve("25.07.1", ToolTarget::host())
+            .expect("asset name"),
+        format!(
+            "helix-25.07.1-{}-{}.tar.xz",
+            std::env::consts::ARCH,
+            std::env::consts::OS
+        )
+    );
+
+    let kubectx = policies[1];
+    assert!(kubectx.matches("kctx"));
+    let release = kubectx.release.expect("release policy");
+    assert_eq!(release.tag_prefix, "v");
+    assert_eq!(
+        release.verification,
+        ReleaseVerification::RequiredSha256Digest
+    );
+    let expected_target = match std::env::consts::ARCH {
+        "x86_64" => "x86_64",
+        "aarch64" => "arm64",
+        other => panic!("unsupported local test platform: {other}"),
+    };
+    assert_eq!(
+        release
+            .expected_asset_name
+            .expect("asset resolver")
+            .resolve("0.9.5", ToolTarget::host())
+            .expect("asset name"),
+        format!(
+            "kubectx_v0.9.5_{}_{expected_target}.tar.gz",
+            std::env::consts::OS
+        )
+    );
+}
+
+#[test]
+fn tool_registry_entries_accept_checksum_file_and_minisign_verification() {
+    let raw = r#"
+[tools.kubeconform]
+owner = "yannh"
+repo = "kubeconform"
+asset = "kubeconform-{os}-{target}.tar.gz"
+verification = "checksum-file"
+
+[tools.zig]
+owner = "ziglang"
+repo = "zig"
+asset = "zig-{arch}-{os}-{version}.tar.xz"
+layout = "package"
+entry = "zig"
+verification = "minisign"
+minisign_key = "RWSGOq2NVecA2UPNdBUZykf1CCb147pkmdtYxgb3Ti+JO/wCYvhbAb/U"
+"#;
+    let policies = parse_tool_registries(
+        &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    assert_eq!(policies.len(), 2);
+    assert_eq!(
+        policies[0].source_label,
+        "GitHub Release (checksum file verified; user registry)"
+    );
+    assert_eq!(
+        policies[0].release.expect("release policy").verification,
+        ReleaseVerification::ChecksumFile
+    );
+    assert_eq!(
+        policies[1].source_label,
+        "GitHub Release (minisign verified; user registry)"
+    );
+    assert_eq!(
+        policies[1].release.expect("release policy").verification,
+        ReleaseVerification::Minisign {
+            public_key: "RWSGOq2NVecA2UPNdBUZykf1CCb147pkmdtYxgb3Ti+JO/wCYvhbAb/U"
+        }
+    );
+
+    let cases = [
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+verification = "minisign"
+"#,
+            "requires `minisign_key`",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+minisign_key = "RWSGOq2NVecA2UPNdBUZykf1CCb147pkmdtYxgb3Ti+JO/wCYvhbAb/U"
+"#,
+            "only valid with `verification = \"minisign\"`",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+verification = "minisign"
+minisign_key = "not-a-key"
+"#,
+            "invalid `minisign_key`",
+        ),
+    ];
+    for (raw, expected) in cases {
+        let err = parse_tool_registries(
+            &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+            tool_policies(),
+        )
+        .expect_err("invalid registry");
+        assert!(
+            format!("{err:#}").contains(expected),
+            "expected `{expected}` in `{err:#}`"
+        );
+    }
+}
+
+#[test]
+fn tool_registry_shell_init_renders_managed_blocks_per_shell() {
+    let raw = r#"
+[tools.direnv]
+owner = "direnv"
+repo = "direnv"
+asset = "direnv.{os}-{arch}"
+
+[tools.direnv.shell_init]
+bash = 'eval "$({path} hook bash)"'
+fish = """
+{path} hook fish | source
+"""
+"#;
+    let policies = parse_tool_registries(
+        &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    let shell_init = policies[0].shell_init;
+    assert_eq!(
+        shell_init
+            .iter()
+            .map(|snippet| snippet.shell)
+            .collect::<Vec<_>>(),
+        vec![IntegrationShell::Bash, IntegrationShell::Fish]
+    );
+    assert_eq!(
+        shell_init_block(&shell_init[0], Path::new("/tmp/a'b/direnv")),
+        r#"eval "$('/tmp/a'"'"'b/direnv' hook bash)""#
+    );
+    assert_eq!(
+        shell_init_block(&shell_init[1], Path::new("/opt/bin/direnv")),
+        "'/opt/bin/direnv' hook fish | source"
+    );
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-shell-init-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let rc_path = root.join("conf.d").join("za-direnv.fish");
+    let (start, end) = shell_init_markers("direnv", IntegrationShell::Fish);
+    assert_eq!(start, "# >>> za direnv (fish) >>>");
+    let change = upsert_managed_block(
+        &rc_path,
+        &start,
+        &end,
+        ManagedBlockPosition::Bottom,
+        &shell_init_block(&shell_init[1], Path::new("/opt/bin/direnv")),
+    )
+    .expect("write fish init");
+    assert!(matches!(change, ManagedFileChange::Created));
+    let content = fs::read_to_string(&rc_path).expect("read fish init");
+    assert!(content.starts_with(&start));
+    assert!(content.trim_end().ends_with(&end));
+
+    let err = parse_tool_registries(
+        &[(
+            ToolRegistryOrigin::User,
+            Path::new("tools.toml"),
+            r##"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+shell_init = { zsh = "# >>> za foo (zsh) >>>" }
+"##,
+        )],
+        tool_policies(),
+    )
+    .expect_err("marker in snippet");
+    assert!(format!("{err:#}").contains("managed block markers"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_registry_entries_select_gitlab_and_gitea_backends() {
+    let raw = r#"
+[tools.teamcli]
+forge = "gitlab"
+base_url = "https://gitlab.example.com/"
+owner = "platform/tools"
+repo = "teamcli"
+asset = "teamcli-{version}-{target}.tar.gz"
+
+[tools.forgecli]
+forge = "forgejo"
+base_url = "https://code.example.com"
+owner = "platform"
+repo = "forgecli"
+asset = "forgecli-{version}.tar.gz"
+verification = "none"
+
+[tools.publiccli]
+forge = "gitlab"
+owner = "group"
+repo = "publiccli"
+asset = "publiccli-{version}.tar.gz"
+"#;
+    let policies = parse_tool_registries(
+        &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    let find = |name: &str| {
+        policies
+            .iter()
+            .find(|policy| policy.canonical_name == name)
+            .copied()
+            .expect("registered policy")
+    };
+
+    let teamcli = find("teamcli");
+    assert_eq!(
+        teamcli.source_label,
+        "GitLab Release (checksum file verified; user registry)"
+    );
+    let release = teamcli.release.expect("release policy");
+    assert_eq!(
+        release.backend,
+        ReleaseBackend::Gitlab {
+            base_url: "https://gitlab.example.com"
+        }
+    );
+    assert_eq!(release.verification, ReleaseVerification::ChecksumFile);
+
+    let forgecli = find("forgecli");
+    assert_eq!(
+        forgecli.release.expect("release policy").backend,
+        ReleaseBackend::Gitea {
+            base_url: "https://code.example.com"
+        }
+    );
+    assert_eq!(
+        forgecli.source_label,
+        "Gitea Release (SHA-256 unavailable; unverified; user registry)"
+    );
+    assert_eq!(
+        find("publiccli").release.expect("release policy").backend,
+        ReleaseBackend::Gitlab {
+            base_url: "https://gitlab.com"
+        }
+    );
+
+    let cases = [
+        (
+            r#"
+[tools.foo]
+forge = "gitea"
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+"#,
+            "requires `base_url`",
+        ),
+        (
+            r#"
+[tools.foo]
+base_url = "https://git.example.com"
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+"#,
+            "`base_url` is only valid",
+        ),
+        (
+            r#"
+[tools.foo]
+forge = "gitlab"
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+verification = "sha256-digest"
+"#,
+            "GitLab releases do not publish asset digests",
+        ),
+        (
+            r#"
+[tools.foo]
+forge = "gitea"
+base_url = "git.example.com"
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+"#,
+            "must be an http(s) URL",
+        ),
+    ];
+    for (raw, expected) in cases {
+        let err = parse_tool_registries(
+            &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+            tool_policies(),
+        )
+        .expect_err("invalid registry");
+        assert!(
+            format!("{err:#}").contains(expected),
+            "expected `{expected}` in `{err:#}`"
+        );
+    }
+}
+
+#[test]
+fn checksum_asset_lookup_prefers_per_asset_files_over_release_manifests() {
+    let assets = [
+        "tool-x86_64.tar.gz",
+        "tool_1.0.0_checksums.txt",
+        "SHA256SUMS",
+        "tool-x86_64.tar.gz.sha256",
+    ];
+    assert_eq!(
+        find_checksum_asset_name("tool-x86_64.tar.gz", assets),
+        Some("tool-x86_64.tar.gz.sha256")
+    );
+    assert_eq!(
+        find_checksum_asset_name("tool-aarch64.tar.gz", assets),
+        Some("SHA256SUMS")
+    );
+    assert_eq!(
+        find_checksum_asset_name("tool-aarch64.tar.gz", ["tool_1.0.0_checksums.txt"]),
+        Some("tool_1.0.0_checksums.txt")
+    );
+    assert_eq!(
+        find_checksum_asset_name("tool-aarch64.tar.gz", ["tool-aarch64.tar.gz"]),
+        None
+    );
+}
+
+#[test]
+fn checksum_files_parse_gnu_bsd_and_single_digest_formats() {
+    let a = "a".repeat(64);
+    let b = "B".repeat(64);
+    let gnu = format!("{a}  tool-x86_64.tar.gz\n{b} *./tool-aarch64.tar.gz\n");
+    assert_eq!(
+        parse_checksum_file(&gnu, "tool-aarch64.tar.gz").expect("gnu entry"),
+        "b".repeat(64)
+    );
+    let bsd = format!("SHA256 (tool-x86_64.tar.gz) = {a}\n");
+    assert_eq!(
+        parse_checksum_file(&bsd, "tool-x86_64.tar.gz").expect("bsd entry"),
+        a
+    );
+    let single = format!("{a}\n");
+    assert_eq!(
+        parse_checksum_file(&single, "tool-x86_64.tar.gz").expect("single digest"),
+        a
+    );
+    let err = parse_checksum_file(&gnu, "tool-riscv64.tar.gz").expect_err("missing entry");
+    assert!(err.to_string().contains("tool-riscv64.tar.gz"));
+}
+
+#[test]
+fn blake2b512_matches_reference_vectors() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-blake2b-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&root).expect("create root");
+    let digest_of = |data: &[u8]| {
+        let path = root.join("payload");
+        fs::write(&path, data).expect("write payload");
+        blake2b512_file(&path)
+            .expect("hash payload")
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>()
+    };
+
+    assert_eq!(
+        digest_of(b""),
+        "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+    );
+    // RFC 7693 appendix A.
+    assert_eq!(
+        digest_of(b"abc"),
+        "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+    );
+    assert_eq!(
+        digest_of(b"The quick brown fox jumps over the lazy dog"),
+        "a8add4bdddfd93e4877d2746e62817b116364a1fa7bc148d95090bc7333b3673f82401cf7aa2e4cb1ecd90296e3f14cb5413f8ed77be73045b13914cdcd6a918"
+    );
+    // Larger than the read buffer, so the file is hashed in several chunks.
+    let large = vec![0x5au8; 20_000];
+    let one_shot = {
+        use blake2::{Blake2b512, Digest};
+        Blake2b512::digest(&large)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>()
+    };
+    assert_eq!(digest_of(&large), one_shot);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn minisign_signatures_verify_prehashed_payloads() {
+    use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+    use graviola::signing::eddsa::Ed25519SigningKey;
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-minisign-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&root).expect("create root");
+    let payload = root.join("tool.tar.gz");
+    fs::write(&payload, b"release payload").expect("write payload");
+
+    let signing_key = Ed25519SigningKey::generate().expect("generate key");
+    let key_id = *b"za-test!";
+    let mut public_key = b"Ed".to_vec();
+    public_key.extend_from_slice(&key_id);
+    public_key.extend_from_slice(&signing_key.public_key().as_bytes());
+    let public_key = MinisignPublicKey::parse(&format!(
+        "untrusted comment: minisign public key\n{}\n",
+        BASE64.encode(&public_key)
+    ))
+    .expect("parse public key");
+
+    let signature_bytes = signing_key.sign(&blake2b512_file(&payload).expect("prehash payload"));
+    let mut signature = b"ED".to_vec();
+    signature.extend_from_slice(&key_id);
+    signature.extend_from_slice(&signature_bytes);
+    let trusted_comment = "timestamp:1700000000\tfile:tool.tar.gz";
+    let mut signed_comment = signature_bytes.to_vec();
+    signed_comment.extend_from_slice(trusted_comment.as_bytes());
+    let minisig = format!(
+        "untrusted comment: signature from minisign secret key\n{}\ntrusted comment: {trusted_comment}\n{}\n",
+        BASE64.encode(&signature),
+        BASE64.encode(signing_key.sign(&signed_comment))
+    );
+
+    verify_minisign(public_key, &minisig, &payload).expect("valid signature");
+
+    let tampered_comment = minisig.replace("file:tool.tar.gz", "file:other.tar.gz");
+    let err = verify_minisign(public_key, &tampered_comment, &payload)
+        .expect_err("tampered trusted comment");
+    assert!(err.to_string().contains("trusted comment"));
+
+    fs::write(&payload, b"tampered payload").expect("tamper payload");
+    let err = verify_minisign(public_key, &minisig, &payload).expect_err("tampered payload");
+    assert!(err.to_string().contains("does not match"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_registry_user_entries_override_system_entries() {
+    let system = r#"
+[tools.kubectx]
+owner = "ahmetb"
+repo = "kubectx"
+asset = "kubectx-{version}.tar.gz"
+"#;
+    let user = r#"
+[tools.kubectx]
+owner = "internal"
+repo = "kubectx"
+asset = "kubectx-{version}-{target}.tar.gz"
+"#;
+    let policies = parse_tool_registries(
+        &[
+            (
+                ToolRegistryOrigin::System,
+                Path::new("/etc/za/tools.toml"),
+                system,
+            ),
+            (ToolRegistryOrigin::User, Path::new("tools.toml"), user),
+        ],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    assert_eq!(policies.len(), 1);
+    assert_eq!(
+        policies[0].release.expect("release policy").owner,
+        "internal"
+    );
+}
+
+#[test]
+fn tool_registry_rejects_invalid_entries() {
+    let cases = [
+        (
+            r#"
+[tools.ripgrep-fork]
+owner = "me"
+repo = "rg"
+asset = "rg-{version}.tar.gz"
+aliases = ["ripgrep"]
+"#,
+            "already registered",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{tag}.tar.gz"
+"#,
+            "unknown placeholder `{tag}`",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+layout = "package"
+"#,
+            "requires `entry`",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+layout = "package"
+entry = "../foo"
+"#,
+            "relative path inside the package payload",
+        ),
+    ];
+    for (raw, expected) in cases {
+        let err = parse_tool_registries(
+            &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+            tool_policies(),
+        )
+        .expect_err("invalid registry");
+        assert!(
+            format!("{err:#}").contains(expected),
+            "expected `{expected}` in `{err:#}`"
+        );
+    }
+}
+
+#[test]
+fn tool_target_parses_triples_platforms_and_labels() {
+    let Ok(host_linux) = ToolTarget::parse("linux/amd64") else {
+        assert_ne!(std::env::consts::OS, "linux");
+        return;
+    };
+    assert_eq!(host_linux.arch, "x86_64");
+    assert_eq!(host_linux.libc, None);
+    assert_eq!(host_linux.to_string(), "x86_64-linux");
+
+    let musl = ToolTarget::parse("aarch64-unknown-linux-musl").expect("musl triple");
+    assert_eq!(musl.arch, "aarch64");
+    assert_eq!(musl.libc, Some(ToolLibc::Musl));
+    assert_eq!(musl.to_string(), "aarch64-linux-musl");
+    assert_eq!(
+        ToolTarget::parse(&musl.to_string()).expect("label round-trips"),
+        musl
+    );
+    assert_eq!(
+        musl.rust_triple().expect("triple"),
+        "aarch64-unknown-linux-musl"
+    );
+
+    let err = ToolTarget::parse("x86_64-apple-darwin").expect_err("cross-OS target");
+    assert!(err.to_string().contains("same OS"));
+    let err = ToolTarget::parse("riscv64gc-unknown-linux-gnu").expect_err("unknown target");
+    assert!(err.to_string().contains("unsupported tool target"));
+}
+
+#[test]
+fn foreign_targets_use_a_separate_store_and_updates_keep_the_recorded_target() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-target-store-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    assert_eq!(
+        home.for_target(ToolTarget::host()).store_dir,
+        home.store_dir
+    );
+
+    let foreign_arch = if std::env::consts::ARCH == "x86_64" {
+        "aarch64"
+    } else {
+        "x86_64"
+    };
+    if let Ok(foreign) = ToolTarget::parse(&format!("{foreign_arch}-unknown-linux-musl")) {
+        assert!(!foreign.runs_on_host());
+        assert_eq!(
+            home.for_target(foreign).store_dir,
+            root.join(format!("store-{foreign_arch}-linux-musl"))
+        );
+    }
+
+    let tool = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+    fs::create_dir_all(home.install_path(&tool).parent().expect("parent"))
+        .expect("create version dir");
+    fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+            .expect("chmod binary");
+    }
+    super::state::activate_tool(&home, &tool).expect("activate rg");
+    let pinned = ToolTarget {
+        libc: Some(ToolLibc::Gnu),
+        ..ToolTarget::host()
+    };
+    super::state::write_manifest(
+        &home,
+        &tool,
+        &super::InstallSource {
+            kind: "download",
+            detail: "URL https://example.com/rg.tar.gz".to_string(),
+            asset: None,
+            unverified: false,
+        },
+        Some(pinned),
+        None,
+    )
+    .expect("write manifest");
+    if ToolTarget::parse(&pinned.to_string()).is_ok() {
+        assert_eq!(
+            super::state::read_active_target(&home, "rg").expect("active target"),
+            Some(pinned)
+        );
+    }
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_policy_asset_names_follow_requested_target() {
+    if std::env::consts::OS != "linux" {
+        return;
+    }
+    let amd64 = ToolTarget::parse("linux/amd64").expect("target");
+    let amd64_gnu = ToolTarget::parse("x86_64-unknown-linux-gnu").expect("target");
+    let amd64_musl = ToolTarget::parse("x86_64-unknown-linux-musl").expect("target");
+    let arm64_musl = ToolTarget::parse("aarch64-unknown-linux-musl").expect("target");
+    let arm64_gnu = ToolTarget::parse("aarch64-unknown-linux-gnu").expect("target");
+    let resolve = |name: &str, version: &str, target: ToolTarget| {
+        find_tool_policy(name)
+            .expect("policy")
+            .release
+            .expect("release policy")
+            .expected_asset_name
+            .expect("asset resolver")
+            .resolve(version, target)
+    };
+
+    assert_eq!(
+        resolve("just", "1.40.0", arm64_musl).expect("asset name"),
+        "just-1.40.0-aarch64-unknown-linux-musl.tar.gz"
+    );
+    assert_eq!(
+        resolve("protoc", "35.1", arm64_gnu).expect("asset name"),
+        "protoc-35.1-linux-aarch_64.zip"
+    );
+
+    assert_eq!(
+        resolve("fd", "10.2.0", amd64).expect("asset name"),
+        "fd-v10.2.0-x86_64-unknown-linux-musl.tar.gz"
+    );
+    assert_eq!(
+        resolve("fd", "10.2.0", amd64_gnu).expect("asset name"),
+        "fd-v10.2.0-x86_64-unknown-linux-gnu.tar.gz"
+    );
+
+    assert_eq!(
+        resolve("rg", "14.1.1", amd64_musl).expect("asset name"),
+        "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz"
+    );
+    let err =
+        resolve("rg", "14.1.1", amd64_gnu).expect_err("musl asset must not satisfy gnu target");
+    assert!(err.to_string().contains("no gnu asset for ripgrep"));
+
+    assert_eq!(
+        resolve("rg", "14.1.1", arm64_gnu).expect("asset name"),
+        "ripgrep-14.1.1-aarch64-unknown-linux-gnu.tar.gz"
+    );
+    let err =
+        resolve("rg", "14.1.1", arm64_musl).expect_err("gnu asset must not satisfy musl target");
+    assert!(err.to_string().contains("no musl asset for ripgrep"));
+
+    let err = arm64_musl
+        .ensure_asset_compatible("ripgrep", "ripgrep-14.1.1-aarch64-unknown-linux-gnu.tar.gz")
+        .expect_err("gnu asset must not satisfy musl target");
+    assert!(err.to_string().contains("no musl asset for ripgrep"));
+    let err = amd64_gnu
+        .ensure_asset_compatible("ripgrep", "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz")
+        .expect_err("musl asset must not satisfy gnu target");
+    assert!(err.to_string().contains("no gnu asset for ripgrep"));
+    amd64
+        .ensure_asset_compatible("ripgrep", "ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz")
+        .expect("unpinned target accepts either libc");
+
+    let err =
+        resolve("cargo-fuzz", "0.12.0", arm64_gnu).expect_err("cargo-fuzz has no arm64 asset");
+    assert!(err.to_string().contains("aarch64-linux"));
+}
+
+#[test]
+fn sync_lockfile_round_trips_and_pins_locked_installs() {
+    use super::lockfile::{
+        TOOL_SYNC_LOCKFILE_VERSION, ToolSyncLockfile, collect_sync_lock_entries, locked_sync_specs,
+        read_sync_lockfile, sync_lockfile_path, write_sync_lockfile,
+    };
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-sync-lockfile-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let tool = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+    fs::create_dir_all(home.install_path(&tool).parent().expect("parent"))
+        .expect("create version dir");
+    fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+            .expect("chmod binary");
+    }
+    super::state::activate_tool(&home, &tool).expect("activate rg");
+    let asset = super::ReleaseAsset {
+        url: "https://example.com/ripgrep-14.1.0.tar.gz".to_string(),
+        sha256: "ab".repeat(32),
+    };
+    super::state::write_manifest(
+        &home,
+        &tool,
+        &super::InstallSource {
+            kind: "download",
+            detail: format!("URL {}", asset.url),
+            asset: Some(asset.clone()),
+            unverified: false,
+        },
+        Some(ToolTarget::host()),
+        None,
+    )
+    .expect("write manifest");
+
+    let manifest_path = root.join("za.tools.toml");
+    let lockfile_path = sync_lockfile_path(&manifest_path);
+    assert_eq!(lockfile_path, root.join("za.tools.lock"));
+
+    let specs = vec!["rg".to_string()];
+    let lockfile = ToolSyncLockfile {
+        version: TOOL_SYNC_LOCKFILE_VERSION,
+        tools: collect_sync_lock_entries(&home, &specs, std::slice::from_ref(&tool))
+            .expect("collect lock entries"),
+    };
+    assert_eq!(lockfile.tools.len(), 1);
+    assert_eq!(lockfile.tools[0].version, "14.1.0");
+    assert_eq!(lockfile.tools[0].url.as_deref(), Some(asset.url.as_str()));
+    assert_eq!(
+        lockfile.tools[0].sha256.as_deref(),
+        Some(asset.sha256.as_str())
+    );
+
+    write_sync_lockfile(&lockfile_path, &lockfile).expect("write lockfile");
+    let reread = read_sync_lockfile(&lockfile_path)
+        .expect("read lockfile")
+        .expect("lockfile exists");
+    assert_eq!(reread, lockfile);
+
+    let sync_entry = |name: &str| super::batch::ToolSyncEntry {
+        spec: name.to_string(),
+        name: name.to_string(),
+        channel: ToolUpdateChannel::Stable,
+        hold: false,
+        integrations: true,
+    };
+    let entries = vec![sync_entry("rg")];
+    let locked = locked_sync_specs(&lockfile_path, &reread, &entries, ToolTarget::host())
+        .expect("locked specs");
+    assert_eq!(locked.len(), 1);
+    assert_eq!(locked[0].version.as_deref(), Some("14.1.0"));
+    assert_eq!(locked[0].locked.as_ref(), Some(&asset));
+
+    let drifted = vec![sync_entry("rg"), sync_entry("codex")];
+    let err = locked_sync_specs(&lockfile_path, &reread, &drifted, ToolTarget::host())
+        .expect_err("manifest drift must fail");
+    assert!(
+        err.to_string().contains("not locked: codex"),
+        "unexpected error: {err:#}"
+    );
+
+    let arm = ToolTarget::parse("linux/arm64").expect("arm target");
+    if !arm.is_host() {
+        let err = locked_sync_specs(&lockfile_path, &reread, &entries, arm)
+            .expect_err("target drift must fail");
+        assert!(err.to_string().contains("but this sync targets"));
+    }
+
+    let mut unpinned = reread.clone();
+    unpinned.tools[0].url = None;
+    unpinned.tools[0].sha256 = None;
+    let err = locked_sync_specs(&lockfile_path, &unpinned, &entries, ToolTarget::host())
+        .expect_err("entries without an asset must not install unpinned");
+    assert!(
+        err.to_string().contains("must set both `url` and `sha256`"),
+        "unexpected error: {err:#}"
+    );
+
+    super::state::write_manifest(
+        &home,
+        &tool,
+        &super::InstallSource {
+            kind: "adopted",
+            detail: "test".to_string(),
+            asset: None,
+            unverified: false,
+        },
+        Some(ToolTarget::host()),
+        None,
+    )
+    .expect("rewrite manifest without asset");
+    let unpinned = collect_sync_lock_entries(&home, &specs, std::slice::from_ref(&tool))
+        .expect("tools without a recorded asset are locked by version");
+    assert_eq!(unpinned[0].version, "14.1.0");
+    assert_eq!(unpinned[0].url, None);
+    assert_eq!(unpinned[0].sha256, None);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn sync_locks_tools_whose_manifest_predates_asset_recording() {
+    use super::batch::{ToolSyncRequest, sync_manifest};
+    use super::lockfile::{read_sync_lockfile, sync_lockfile_path};
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-sync-legacy-manifest-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let tool = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+    fs::create_dir_all(home.install_path(&tool).parent().expect("parent"))
+        .expect("create version dir");
+    fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+            .expect("chmod binary");
+    }
+    super::state::activate_tool(&home, &tool).expect("activate rg");
+    let legacy = serde_json::json!({
+        "schema_version": super::MANIFEST_SCHEMA_VERSION,
+        "name": "rg",
+        "version": "14.1.0",
+        "installed_at_unix_secs": 0,
+        "source_kind": "download",
+        "source_detail": "URL https://example.com/ripgrep-14.1.0.tar.gz",
+        "sha256": sha256_file(&home.install_path(&tool)).expect("hash binary"),
+        "size_bytes": 10,
+    });
+    fs::write(
+        home.manifest_path(&tool),
+        serde_json::to_vec_pretty(&legacy).expect("serialize manifest"),
+    )
+    .expect("write legacy manifest");
+
+    let manifest_path = root.join("za.tools.toml");
+    fs::write(&manifest_path, "tools = [\"rg@14.1.0\"]\n").expect("write sync manifest");
+    sync_manifest(
+        &home,
+        ToolSyncRequest {
+            file: &manifest_path,
+            target: ToolTarget::host(),
+            locked: false,
+            prune: false,
+            dry_run: false,
+            verbose: false,
+        },
+    )
+    .expect("sync over a legacy manifest succeeds");
+
+    let lockfile = read_sync_lockfile(&sync_lockfile_path(&manifest_path))
+        .expect("read lockfile")
+        .expect("lockfile written");
+    assert_eq!(lockfile.tools.len(), 1);
+    assert_eq!(lockfile.tools[0].version, "14.1.0");
+    assert_eq!(lockfile.tools[0].url, None);
+
+    let err = sync_manifest(
+        &home,
+        ToolSyncRequest {
+            file: &manifest_path,
+            target: ToolTarget::host(),
+            locked: true,
+            prune: false,
+            dry_run: false,
+            verbose: false,
+        },
+    )
+    .expect_err("--locked refuses entries without an asset");
+    assert!(
+        err.to_string().contains("must set both `url` and `sha256`"),
+        "unexpected error: {err:#}"
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_export_pins_versions_assets_and_checksums() {
+    use super::export::{
+        collect_export_entries, render_export_dockerfile, render_export_script, run_export,
+    };
+    use super::lockfile::{locked_sync_specs, read_sync_lockfile, sync_lockfile_path};
+    use crate::cli::ToolExportFormat;
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-tool-export-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let asset = super::ReleaseAsset {
+        url: "https://github.com/BurntSushi/ripgrep/releases/download/14.1.0/ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz".to_string(),
+        sha256: "ab".repeat(32),
+    };
+    for (name, version, asset) in [
+        ("rg", "14.1.0", Some(asset.clone())),
+        ("fd", "10.2.0", None),
+    ] {
+        let tool = ToolRef {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+        fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+                .expect("chmod binary");
+        }
+        super::state::activate_tool(&home, &tool).expect("activate tool");
+        super::state::write_manifest(
+            &home,
+            &tool,
+            &super::InstallSource {
+                kind: if asset.is_some() {
+                    "download"
+                } else {
+                    "adopted"
+                },
+                detail: "test".to_string(),
+                asset,
+                unverified: false,
+            },
+            Some(ToolTarget::host()),
+            None,
+        )
+        .expect("write manifest");
+    }
+
+    let entries = collect_export_entries(&home, &[]).expect("collect export entries");
+    assert_eq!(
+        entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.version.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("fd", "10.2.0"), ("rg", "14.1.0")]
+    );
+    assert!(collect_export_entries(&home, &["just".to_string()]).is_err());
+
+    let script = render_export_script(&entries, None);
+    assert!(script.starts_with("#!/bin/sh\n"));
+    assert!(script.contains(&format!(
+        "za_export_binary 'rg' '{}' '{}' 'rg'\n",
+        asset.url, asset.sha256
+    )));
+    assert!(script.contains("# fd 10.2.0"));
+    assert!(!script.contains("za_export_binary 'fd'"));
+    let dockerfile = render_export_dockerfile(&entries);
+    assert!(dockerfile.contains("FROM ${ZA_EXPORT_BASE_IMAGE} AS za-tools"));
+    assert!(dockerfile.contains("ZA_EXPORT_BIN_DIR='/opt/za/bin'"));
+
+    let package = super::export::ToolExportEntry {
+        name: "buildkit".to_string(),
+        version: "0.20.0".to_string(),
+        target: None,
+        asset: Some(asset.clone()),
+        package_entry: Some("bin/buildctl"),
+        exposed: &[
+            super::policy::ExposedEntry {
+                relpath: "bin/buildctl",
+                placement: EntryPlacement::Bin("buildctl"),
+            },
+            super::policy::ExposedEntry {
+                relpath: "bin/docker-buildx",
+                placement: EntryPlacement::DockerCliPlugin("docker-buildx"),
+            },
+        ],
+        source_kind: Some("download".to_string()),
+    };
+    let script = render_export_script(std::slice::from_ref(&package), None);
+    assert!(script.contains(
+        "za_export_link \"$ZA_EXPORT_SHARE_DIR\"/'buildkit'/'bin/buildctl' \"$ZA_EXPORT_BIN_DIR\" 'buildctl'\n"
+    ));
+    assert!(script.contains(
+        "za_export_link \"$ZA_EXPORT_SHARE_DIR\"/'buildkit'/'bin/docker-buildx' \"$ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR\" 'docker-buildx'\n"
+    ));
+    assert!(script.contains(
+        "ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR=\"${ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR:-/usr/local/lib/docker/cli-plugins}\""
+    ));
+    let compose = super::export::ToolExportEntry {
+        name: "docker-compose".to_string(),
+        package_entry: None,
+        exposed: super::exposed_entries_for_name("docker-compose"),
+        ..package
+    };
+    let script = render_export_script(&[compose], None);
+    assert!(script.contains(
+        "za_export_link \"$ZA_EXPORT_BIN_DIR\"/'docker-compose' \"$ZA_EXPORT_DOCKER_CLI_PLUGINS_DIR\" 'docker-compose'\n"
+    ));
+
+    let manifest_path = root.join("export").join("za.tools.toml");
+    run_export(
+        &home,
+        &["rg".to_string()],
+        ToolExportFormat::SyncManifest,
+        Some(&manifest_path),
+    )
+    .expect("export sync manifest");
+    let sync_entries = load_sync_entries_from_manifest(&manifest_path).expect("parse export");
+    assert_eq!(sync_entries.len(), 1);
+    assert_eq!(sync_entries[0].spec, "rg:14.1.0");
+    let lockfile_path = sync_lockfile_path(&manifest_path);
+    let lockfile = read_sync_lockfile(&lockfile_path)
+        .expect("read lockfile")
+        .expect("lockfile exists");
+    let locked = locked_sync_specs(&lockfile_path, &lockfile, &sync_entries, ToolTarget::host())
+        .expect("locked specs");
+    assert_eq!(locked[0].version.as_deref(), Some("14.1.0"));
+    assert_eq!(locked[0].locked.as_ref(), Some(&asset));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_sbom_describes_active_tools_in_cyclonedx_and_spdx() {
+    use super::sbom::{
+        SbomDocumentInfo, collect_sbom_entries, is_spdx_license_id, render_cyclonedx, render_spdx,
+        spdx_package_id,
+    };
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-tool-sbom-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let asset = super::ReleaseAsset {
+        url: "https://github.com/BurntSushi/ripgrep/releases/download/14.1.0/ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz".to_string(),
+        sha256: "ab".repeat(32),
+    };
+    let tool = ToolRef {
+        name: "rg".to_string(),
+        version: "14.1.0".to_string(),
+    };
+    fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+    fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+            .expect("chmod binary");
+    }
+    super::state::activate_tool(&home, &tool).expect("activate tool");
+    super::state::write_manifest(
+        &home,
+        &tool,
+        &super::InstallSource {
+            kind: "download",
+            detail: "test".to_string(),
+            asset: Some(asset.clone()),
+            unverified: false,
+        },
+        Some(ToolTarget::host()),
+        None,
+    )
+    .expect("write manifest");
+
+    let mut entries = collect_sbom_entries(&home).expect("collect SBOM entries");
+    assert_eq!(entries.len(), 1);
+    let entry = &mut entries[0];
+    assert_eq!(
+        entry.repository.as_deref(),
+        Some("https://github.com/BurntSushi/ripgrep")
+    );
+    assert_eq!(
+        entry.purl.as_deref(),
+        Some("pkg:github/burntsushi/ripgrep@14.1.0")
+    );
+    assert_eq!(entry.asset.as_ref(), Some(&asset));
+    let installed_sha256 = entry.sha256.clone().expect("installed digest");
+    entry.license = Some("MIT".to_string());
+    let info = SbomDocumentInfo {
+        scope: "user",
+        created_unix_secs: 1_700_000_000,
+        uuid: "3f0c2a4e-1b2d-4c3e-8f4a-5b6c7d8e9f00".to_string(),
+    };
+
+    let cyclonedx: serde_json::Value =
+        serde_json::from_str(&render_cyclonedx(&entries, &info).expect("render CycloneDX"))
+            .expect("CycloneDX is JSON");
+    assert_eq!(cyclonedx["bomFormat"], "CycloneDX");
+    assert_eq!(cyclonedx["specVersion"], "1.5");
+    assert_eq!(
+        cyclonedx["serialNumber"],
+        "urn:uuid:3f0c2a4e-1b2d-4c3e-8f4a-5b6c7d8e9f00"
+    );
+    assert_eq!(cyclonedx["metadata"]["timestamp"], "2023-11-14T22:13:20Z");
+    let component = &cyclonedx["components"][0];
+    assert_eq!(component["name"], "rg");
+    assert_eq!(component["version"], "14.1.0");
+    assert_eq!(component["hashes"][0]["alg"], "SHA-256");
+    assert_eq!(component["hashes"][0]["content"], installed_sha256.as_str());
+    assert_eq!(component["licenses"][0]["license"]["id"], "MIT");
+    assert_eq!(component["externalReferences"][0]["type"], "vcs");
+    assert_eq!(
+        component["externalReferences"][1]["url"],
+        asset.url.as_str()
+    );
+    assert_eq!(component["properties"][0]["name"], "za:installed_at");
+
+    let spdx: serde_json::Value =
+        serde_json::from_str(&render_spdx(&entries, &info).expect("render SPDX"))
+            .expect("SPDX is JSON");
+    assert_eq!(spdx["spdxVersion"], "SPDX-2.3");
+    assert_eq!(spdx["SPDXID"], "SPDXRef-DOCUMENT");
+    assert_eq!(spdx["creationInfo"]["created"], "2023-11-14T22:13:20Z");
+    let package = &spdx["packages"][0];
+    assert_eq!(package["SPDXID"], "SPDXRef-Package-rg");
+    assert_eq!(package["downloadLocation"], asset.url.as_str());
+    assert_eq!(package["checksums"][0]["algorithm"], "SHA256");
+    assert_eq!(
+        package["checksums"][0]["checksumValue"],
+        asset.sha256.as_str()
+    );
+    assert_eq!(package["licenseDeclared"], "MIT");
+    assert_eq!(package["filesAnalyzed"], false);
+    assert_eq!(
+        package["externalRefs"][0]["referenceLocator"],
+        "pkg:github/burntsushi/ripgrep@14.1.0"
+    );
+    assert_eq!(
+        spdx["relationships"][0]["relatedSpdxElement"],
+        "SPDXRef-Package-rg"
+    );
+    let sanitized = spdx_package_id("foo_bar");
+    assert!(sanitized.starts_with("SPDXRef-Package-foo-bar-"));
+    assert_ne!(sanitized, spdx_package_id("foo-bar"));
+    assert_ne!(sanitized, spdx_package_id("foo+bar"));
+    assert_eq!(spdx_package_id("foo.bar"), "SPDXRef-Package-foo.bar");
+
+    assert!(is_spdx_license_id("Apache-2.0"));
+    assert!(is_spdx_license_id("GPL-2.0+"));
+    assert!(!is_spdx_license_id("Apache License 2.0"));
+    assert!(!is_spdx_license_id("LicenseRef-custom"));
+
+    entries[0].license = Some("Apache License 2.0".to_string());
+    let cyclonedx: serde_json::Value =
+        serde_json::from_str(&render_cyclonedx(&entries, &info).expect("render CycloneDX"))
+            .expect("CycloneDX is JSON");
+    let license = &cyclonedx["components"][0]["licenses"][0]["license"];
+    assert_eq!(license["name"], "Apache License 2.0");
+    assert!(license.get("id").is_none());
+    let spdx: serde_json::Value =
+        serde_json::from_str(&render_spdx(&entries, &info).expect("render SPDX"))
+            .expect("SPDX is JSON");
+    assert_eq!(spdx["packages"][0]["licenseDeclared"], "NOASSERTION");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn tool_spec_parses_semver_ranges() {
+    let caret = ToolSpec::parse("just@^1.30").expect("caret range");
+    assert_eq!(caret.version, None);
+    assert_eq!(
+        caret.requirement.as_ref().map(ToString::to_string),
+        Some("^1.30".to_string())
+    );
+    assert_eq!(caret.display_spec(), "just:^1.30");
+
+    let tilde = ToolSpec::parse("rg@~14").expect("tilde range");
+    assert!(tilde.requirement.is_some());
+
+    let bounded = ToolSpec::parse("codex@>=0.40,<0.50").expect("bounded range");
+    let requirement = bounded.requirement.expect("requirement");
+    assert!(requirement.matches(&semver::Version::new(0, 45, 1)));
+    assert!(!requirement.matches(&semver::Version::new(0, 50, 0)));
+
+    let exact = ToolSpec::parse("rg@v14.1.0").expect("exact version");
+    assert_eq!(exact.version.as_deref(), Some("14.1.0"));
+    assert!(exact.requirement.is_none());
+    assert_eq!(exact.display_spec(), "rg:14.1.0");
+
+    let err = ToolSpec::parse("rg@^fourteen").expect_err("invalid range");
+    assert!(err.to_string().contains("invalid version requirement"));
+}
+
+#[test]
+fn load_sync_entries_accepts_per_tool_tables() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-sync-manifest-table-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    fs::create_dir_all(&root).expect("create temp root");
+    let manifest_path = root.join("za.tools.toml");
+    fs::write(
+        &manifest_path,
+        r#"
+[tools]
+rg = "~14"
+codex = { channel = "alpha" }
+just = { version = "^1.30", hold = true }
+starship = { integrations = true }
+"#,
+    )
+    .expect("write manifest");
+
+    let entries = load_sync_entries_from_manifest(&manifest_path).expect("parse manifest");
+    let by_name = entries
+        .iter()
+        .map(|entry| (entry.name.as_str(), entry))
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(by_name["rg"].spec, "rg:~14");
+    assert!(!by_name["rg"].integrations);
+    assert_eq!(by_name["codex"].channel, ToolUpdateChannel::CodexAlpha);
+    assert_eq!(by_name["just"].spec, "just:^1.30");
+    assert!(by_name["just"].hold);
+    assert!(by_name["starship"].integrations);
+
+    fs::write(&manifest_path, "[tools]\nrg = { channel = \"alpha\" }\n").expect("write manifest");
+    let err = load_sync_entries_from_manifest(&manifest_path).expect_err("alpha is codex-only");
+    assert!(format!("{err:#}").contains("only supported for `codex`"));
+
+    fs::write(&manifest_path, "[tools]\nrg = { pinned = true }\n").expect("write manifest");
+    let err = load_sync_entries_from_manifest(&manifest_path).expect_err("unknown key");
+    assert!(format!("{err:#}").contains("tools.rg"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn sync_prune_removes_unlisted_tools_but_keeps_za() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-sync-prune-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    for (name, version) in [("rg", "14.1.0"), ("just", "1.30.0"), ("za", "0.1.94")] {
+        let tool = ToolRef {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+        fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+    }
+    let entries = vec![super::batch::ToolSyncEntry {
+        spec: "rg".to_string(),
+        name: "rg".to_string(),
+        channel: ToolUpdateChannel::Stable,
+        hold: false,
+        integrations: true,
+    }];
+
+    super::batch::prune_unlisted_tools(&home, &entries, true).expect("preview prune");
+    assert!(home.name_dir("just").exists());
+
+    super::batch::prune_unlisted_tools(&home, &entries, false).expect("prune");
+    assert!(!home.name_dir("just").exists());
+    assert!(home.name_dir("rg").exists());
+    assert!(home.name_dir("za").exists());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn artifact_cache_restores_stored_assets_by_digest() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-artifact-cache-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let cache_root = root.join("cache");
+    let asset = root.join("download/rg-14.1.0-x86_64-unknown-linux-musl.tar.gz");
+    fs::create_dir_all(asset.parent().expect("asset parent")).expect("create download dir");
+    fs::write(&asset, b"release asset bytes").expect("write asset");
+    let sha256 = sha256_file(&asset).expect("hash asset");
+
+    let stored = store_artifact(
+        &cache_root,
+        &asset,
+        "https://example.com/rg.tar.gz",
+        &sha256,
+    )
+    .expect("store asset");
+    assert_eq!(stored.size_bytes, 19);
+    assert_eq!(
+        list_cache_entries(&cache_root).expect("list cache"),
+        vec![stored.clone()]
+    );
+
+    let restored_path = root.join("restore/rg.tar.gz");
+    fs::create_dir_all(restored_path.parent().expect("restore parent")).expect("create dir");
+    let restored = restore_cached_artifact(&cache_root, &sha256.to_uppercase(), &restored_path)
+        .expect("restore asset")
+        .expect("cache hit");
+    assert_eq!(restored.file_name, stored.file_name);
+    assert_eq!(
+        fs::read(&restored_path).expect("read restored"),
+        b"release asset bytes"
+    );
+
+    assert!(contains_artifact(&cache_root, &sha256));
+    let release = CachedRelease {
+        tool: "rg".to_string(),
+        version: "14.1.0".to_string(),
+        target: ToolTarget::host().to_string(),
+    };
+    assert_eq!(find_cached_release(&cache_root, &release), None);
+    record_cached_release(&cache_root, &sha256, release.clone()).expect("record release");
+    record_cached_release(&cache_root, &sha256, release.clone()).expect("record again");
+    assert_eq!(
+        find_cached_release(&cache_root, &release),
+        Some(super::ReleaseAsset {
+            url: "https://example.com/rg.tar.gz".to_string(),
+            sha256: sha256.clone(),
+        })
+    );
+    store_artifact(
+        &cache_root,
+        &asset,
+        "https://example.com/rg.tar.gz",
+        &sha256,
+    )
+    .expect("store asset again");
+    let entries = list_cache_entries(&cache_root).expect("list cache");
+    assert_eq!(entries[0].releases, vec![release.clone()]);
+    for digest in ["../../etc", "..", &"g".repeat(64)] {
+        assert!(!contains_artifact(&cache_root, digest));
+        assert!(
+            restore_cached_artifact(&cache_root, digest, &restored_path)
+                .expect("restore with invalid digest")
+                .is_none()
+        );
+        assert!(store_artifact(&cache_root, &asset, "https://example.com/x", digest).is_err());
+    }
+    fs::create_dir_all(cache_root.join("sha256").join("not-a-digest")).expect("create stray");
+    assert_eq!(
+        list_cache_entries(&cache_root).expect("list cache").len(),
+        1
+    );
+
+    let cached_file = cache_root
+        .join("sha256")
+        .join(&sha256)
+        .join(&stored.file_name);
+    fs::write(&cached_file, b"tampered").expect("corrupt cache");
+    assert!(
+        restore_cached_artifact(&cache_root, &sha256, &restored_path)
+            .expect("restore corrupted asset")
+            .is_none()
+    );
+    assert!(
+        list_cache_entries(&cache_root)
+            .expect("list cache")
+            .is_empty()
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn partial_download_keeps_parts_only_for_matching_metadata() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-partial-download-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let meta = PartialDownloadMeta {
+        url: "https://example.com/rg.tar.gz".to_string(),
+        etag: "\"abc\"".to_string(),
+        total_bytes: 4 * 1024 * 1024,
+        parts: 4,
+    };
+
+    let staging = PartialDownload::open(&root, &meta)
+        .expect("open staging")
+        .expect("staging unlocked");
+    assert!(!staging.resumed());
+    assert!(
+        PartialDownload::open(&root, &meta)
+            .expect("open locked staging")
+            .is_none(),
+        "a second download of the same URL must not share the parts"
+    );
+    let part = staging.part_path(0);
+    fs::write(&part, b"staged").expect("write part");
+    drop(staging);
+
+    let staging = PartialDownload::open(&root, &meta)
+        .expect("reopen staging")
+        .expect("staging unlocked");
+    assert!(staging.resumed());
+    assert_eq!(fs::read(&part).expect("read part"), b"staged");
+    drop(staging);
+
+    let changed = PartialDownloadMeta {
+        etag: "\"def\"".to_string(),
+        ..meta.clone()
+    };
+    let staging = PartialDownload::open(&root, &changed)
+        .expect("open changed staging")
+        .expect("staging unlocked");
+    assert!(!staging.resumed());
+    assert!(!part.exists(), "parts of a changed asset are dropped");
+    staging.discard();
+    assert!(!part.parent().expect("staging dir").exists());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn select_prunable_entries_drops_stale_then_least_recently_used() {
+    let entry = |sha256: &str, size_bytes: u64, last_used_unix_secs: u64| CachedArtifact {
+        sha256: sha256.to_string(),
+        file_name: format!("{sha256}.tar.gz"),
+        url: format!("https://example.com/{sha256}.tar.gz"),
+        size_bytes,
+        stored_at_unix_secs: 0,
+        last_used_unix_secs,
+        releases: Vec::new(),
+    };
+    let entries = vec![
+        entry("fresh", 40, 1_000),
+        entry("older", 40, 900),
+        entry("oldest", 40, 100),
+    ];
+    let names = |pruned: Vec<CachedArtifact>| {
+        pruned
+            .into_iter()
+            .map(|entry| entry.sha256)
+            .collect::<Vec<_>>()
+    };
+
+    let by_age = CachePrunePolicy {
+        older_than: Some(Duration::from_secs(500)),
+        ..CachePrunePolicy::default()
+    };
+    assert_eq!(
+        names(select_prunable_entries(&entries, by_age, 1_000)),
+        vec!["oldest"]
+    );
+
+    let by_size = CachePrunePolicy {
+        max_bytes: Some(80),
+        ..CachePrunePolicy::default()
+    };
+    assert_eq!(
+        names(select_prunable_entries(&entries, by_size, 1_000)),
+        vec!["oldest"]
+    );
+
+    let combined = CachePrunePolicy {
+        older_than: Some(Duration::from_secs(500)),
+        max_bytes: Some(40),
+        ..CachePrunePolicy::default()
+    };
+    assert_eq!(
+        names(select_prunable_entries(&entries, combined, 1_000)),
+        vec!["oldest", "older"]
+    );
+
+    let all = CachePrunePolicy {
+        all: true,
+        ..CachePrunePolicy::default()
+    };
+    assert_eq!(select_prunable_entries(&entries, all, 1_000).len(), 3);
+}
+
+#[test]
+fn parse_byte_size_accepts_binary_suffixes() {
+    use crate::command::za_config::parse_byte_size;
+
+    assert_eq!(parse_byte_size("512").expect("bytes"), 512);
+    assert_eq!(parse_byte_size("4K").expect("kib"), 4 * 1024);
+    assert_eq!(parse_byte_size("512MiB").expect("mib"), 512 * 1024 * 1024);
+    assert_eq!(parse_byte_size("2g").expect("gib"), 2 * 1024 * 1024 * 1024);
+    assert!(parse_byte_size("2 parsecs").is_err());
+    assert!(parse_byte_size("G").is_err());
+}
+
+#[test]
+fn rollback_reactivates_the_newest_retained_version() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-rollback-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    for (version, installed_at) in [("1.0.0", 100), ("1.2.0", 300), ("1.1.0", 200)] {
+        let tool = ToolRef {
+            name: "rg".to_string(),
+            version: version.to_string(),
+        };
+        fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+        fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+                .expect("chmod binary");
+        }
+        let manifest = super::ToolManifest {
+            schema_version: super::MANIFEST_SCHEMA_VERSION,
+            name: tool.name.clone(),
+            version: tool.version.clone(),
+            installed_at_unix_secs: installed_at,
+            source_kind: "download".to_string(),
+            source_detail: "test".to_string(),
+            sha256: "00".repeat(32),
+            size_bytes: 10,
+            target: None,
+            requirement: None,
+            asset_url: None,
+            asset_sha256: None,
+            adhoc: None,
+            unverified: false,
+        };
+        fs::write(
+            home.manifest_path(&tool),
+            serde_json::to_vec(&manifest).expect("serialize manifest"),
+        )
+        .expect("write manifest");
+    }
+    let newest = ToolRef {
+        name: "rg".to_string(),
+        version: "1.2.0".to_string(),
+    };
+    super::state::activate_tool(&home, &newest).expect("activate newest");
+
+    assert_eq!(
+        super::state::stale_versions_to_prune(&home, &newest, 2).expect("stale versions"),
+        vec!["1.0.0".to_string()]
+    );
+    assert_eq!(
+        super::state::rollback_candidate(&home, "rg").expect("rollback candidate"),
+        Some("1.1.0".to_string())
+    );
+
+    let err = super::state::rollback_tool(&home, "rg", Some("9.9.9")).expect_err("unknown version");
+    assert!(err.to_string().contains("retained versions: 1.1.0, 1.0.0"));
+
+    let admin_policy = parse_admin_policy(
+        Path::new("/etc/za/tool-policy.toml"),
+        "[versions]\nrg = \">=1.1\"\n",
+    )
+    .expect("parse admin policy");
+    let err =
+        super::state::rollback_tool_with_policy(&home, "rg", Some("1.0.0"), Some(&admin_policy))
+            .expect_err("admin range refuses 1.0.0");
+    assert!(format!("{err:#}").contains("admin tool policy"), "{err:#}");
+    assert_eq!(
+        super::state::read_current_version(&home, "rg").expect("current version"),
+        Some("1.2.0".to_string())
+    );
+
+    super::state::rollback_tool_with_policy(&home, "rg", None, Some(&admin_policy))
+        .expect("roll back");
+    assert_eq!(
+        super::state::read_current_version(&home, "rg").expect("current version"),
+        Some("1.1.0".to_string())
+    );
+
+    let active = ToolRef {
+        name: "rg".to_string(),
+        version: "1.1.0".to_string(),
+    };
+    let removed = prune_non_active_versions(&home, &active, 2).expect("prune versions");
+    assert_eq!(removed, vec!["1.0.0".to_string()]);
+    assert!(home.version_dir(&newest).exists());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn admin_policy_checks_versions_taken_from_the_store() {
+    let root = std::env::temp_dir().join(format!("za-test-admin-stored-{}", std::process::id()));
+    let home = ToolHome {
+        scope: ToolScope::Global,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let stored = |version: &str| ToolRef {
+        name: "rg".to_string(),
+        version: version.to_string(),
+    };
+
+    let ranged = parse_admin_policy(
+        Path::new("/etc/za/tool-policy.toml"),
+        "[versions]\nrg = \">=14\"\n",
+    )
+    .expect("parse admin policy");
+    ranged
+        .check_installed(&home, &stored("14.1.1"))
+        .expect("stored version inside the range");
+    let err = ranged
+        .check_installed(&home, &stored("13.0.0"))
+        .expect_err("stored version outside the range");
+    assert!(format!("{err:#}").contains("admin tool policy"), "{err:#}");
+
+    let denied = parse_admin_policy(Path::new("/etc/za/tool-policy.toml"), "deny = [\"rg\"]\n")
+        .expect("parse admin policy");
+    let err = denied
+        .check_installed(&home, &stored("14.1.1"))
+        .expect_err("denied tool");
+    assert!(err.to_string().contains("denied"), "{err:#}");
+}
+
+#[test]
+fn history_records_rollback_and_uninstall_events_newest_first() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-history-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    for version in ["1.0.0", "1.1.0"] {
+        let tool = ToolRef {
+            name: "rg".to_string(),
+            version: version.to_string(),
+        };
+        fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+        fs::write(home.install_path(&tool), "#!/bin/sh\n").expect("write binary");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(home.install_path(&tool), fs::Permissions::from_mode(0o755))
+                .expect("chmod binary");
+        }
+    }
+    super::state::activate_tool(
+        &home,
+        &ToolRef {
+            name: "rg".to_string(),
+            version: "1.1.0".to_string(),
+        },
+    )
+    .expect("activate");
+
+    super::state::rollback_tool(&home, "rg", Some("1.0.0")).expect("roll back");
+    uninstall(&home, ToolSpec::from_args("rg", None).expect("spec")).expect("uninstall");
+
+    let history_db = home.history_db_path().expect("history path");
+    assert_eq!(history_db, root.join("history.db"));
+    let project_current = std::path::Path::new("/work/app/.za/tools/current");
+    let project_db = super::history::project_history_db_file(project_current);
+    assert!(project_db.starts_with("app-") && project_db.ends_with(".db"));
+    assert_ne!(
+        project_db,
+        super::history::project_history_db_file(std::path::Path::new(
+            "/other/app/.za/tools/current"
+        ))
+    );
+    let events = super::history::load_events(&history_db, Some("rg"), None).expect("load");
+    let summary = events
+        .iter()
+        .map(|event| {
+            (
+                event.action,
+                event.version.as_deref(),
+                event.previous_version.as_deref(),
+                event.detail.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                super::history::ToolHistoryAction::Uninstall,
+                Some("1.0.0"),
+                None,
+                Some("removed 1.0.0, 1.1.0"),
+            ),
+            (
+                super::history::ToolHistoryAction::Rollback,
+                Some("1.0.0"),
+                Some("1.1.0"),
+                None,
+            ),
+        ]
+    );
+    assert!(
+        super::history::load_events(&history_db, Some("fd"), None)
+            .expect("load other tool")
+            .is_empty()
+    );
+    let future_cutoff = events[0].recorded_at_unix_ms + 60_000;
+    assert!(
+        super::history::load_events(&history_db, None, Some(future_cutoff))
+            .expect("load with cutoff")
+            .is_empty()
+    );
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn pick_release_asset_matches_host_os_arch_and_prefers_musl_archives() {
+    let assets = [
+        "tool-v1.2.0-x86_64-unknown-linux-gnu.tar.gz",
+        "tool-v1.2.0-x86_64-unknown-linux-musl.tar.gz",
+        "tool-v1.2.0-x86_64-unknown-linux-musl.tar.gz.sha256",
+        "tool-v1.2.0-aarch64-unknown-linux-musl.tar.gz",
+        "tool-v1.2.0-x86_64-apple-darwin.tar.gz",
+        "tool-v1.2.0-x86_64-pc-windows-msvc.zip",
+        "tool_1.2.0_amd64.deb",
+        "checksums.txt",
+    ];
+    let host = ToolTarget {
+        os: "linux",
+        arch: "x86_64",
+        libc: None,
+    };
+    assert_eq!(
+        source::pick_release_asset_for_target(assets, host).expect("host asset"),
+        "tool-v1.2.0-x86_64-unknown-linux-musl.tar.gz"
+    );
+    let gnu = ToolTarget {
+        libc: Some(ToolLibc::Gnu),
+        ..host
+    };
+    assert_eq!(
+        source::pick_release_asset_for_target(assets, gnu).expect("gnu asset"),
+        "tool-v1.2.0-x86_64-unknown-linux-gnu.tar.gz"
+    );
+    let mac = ToolTarget {
+        os: "macos",
+        arch: "aarch64",
+        libc: None,
+    };
+    assert_eq!(
+        source::pick_release_asset_for_target(["tool_darwin_arm64", "tool_linux_arm64"], mac)
+            .expect("bare binary"),
+        "tool_darwin_arm64"
+    );
+
+    assert_eq!(
+        source::pick_release_asset_for_target(
+            [
+                "tool-x86_64-unknown-linux-gnu.zip",
+                "tool-x86_64-unknown-linux-gnu.tar.gz",
+            ],
+            gnu
+        )
+        .expect("tarball over zip"),
+        "tool-x86_64-unknown-linux-gnu.tar.gz"
+    );
+    assert_eq!(
+        source::pick_release_asset_for_target(
+            [
+                "tool-x86_64-linux-musl.zip",
+                "tool-x86_64-linux-musl.tar.xz",
+                "tool-x86_64-linux-gnu.tar.gz",
+            ],
+            host
+        )
+        .expect("musl xz tarball"),
+        "tool-x86_64-linux-musl.tar.xz"
+    );
+    assert_eq!(
+        source::pick_release_asset_for_target(["tool-linux-amd64", "other-linux-x64"], host)
+            .expect("shortest name"),
+        "other-linux-x64"
+    );
+    let err = source::pick_release_asset_for_target(["tool-windows-amd64.zip"], host)
+        .expect_err("no host asset");
+    assert!(err.to_string().contains("no release asset matches"));
+}
+
+#[test]
+fn adhoc_repo_and_tag_prefix_parse() {
+    assert_eq!(
+        parse_adhoc_repo("sharkdp/bat").expect("owner/repo"),
+        ("sharkdp".to_string(), "bat".to_string())
+    );
+    assert_eq!(
+        parse_adhoc_repo("https://github.com/cli/cli.git").expect("url"),
+        ("cli".to_string(), "cli".to_string())
+    );
+    assert!(parse_adhoc_repo("bat").is_err());
+    assert!(parse_adhoc_repo("a/b/c").is_err());
+
+    assert_eq!(release_tag_prefix("v0.24.0").expect("v tag"), "v");
+    assert_eq!(release_tag_prefix("14.1.0").expect("bare tag"), "");
+    assert_eq!(release_tag_prefix("cli-0.4.0").expect("named tag"), "cli-");
+    assert!(release_tag_prefix("nightly").is_err());
+}
+
+#[test]
+fn installed_adhoc_tools_are_registered_from_their_manifest() {
+    let root = std::env::temp_dir().join(format!(
+        "za-test-adhoc-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let tool = ToolRef {
+        name: format!("za-test-adhoc-{}", std::process::id()),
+        version: "0.3.1".to_string(),
+    };
+    let source = AdhocToolSource {
+        owner: "example".to_string(),
+        repo: "Za-Test-Adhoc".to_string(),
+        tag_prefix: "v".to_string(),
+        require_digest: true,
+    };
+    fs::create_dir_all(home.version_dir(&tool)).expect("create version dir");
+    fs::create_dir_all(&home.current_dir).expect("create current dir");
+    fs::write(home.current_file(&tool.name), "0.3.1\n").expect("write current version");
+    let manifest = super::ToolManifest {
+        schema_version: super::MANIFEST_SCHEMA_VERSION,
+        name: tool.name.clone(),
+        version: tool.version.clone(),
+        installed_at_unix_secs: 0,
+        source_kind: "download".to_string(),
+        source_detail: "test".to_string(),
+        sha256: "00".repeat(32),
+        size_bytes: 10,
+        target: None,
+        requirement: None,
+        asset_url: None,
+        asset_sha256: None,
+        adhoc: Some(source.clone()),
+        unverified: false,
+    };
+    fs::write(
+        home.manifest_path(&tool),
+        serde_json::to_vec(&manifest).expect("serialize manifest"),
+    )
+    .expect("write manifest");
+
+    assert!(find_tool_policy(&tool.name).is_none());
+    register_installed_adhoc_tools(&home).expect("register ad hoc tools");
+    let release = find_tool_policy(&tool.name)
+        .and_then(|policy| policy.release)
+        .expect("ad hoc policy");
+    assert_eq!(release.owner, "example");
+    assert_eq!(release.tag_prefix, "v");
+    assert!(matches!(
+        release.verification,
+        ReleaseVerification::AvailableDigest { required: true }
+    ));
+    assert_eq!(adhoc_source(&tool.name), Some(source));
+    assert_eq!(adhoc_source("rg"), None);
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn registry_expose_entries_parse_package_binaries_and_docker_plugins() {
+    let raw = r#"
+[tools.buildkit]
+owner = "moby"
+repo = "buildkit"
+asset = "buildkit-v{version}.{os}-{arch}.tar.gz"
+layout = "package"
+entry = "bin/buildctl"
+expose = [
+  { path = "bin/buildctl" },
+  { path = "bin/buildkitd", name = "buildkit-daemon" },
+  { path = "bin/docker-buildx", placement = "docker-cli-plugin" },
+]
+"#;
+    let policies = parse_tool_registries(
+        &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    let exposed = policies[0]
+        .exposed
+        .iter()
+        .map(|entry| (entry.relpath, entry.placement))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exposed,
+        vec![
+            ("bin/buildctl", EntryPlacement::Bin("buildctl")),
+            ("bin/buildkitd", EntryPlacement::Bin("buildkit-daemon")),
+            (
+                "bin/docker-buildx",
+                EntryPlacement::DockerCliPlugin("docker-buildx")
+            ),
+        ]
+    );
+
+    let cases = [
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}"
+expose = [{ path = "bin/foo" }]
+"#,
+            "only valid with `layout = \"package\"`",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}"
+expose = [{}]
+"#,
+            "must not repeat the tool's own `bin/foo` link",
+        ),
+        (
+            r#"
+[tools.foo]
+owner = "me"
+repo = "foo"
+asset = "foo-{version}.tar.gz"
+layout = "package"
+entry = "foo"
+expose = [{ path = "../foo" }]
+"#,
+            "invalid `expose.path`",
+        ),
+    ];
+    for (raw, expected) in cases {
+        let err = parse_tool_registries(
+            &[(ToolRegistryOrigin::User, Path::new("tools.toml"), raw)],
+            tool_policies(),
+        )
+        .expect_err("invalid expose entry");
+        assert!(
+            format!("{err:#}").contains(expected),
+            "unexpected error: {err:#}"
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn package_exposed_entries_follow_activation_and_uninstall() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = std::env::temp_dir().join(format!(
+        "za-test-exposed-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos()
+    ));
+    let home = ToolHome {
+        scope: ToolScope::User,
+        store_dir: root.join("store"),
+        current_dir: root.join("current"),
+        bin_dir: root.join("bin"),
+    };
+    let name = format!("za-test-multibin-{}", std::process::id());
+    let raw = format!(
+        r#"
+[tools.{name}]
+owner = "me"
+repo = "multibin"
+asset = "multibin-{{version}}.tar.gz"
+layout = "package"
+entry = "bin/alpha"
+expose = [{{ path = "bin/alpha" }}, {{ path = "bin/beta" }}, {{ path = "bin/gamma" }}]
+"#
+    );
+    let policies = parse_tool_registries(
+        &[(ToolRegistryOrigin::User, Path::new("tools.toml"), &raw)],
+        tool_policies(),
+    )
+    .expect("parse registry");
+    register_adhoc_policy(policies[0]);
+
+    let tool = ToolRef {
+        name: name.clone(),
+        version: "1.0.0".to_string(),
+    };
+    let payload_bin = home.version_dir(&tool).join("payload/bin");
+    fs::create_dir_all(&payload_bin).expect("create payload");
+    for binary in ["alpha", "beta", "gamma"] {
+        let path = payload_bin.join(binary);
+        fs::write(&path, "#!/bin/sh\n").expect("write binary");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod binary");
+    }
+    fs::create_dir_all(&home.bin_dir).expect("create bin dir");
+    fs::write(home.bin_dir.join("gamma"), "unmanaged\n").expect("write unmanaged gamma");
+
+    super::state::activate_tool(&home, &tool).expect("activate package");
+    for binary in ["alpha", "beta"] {
+        let link = fs::read_link(home.bin_dir.join(binary)).expect("exposed link");
+        assert!(link.ends_with(format!("payload/bin/{binary}")), "{link:?}");
+    }
+    assert_eq!(
+        fs::read_to_string(home.bin_dir.join("gamma")).expect("read gamma"),
+        "unmanaged\n"
+    );
+    assert!(
+        super::state::stale_exposed_links(&home, &tool)
+            .expect("check exposed links")
+            .is_empty()
+    );
+    fs::remove_file(home.bin_dir.join("beta")).expect("remove beta link");
+    assert_eq!(
+        super::state::stale_exposed_links(&home, &tool).expect("check exposed links"),
+        vec![home.bin_dir.join("beta")]
+    );
+    super::state::activate_tool(&home, &tool).expect("re-link package");
+
+    let broken = ToolRef {
+        name: name.clone(),
+        version: "2.0.0".to_string(),
+    };
+    let broken_bin = home.version_dir(&broken).join("payload/bin");
+    fs::create_dir_all(&broken_bin).expect("create broken payload");
+    for binary in ["alpha", "gamma"] {
+        let path = broken_bin.join(binary);
+        fs::write(&path, "#!/bin/sh\n").expect("write binary");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod binary");
+    }
+    super::state::activate_tool(&home, &broken).expect_err("missing beta must fail activation");
+    for binary in ["alpha", "beta"] {
+        let link = fs::read_link(home.bin_dir.join(binary)).expect("exposed link kept");
+        assert!(link.to_string_lossy().contains("1.0.0"), "{link:?}");
+    }
+    let leftovers = fs::read_dir(&home.bin_dir)
+        .expect("read bin dir")
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().contains("tmp-link"))
+        .count();
+    assert_eq!(leftovers, 0);
+
+    uninstall(&home, ToolSpec::from_args(&name, None).expect("spec")).expect("uninstall");
+    assert!(fs::symlink_metadata(home.bin_dir.join("alpha")).is_err());
+    assert!(fs::symlink_metadata(home.bin_dir.join("beta")).is_err());
+    assert!(home.bin_dir.join("gamma").exists());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn admin_policy_refuses_denied_unlisted_and_untrusted_tools() {
+    let path = Path::new("/etc/za/tool-policy.toml");
+    let policy = parse_admin_policy(
+        path,
+        r#"
+allow = ["ripgrep", "fd", "codex"]
+deny = ["codex-cli"]
+sources = ["catalog", "adopted"]
+
+[versions]
+ripgrep = ">=14, <15"
+"#,
+    )
+    .expect("parse admin policy");
+
+    policy
+        .check_tool("rg", Some(AdminToolSource::Catalog))
+        .expect("rg allowed");
+    policy
+        .check_tool("fd", Some(AdminToolSource::Adopted))
+        .expect("adopted fd allowed");
+
+    let denied = policy
+        .check_tool("codex", Some(AdminToolSource::Catalog))
+        .expect_err("deny wins over allow");
+    assert!(denied.to_string().contains("denied"), "{denied:#}");
+    assert!(denied.to_string().contains("/etc/za/tool-policy.toml"));
+    let unlisted = policy
+        .check_tool("just", Some(AdminToolSource::Catalog))
+        .expect_err("just is not allowed");
+    assert!(unlisted.to_string().contains("allow list"), "{unlisted:#}");
+    let adhoc = policy
+        .check_tool("fd", Some(AdminToolSource::Adhoc))
+        .expect_err("ad hoc source is not allowed");
+    assert!(
+        adhoc.to_string().contains("adhoc source")
+            && adhoc.to_string().contains("catalog, adopted"),
+        "{adhoc:#}"
+    );
+
+    policy.check_version("rg", "14.1.1").expect("in range");
+    policy
+        .check_version("fd", "99.0.0")
+        .expect("fd has no range");
+    let out_of_range = policy
+        .check_version("rg", "15.0.0")
+        .expect_err("rg 15 is out of range");
+    assert!(
+        format!("{out_of_range:#}").contains("does not satisfy"),
+        "{out_of_range:#}"
+    );
+
+    assert!(
+        policy
+            .violations("rg", "14.0.0", Some(AdminToolSource::Catalog))
+            .is_empty()
+    );
+    let violations = policy.violations("just", "13.0.0", Some(AdminToolSource::Adhoc));
+    assert_eq!(violations.len(), 1, "{violations:?}");
+    assert!(violations[0].starts_with("out of admin policy:"));
+    assert_eq!(
+        policy
+            .violations("rg", "15.0.0", Some(AdminToolSource::Adhoc))
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn admin_policy_rejects_unknown_keys_and_invalid_ranges() {
+    let path = Path::new("/etc/za/tool-policy.toml");
+    let unknown = parse_admin_policy(path, "alow = [\"rg\"]\n").expect_err("typo is rejected");
+    assert!(format!("{unknown:#}").contains("invalid admin tool policy"));
+
+    let source = parse_admin_policy(path, "sources = [\"mirror\"]\n").expect_err("bad source");
+    assert!(format!("{source:#}").contains("invalid admin tool policy"));
+
+    let range = parse_admin_policy(path, "[versions]\nrg = \"fourteen\"\n")
+        .expect_err("bad range is rejected");
+    assert!(format!("{range:#}").contains("`rg`"), "{range:#}");
+
+    let open = parse_admin_policy(path, "").expect("empty policy");
+    open.check_tool("just", None)
+        .expect("empty policy allows everything");
+}
+
+#[test]
+fn admin_version_range_narrows_requested_resolution() {
+    let requested = semver::VersionReq::parse(">=13").expect("requested");
+    let admin = semver::VersionReq::parse("<15").expect("admin");
+
+    let merged = resolution_requirement(Some(&requested), Some(&admin)).expect("merged");
+    assert!(merged.matches(&semver::Version::new(14, 1, 0)));
+    assert!(!merged.matches(&semver::Version::new(15, 0, 0)));
+    assert!(!merged.matches(&semver::Version::new(12, 0, 0)));
+
+    assert_eq!(
+        resolution_requirement(None, Some(&admin)),
+        Some(admin.clone())
+    );
+    assert_eq!(
+        resolution_requirement(Some(&requested), None),
+        Some(requested)
+    );
+    assert_eq!(resolution_requirement(None, None), None);
+}
+
+#[test]
+fn admin_version_range_defers_batch_updates_to_in_range_resolution() {
+    use super::batch::{resolve_batch_latest_lookup, resolve_batch_tool_spec};
+
+    let policy = parse_admin_policy(
+        Path::new("/etc/za/tool-policy.toml"),
+        "[versions]\nripgrep = \">=14, <15\"\n",
+    )
+    .expect("parse admin policy");
+    let spec = ToolSpec::from_args("rg", None).expect("spec");
+
+    // Upstream latest is outside the admin range; the batch must not pin it.
+    let lookup = std::collections::HashMap::from([(
+        "rg".to_string(),
+        LatestCheck::Latest("15.0.0".to_string()),
+    )]);
+    let deferred =
+        resolve_batch_tool_spec(&spec, Some(&lookup), Some(&policy)).expect("deferred spec");
+    assert_eq!(deferred.version, None);
+    assert_eq!(deferred.requirement, None);
+    assert!(
+        resolve_batch_latest_lookup(
+            std::slice::from_ref(&spec),
+            ToolUpdateChannel::Stable,
+            Some(&policy)
+        )
+        .expect("lookup")
+        .is_none()
+    );
+    assert_eq!(
+        resolution_requirement(None, policy.version_requirement("rg")),
+        Some(semver::VersionReq::parse(">=14, <15").expect("range"))
+    );
+
+    let pinned = resolve_batch_tool_spec(&spec, Some(&lookup), None).expect("pinned spec");
+    assert_eq!(pinned.version.as_deref(), Some("15.0.0"));
+}
+
+#[test]
+fn admin_tool_source_follows_tool_origin() {
+    assert_eq!(
+        AdminToolSource::of_tool("rg"),
+        Some(AdminToolSource::Catalog)
+    );
+    assert_eq!(AdminToolSource::of_tool("za-test-no-such-tool"), None);
+}
@@ -0,0 +1,203 @@
+//! Release-side verification for assets the GitHub API does not publish digests for: checksum
+//! files shipped next to the asset and minisign signatures checked against a pinned key.
+
+use super::*;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use blake2::{Blake2b512, Digest};
+use graviola::signing::eddsa::Ed25519VerifyingKey;
+
+const MINISIGN_ALG_LEGACY: [u8; 2] = *b"Ed";
+const MINISIGN_ALG_PREHASHED: [u8; 2] = *b"ED";
+const MINISIGN_PUBLIC_KEY_LEN: usize = 2 + 8 + 32;
+const MINISIGN_SIGNATURE_LEN: usize = 2 + 8 + 64;
+const MINISIGN_TRUSTED_COMMENT_PREFIX: &str = "trusted comment: ";
+pub(super) const MINISIGN_SIGNATURE_SUFFIX: &str = ".minisig";
+
+/// Per-asset checksum files first, then release-wide manifests.
+const CHECKSUM_ASSET_SUFFIXES: [&str; 3] = [".sha256", ".sha256sum", ".sha256.txt"];
+const CHECKSUM_MANIFEST_NAMES: [&str; 4] = [
+    "SHA256SUMS",
+    "SHA256SUMS.txt",
+    "sha256sums.txt",
+    "checksums.txt",
+];
+
+/// Picks the release asset most likely to carry the SHA-256 of `asset_name`.
+pub(super) fn find_checksum_asset_name<'a>(
+    asset_name: &str,
+    available: impl IntoIterator<Item = &'a str> + Clone,
+) -> Option<&'a str> {
+    let per_asset = CHECKSUM_ASSET_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{asset_name}{suffix}"));
+    let exact = per_asset.chain(CHECKSUM_MANIFEST_NAMES.iter().map(|name| name.to_string()));
+    for candidate in exact {
+        if let Some(found) = available
+            .clone()
+            .into_iter()
+            .find(|name| *name == candidate)
+        {
+            return Some(found);
+        }
+    }
+    // Fall back to prefixed manifests such as `cross_0.2.5_checksums.txt`.
+    available.into_iter().find(|name| {
+        let lower = name.to_ascii_lowercase();
+        lower.ends_with("checksums.txt") || lower.ends_with("sha256sums")
+    })
+}
+
+/// Extracts the SHA-256 for `asset_name` from GNU (`<hex>  <name>`), BSD
+/// (`SHA256 (<name>) = <hex>`) or single-digest checksum files.
+pub(super) fn parse_checksum_file(content: &str, asset_name: &str) -> Result<String> {
+    let lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>();
+    for line in &lines {
+        if let Some(rest) = line.strip_prefix("SHA256 (")
+            && let Some((name, digest)) = rest.split_once(") = ")
+        {
+            if name == asset_name && is_sha256_hex(digest.trim()) {
+                return Ok(digest.trim().to_ascii_lowercase());
+            }
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(digest), Some(name)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let name = name.trim_start_matches('*').trim_start_matches("./");
+        if name == asset_name && is_sha256_hex(digest) {
+            return Ok(digest.to_ascii_lowercase());
+        }
+    }
+    if let [line] = lines.as_slice() {
+        let digest = line.split_whitespace().next().unwrap_or_default();
+        if is_sha256_hex(digest) {
+            return Ok(digest.to_ascii_lowercase());
+        }
+    }
+    bail!("checksum file does not list a sha256 for `{asset_name}`")
+}
+
+fn is_sha256_hex(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Minisign public key as printed by `minisign -G`, e.g. `RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct MinisignPublicKey {
+    key_id: [u8; 8],
+    key: [u8; 32],
+}
+
+impl MinisignPublicKey {
+    pub(super) fn parse(raw: &str) -> Result<Self> {
+        let encoded = raw
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty() && !line.starts_with("untrusted comment:"))
+            .ok_or_else(|| anyhow!("minisign public key is empty"))?;
+        let bytes = BASE64
+            .decode(encoded)
+            .context("decode minisign public key")?;
+        if bytes.len() != MINISIGN_PUBLIC_KEY_LEN || bytes[..2] != MINISIGN_ALG_LEGACY {
+            bail!("`{encoded}` is not an Ed25519 minisign public key");
+        }
+        let mut key_id = [0u8; 8];
+        key_id.copy_from_slice(&bytes[2..10]);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&bytes[10..]);
+        Ok(Self { key_id, key })
+    }
+
+    pub(super) fn key_id_hex(self) -> String {
+        format!("{:016X}", u64::from_le_bytes(self.key_id))
+    }
+}
+
+/// Checks a `.minisig` file over `path`, including the signature over its trusted comment.
+pub(super) fn verify_minisign(
+    public_key: MinisignPublicKey,
+    signature_file: &str,
+    path: &Path,
+) -> Result<()> {
+    let mut lines = signature_file
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let _untrusted = lines
+        .next()
+        .filter(|line| line.starts_with("untrusted comment:"))
+        .ok_or_else(|| anyhow!("minisign signature is missing its untrusted comment"))?;
+    let signature = lines
+        .next()
+        .ok_or_else(|| anyhow!("minisign signature is missing the signature line"))
+        .and_then(|line| BASE64.decode(line).context("decode minisign signature"))?;
+    let trusted_comment = lines
+        .next()
+        .and_then(|line| line.strip_prefix(MINISIGN_TRUSTED_COMMENT_PREFIX))
+        .ok_or_else(|| anyhow!("minisign signature is missing its trusted comment"))?;
+    let global_signature = lines
+        .next()
+        .ok_or_else(|| anyhow!("minisign signature is missing the global signature"))
+        .and_then(|line| {
+            BASE64
+                .decode(line)
+                .context("decode minisign global signature")
+        })?;
+    if signature.len() != MINISIGN_SIGNATURE_LEN {
+        bail!(
+            "minisign signature has unexpected length {}",
+            signature.len()
+        );
+    }
+    let (algorithm, rest) = signature.split_at(2);
+    let (key_id, signature_bytes) = rest.split_at(8);
+    if key_id != public_key.key_id {
+        bail!(
+            "minisign signature was made with key {:016X}, expected {}",
+            u64::from_le_bytes(key_id.try_into().expect("8-byte key id")),
+            public_key.key_id_hex()
+        );
+    }
+
+    let verifying_key = Ed25519VerifyingKey::from_bytes(&public_key.key)
+        .map_err(|err| anyhow!("invalid minisign public key: {err:?}"))?;
+    let message = if algorithm == MINISIGN_ALG_PREHASHED {
+        blake2b512_file(path)?.to_vec()
+    } else if algorithm == MINISIGN_ALG_LEGACY {
+        fs::read(path).with_context(|| format!("read {}", path.display()))?
+    } else {
+        bail!("unsupported minisign signature algorithm");
+    };
+    verifying_key
+        .verify(signature_bytes, &message)
+        .map_err(|_| anyhow!("minisign signature does not match {}", path.display()))?;
+
+    let mut signed_comment = signature_bytes.to_vec();
+    signed_comment.extend_from_slice(trusted_comment.as_bytes());
+    verifying_key
+        .verify(&global_signature, &signed_comment)
+        .map_err(|_| anyhow!("minisign trusted comment signature is invalid"))?;
+    Ok(())
+}
+
+/// Unkeyed BLAKE2b-512 of a file, the prehash used by minisign `ED` signatures.
+pub(super) fn blake2b512_file(path: &Path) -> Result<[u8; 64]> {
+    let mut file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+    let mut hasher = Blake2b512::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .with_context(|| format!("read {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().into())
+}
@@ -18,6 +18,7 @@ use ratatui::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     env, fs,
     io::{self, IsTerminal},
     path::{Path, PathBuf},
@@ -27,8 +28,9 @@ use std::{
 const CONFIG_DIR_NAME: &str = "za";
 const CONFIG_FILE_NAME: &str = "config.toml";
 
-const CONFIG_MODULES: [ConfigModule; 6] = [
+const CONFIG_MODULES: [ConfigModule; 7] = [
     ConfigModule::Auth,
+    ConfigModule::Github,
     ConfigModule::Proxy,
     ConfigModule::Run,
     ConfigModule::Tool,
@@ -36,13 +38,25 @@ const CONFIG_MODULES: [ConfigModule; 6] = [
     ConfigModule::Ide,
 ];
 
-const CONFIG_ITEMS: [ConfigItem; 19] = [
+const CONFIG_ITEMS: [ConfigItem; 24] = [
     ConfigItem {
         key: ConfigKey::GithubToken,
         module: ConfigModule::Auth,
         label: "github-token",
         secret: true,
     },
+    ConfigItem {
+        key: ConfigKey::GithubApiBase,
+        module: ConfigModule::Github,
+        label: "api-base",
+        secret: false,
+    },
+    ConfigItem {
+        key: ConfigKey::GithubDownloadMirror,
+        module: ConfigModule::Github,
+        label: "download-mirror",
+        secret: false,
+    },
     ConfigItem {
         key: ConfigKey::ProxyHttp,
         module: ConfigModule::Proxy,
@@ -115,6 +129,24 @@ const CONFIG_ITEMS: [ConfigItem; 19] = [
         label: "no-proxy",
         secret: false,
     },
+    ConfigItem {
+        key: ConfigKey::ToolRetainVersions,
+        module: ConfigModule::Tool,
+        label: "retain-versions",
+        secret: false,
+    },
+    ConfigItem {
+        key: ConfigKey::ToolMinReleaseAgeDays,
+        module: ConfigModule::Tool,
+        label: "min-release-age-days",
+        secret: false,
+    },
+    ConfigItem {
+        key: ConfigKey::ToolCacheMaxSize,
+        module: ConfigModule::Tool,
+        label: "cache-max-size",
+        secret: false,
+    },
     ConfigItem {
         key: ConfigKey::UpdateHttp,
         module: ConfigModule::Update,
@@ -164,6 +196,7 @@ struct ConfigItem {
 #[derive(Clone, Copy, Eq, PartialEq)]
 enum ConfigModule {
     Auth,
+    Github,
     Proxy,
     Run,
     Tool,
@@ -257,11 +290,13 @@ struct ZaConfig {
     #[serde(default)]
     auth: AuthConfig,
     #[serde(default)]
+    github: GithubConfig,
+    #[serde(default)]
     proxy: ProxyConfig,
     #[serde(default)]
     run: ProxyConfig,
     #[serde(default)]
-    tool: ProxyConfig,
+    tool: ToolConfig,
     #[serde(default)]
     update: ProxyConfig,
     #[serde(default)]
@@ -274,6 +309,14 @@ struct AuthConfig {
     github_token: Option<String>,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GithubConfig {
+    #[serde(default)]
+    api_base: Option<String>,
+    #[serde(default)]
+    download_mirror: Option<String>,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 struct ProxyConfig {
     #[serde(default)]
@@ -286,6 +329,23 @@ struct ProxyConfig {
     no_proxy: Option<String>,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ToolConfig {
+    #[serde(flatten)]
+    proxy: ProxyConfig,
+    #[serde(default)]
+    retain_versions: Option<String>,
+    #[serde(default)]
+    min_release_age_days: Option<String>,
+    /// Download cache size cap such as `512M` or `2G`.
+    #[serde(default)]
+    cache_max_size: Option<String>,
+    /// Per-tool overrides of `min_release_age_days`, set with
+    /// `za config set tool-min-release-age-days <days> --tool <name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    min_release_age_overrides: BTreeMap<String, u64>,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 struct IdeConfig {
     #[serde(default)]
@@ -310,6 +370,13 @@ pub struct ProxyOverrides {
 
 pub type RunProxyOverrides = ProxyOverrides;
 
+const GITHUB_API_BASE_DEFAULT: &str = "https://api.github.com";
+const GITHUB_WEB_BASE: &str = "https://github.com";
+const GITHUB_API_BASE_ENV: &str = "ZA_GITHUB_API_BASE";
+const GITHUB_DOWNLOAD_MIRROR_ENV: &str = "ZA_GITHUB_DOWNLOAD_MIRROR";
+pub const TOOL_RETAIN_VERSIONS_DEFAULT: usize = 2;
+/// `tool.retain_versions` value that turns pruning off.
+pub const TOOL_RETAIN_VERSIONS_ALL: &str = "all";
 pub const IDE_MAX_PER_PROJECT_DEFAULT: usize = 1;
 pub const IDE_ORPHAN_TTL_MINUTES_DEFAULT: u64 = 30;
 
@@ -336,9 +403,18 @@ pub fn run(cmd: Option<ConfigCommands>) -> Result<()> {
             println!("{}", path.display());
             Ok(())
         }
-        Some(ConfigCommands::Set { key, value }) => set_value(key, value),
-        Some(ConfigCommands::Get { key, raw }) => get_value(key, raw),
-        Some(ConfigCommands::Unset { key }) => unset_value(key),
+        Some(ConfigCommands::Set { key, value, tool }) => match tool {
+            Some(tool) => set_tool_override(key, &tool, Some(value)),
+            None => set_value(key, value),
+        },
+        Some(ConfigCommands::Get { key, raw, tool }) => match tool {
+            Some(tool) => get_tool_override(key, &tool),
+            None => get_value(key, raw),
+        },
+        Some(ConfigCommands::Unset { key, tool }) => match tool {
+            Some(tool) => set_tool_override(key, &tool, None),
+            None => unset_value(key),
+        },
     }
 }
 
@@ -350,6 +426,57 @@ pub fn load_github_token() -> Result<Option<String>> {
     Ok(cfg.auth.github_token.and_then(normalize_token))
 }
 
+/// GitHub REST API root, e.g. `https://ghe.example.com/api/v3` for GitHub Enterprise Server.
+///
+/// `ZA_GITHUB_API_BASE` overrides `github.api_base`.
+pub fn load_github_api_base() -> Result<String> {
+    let configured = match env::var(GITHUB_API_BASE_ENV).ok().and_then(normalize_value) {
+        Some(value) => Some(value),
+        None => load_github_config()?.api_base.and_then(normalize_value),
+    };
+    match configured {
+        Some(raw) => normalize_base_url(&raw, "github.api_base"),
+        None => Ok(GITHUB_API_BASE_DEFAULT.to_string()),
+    }
+}
+
+/// Rewrites `https://github.com/...` download URLs onto the configured mirror, if any.
+///
+/// `ZA_GITHUB_DOWNLOAD_MIRROR` overrides `github.download_mirror`.
+pub fn github_download_url(url: &str) -> Result<String> {
+    let configured = match env::var(GITHUB_DOWNLOAD_MIRROR_ENV)
+        .ok()
+        .and_then(normalize_value)
+    {
+        Some(value) => Some(value),
+        None => load_github_config()?
+            .download_mirror
+            .and_then(normalize_value),
+    };
+    let mirror = configured
+        .map(|raw| normalize_base_url(&raw, "github.download_mirror"))
+        .transpose()?;
+    Ok(rewrite_github_download_url(url, mirror.as_deref()))
+}
+
+fn rewrite_github_download_url(url: &str, mirror: Option<&str>) -> String {
+    match (
+        mirror,
+        url.strip_prefix(GITHUB_WEB_BASE)
+            .filter(|rest| rest.starts_with('/')),
+    ) {
+        (Some(mirror), Some(rest)) => format!("{mirror}{rest}"),
+        _ => url.to_string(),
+    }
+}
+
+fn load_github_config() -> Result<GithubConfig> {
+    let Some(path) = maybe_config_path() else {
+        return Ok(GithubConfig::default());
+    };
+    Ok(read_config(&path)?.github)
+}
+
 pub fn load_run_proxy_overrides() -> Result<RunProxyOverrides> {
     load_proxy_overrides(ProxyScope::Run)
 }
@@ -362,7 +489,7 @@ pub fn load_proxy_overrides(scope: ProxyScope) -> Result<ProxyOverrides> {
     let global = normalize_proxy_config(&cfg.proxy);
     let scoped = match scope {
         ProxyScope::Run => normalize_proxy_config(&cfg.run),
-        ProxyScope::Tool => normalize_proxy_config(&cfg.tool),
+        ProxyScope::Tool => normalize_proxy_config(&cfg.tool.proxy),
         ProxyScope::Update => normalize_proxy_config(&cfg.update),
         ProxyScope::Deps => ProxyOverrides::default(),
         ProxyScope::Ci => ProxyOverrides::default(),
@@ -370,6 +497,107 @@ pub fn load_proxy_overrides(scope: ProxyScope) -> Result<ProxyOverrides> {
     Ok(merge_proxy_overrides(&global, &scoped))
 }
 
+/// Number of versions per tool, including the active one, kept in the store after activation.
+/// Defaults to the active and the previous version; `None` (set to `all`) disables pruning.
+pub fn load_tool_retain_versions() -> Result<Option<usize>> {
+    let Some(path) = maybe_config_path() else {
+        return Ok(Some(TOOL_RETAIN_VERSIONS_DEFAULT));
+    };
+    let cfg = read_config(&path)?;
+    parse_tool_retain_versions(cfg.tool.retain_versions.as_deref())
+}
+
+fn parse_tool_retain_versions(raw: Option<&str>) -> Result<Option<usize>> {
+    if raw.is_some_and(|raw| raw.trim().eq_ignore_ascii_case(TOOL_RETAIN_VERSIONS_ALL)) {
+        return Ok(None);
+    }
+    parse_positive_usize_or_default(raw, TOOL_RETAIN_VERSIONS_DEFAULT, "tool.retain_versions")
+        .map(Some)
+        .context("`tool.retain_versions` takes a positive count or `all`")
+}
+
+/// Days a release must have been public before `za tool update`/`outdated` consider it.
+/// A per-tool override wins over the global value; 0 disables the cooldown.
+pub fn load_tool_min_release_age_days(tool: &str) -> Result<u64> {
+    let Some(path) = maybe_config_path() else {
+        return Ok(0);
+    };
+    let cfg = read_config(&path)?;
+    tool_min_release_age_days(&cfg.tool, tool)
+}
+
+fn tool_min_release_age_days(cfg: &ToolConfig, tool: &str) -> Result<u64> {
+    if let Some(days) = cfg.min_release_age_overrides.get(tool) {
+        return Ok(*days);
+    }
+    parse_u64_or_default(
+        cfg.min_release_age_days.as_deref(),
+        0,
+        "tool.min_release_age_days",
+    )
+}
+
+/// Sets or, with `None`, removes the `[tool.min_release_age_overrides]` entry of `tool`.
+fn set_tool_min_release_age_override(
+    cfg: &mut ToolConfig,
+    tool: &str,
+    value: Option<&str>,
+) -> Result<()> {
+    let tool = crate::command::tool::canonical_tool_name(tool.trim());
+    if tool.is_empty() {
+        bail!("tool name cannot be empty");
+    }
+    match value {
+        Some(value) => {
+            let days = value.trim().parse::<u64>().with_context(|| {
+                format!("parse `tool.min_release_age_overrides.{tool}` as non-negative integer")
+            })?;
+            cfg.min_release_age_overrides.insert(tool, days);
+        }
+        None => {
+            cfg.min_release_age_overrides.remove(&tool);
+        }
+    }
+    Ok(())
+}
+
+/// Download cache size cap from `tool.cache_max_size`, if set.
+pub fn load_tool_cache_max_bytes() -> Result<Option<u64>> {
+    let Some(path) = maybe_config_path() else {
+        return Ok(None);
+    };
+    let cfg = read_config(&path)?;
+    cfg.tool
+        .cache_max_size
+        .as_deref()
+        .and_then(|raw| normalize_value(raw.to_string()))
+        .map(|raw| parse_byte_size(&raw).context("parse `tool.cache_max_size`"))
+        .transpose()
+}
+
+/// Parses sizes such as `512M` or `2G`; units are binary multiples.
+pub fn parse_byte_size(raw: &str) -> Result<u64> {
+    let trimmed = raw.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (digits, unit) = trimmed.split_at(split);
+    let value = digits
+        .parse::<u64>()
+        .with_context(|| format!("invalid size `{raw}`; expected e.g. `512M` or `2G`"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1024,
+        "M" | "MB" | "MIB" => 1024 * 1024,
+        "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+        "T" | "TB" | "TIB" => 1024 * 1024 * 1024 * 1024,
+        _ => bail!("invalid size unit in `{raw}`; expected one of B, K, M, G, T"),
+    };
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| anyhow!("size `{raw}` is too large"))
+}
+
 pub fn load_ide_jetbrains_policy() -> Result<IdeJetbrainsPolicy> {
     let Some(path) = maybe_config_path() else {
         return Ok(IdeJetbrainsPolicy {
@@ -404,6 +632,8 @@ fn get_value(key: ConfigKey, raw: bool) -> Result<()> {
     let cfg = read_config(&path)?;
     let value = match key {
         ConfigKey::GithubToken => cfg.auth.github_token,
+        ConfigKey::GithubApiBase => cfg.github.api_base,
+        ConfigKey::GithubDownloadMirror => cfg.github.download_mirror,
         ConfigKey::ProxyHttp => cfg.proxy.http_proxy,
         ConfigKey::ProxyHttps => cfg.proxy.https_proxy,
         ConfigKey::ProxyAll => cfg.proxy.all_proxy,
@@ -412,10 +642,13 @@ fn get_value(key: ConfigKey, raw: bool) -> Result<()> {
         ConfigKey::RunHttps => cfg.run.https_proxy,
         ConfigKey::RunAll => cfg.run.all_proxy,
         ConfigKey::RunNoProxy => cfg.run.no_proxy,
-        ConfigKey::ToolHttp => cfg.tool.http_proxy,
-        ConfigKey::ToolHttps => cfg.tool.https_proxy,
-        ConfigKey::ToolAll => cfg.tool.all_proxy,
-        ConfigKey::ToolNoProxy => cfg.tool.no_proxy,
+        ConfigKey::ToolHttp => cfg.tool.proxy.http_proxy,
+        ConfigKey::ToolHttps => cfg.tool.proxy.https_proxy,
+        ConfigKey::ToolAll => cfg.tool.proxy.all_proxy,
+        ConfigKey::ToolNoProxy => cfg.tool.proxy.no_proxy,
+        ConfigKey::ToolRetainVersions => cfg.tool.retain_versions,
+        ConfigKey::ToolMinReleaseAgeDays => cfg.tool.min_release_age_days,
+        ConfigKey::ToolCacheMaxSize => cfg.tool.cache_max_size,
         ConfigKey::UpdateHttp => cfg.update.http_proxy,
         ConfigKey::UpdateHttps => cfg.update.https_proxy,
         ConfigKey::UpdateAll => cfg.update.all_proxy,
@@ -439,12 +672,60 @@ fn unset_value(key: ConfigKey) -> Result<()> {
     Ok(())
 }
 
+fn ensure_per_tool_key(key: ConfigKey) -> Result<()> {
+    if key != ConfigKey::ToolMinReleaseAgeDays {
+        bail!(
+            "`--tool` only applies to `tool-min-release-age-days`, not `{}`",
+            key_label(key)
+        );
+    }
+    Ok(())
+}
+
+fn set_tool_override(key: ConfigKey, tool: &str, value: Option<String>) -> Result<()> {
+    ensure_per_tool_key(key)?;
+    let path = config_path()?;
+    let mut cfg = read_config(&path)?;
+    set_tool_min_release_age_override(&mut cfg.tool, tool, value.as_deref())?;
+    write_config(&path, &cfg)?;
+    let verb = if value.is_some() {
+        "updated"
+    } else {
+        "removed"
+    };
+    println!(
+        "{verb} {} for `{}` in {}",
+        key_label(key),
+        crate::command::tool::canonical_tool_name(tool.trim()),
+        path.display()
+    );
+    Ok(())
+}
+
+fn get_tool_override(key: ConfigKey, tool: &str) -> Result<()> {
+    ensure_per_tool_key(key)?;
+    let cfg = read_config(&config_path()?)?;
+    let tool = crate::command::tool::canonical_tool_name(tool.trim());
+    match cfg.tool.min_release_age_overrides.get(&tool) {
+        Some(days) => println!("{days}"),
+        None => println!("<unset>"),
+    }
+    Ok(())
+}
+
 fn set_value_impl(key: ConfigKey, value: String, print_result: bool) -> Result<()> {
     let path = config_path()?;
     let normalized = normalize_value(value).ok_or_else(|| anyhow!("value cannot be empty"))?;
     let mut cfg = read_config(&path)?;
     match key {
         ConfigKey::GithubToken => cfg.auth.github_token = Some(normalized),
+        ConfigKey::GithubApiBase => {
+            cfg.github.api_base = Some(normalize_base_url(&normalized, "github.api_base")?)
+        }
+        ConfigKey::GithubDownloadMirror => {
+            cfg.github.download_mirror =
+                Some(normalize_base_url(&normalized, "github.download_mirror")?)
+        }
         ConfigKey::ProxyHttp => cfg.proxy.http_proxy = Some(normalized),
         ConfigKey::ProxyHttps => cfg.proxy.https_proxy = Some(normalized),
         ConfigKey::ProxyAll => cfg.proxy.all_proxy = Some(normalized),
@@ -453,10 +734,22 @@ fn set_value_impl(key: ConfigKey, value: String, print_result: bool) -> Result<(
         ConfigKey::RunHttps => cfg.run.https_proxy = Some(normalized),
         ConfigKey::RunAll => cfg.run.all_proxy = Some(normalized),
         ConfigKey::RunNoProxy => cfg.run.no_proxy = Some(normalized),
-        ConfigKey::ToolHttp => cfg.tool.http_proxy = Some(normalized),
-        ConfigKey::ToolHttps => cfg.tool.https_proxy = Some(normalized),
-        ConfigKey::ToolAll => cfg.tool.all_proxy = Some(normalized),
-        ConfigKey::ToolNoProxy => cfg.tool.no_proxy = Some(normalized),
+        ConfigKey::ToolHttp => cfg.tool.proxy.http_proxy = Some(normalized),
+        ConfigKey::ToolHttps => cfg.tool.proxy.https_proxy = Some(normalized),
+        ConfigKey::ToolAll => cfg.tool.proxy.all_proxy = Some(normalized),
+        ConfigKey::ToolNoProxy => cfg.tool.proxy.no_proxy = Some(normalized),
+        ConfigKey::ToolRetainVersions => {
+            parse_tool_retain_versions(Some(&normalized))?;
+            cfg.tool.retain_versions = Some(normalized)
+        }
+        ConfigKey::ToolMinReleaseAgeDays => {
+            parse_u64_or_default(Some(&normalized), 0, "tool.min_release_age_days")?;
+            cfg.tool.min_release_age_days = Some(normalized)
+        }
+        ConfigKey::ToolCacheMaxSize => {
+            parse_byte_size(&normalized).context("parse `tool.cache_max_size`")?;
+            cfg.tool.cache_max_size = Some(normalized)
+        }
         ConfigKey::UpdateHttp => cfg.update.http_proxy = Some(normalized),
         ConfigKey::UpdateHttps => cfg.update.https_proxy = Some(normalized),
         ConfigKey::UpdateAll => cfg.update.all_proxy = Some(normalized),
@@ -476,6 +769,8 @@ fn unset_value_impl(key: ConfigKey, print_result: bool) -> Result<()> {
     let mut cfg = read_config(&path)?;
     match key {
         ConfigKey::GithubToken => cfg.auth.github_token = None,
+        ConfigKey::GithubApiBase => cfg.github.api_base = None,
+        ConfigKey::GithubDownloadMirror => cfg.github.download_mirror = None,
         ConfigKey::ProxyHttp => cfg.proxy.http_proxy = None,
         ConfigKey::ProxyHttps => cfg.proxy.https_proxy = None,
         ConfigKey::ProxyAll => cfg.proxy.all_proxy = None,
@@ -484,10 +779,13 @@ fn unset_value_impl(key: ConfigKey, print_result: bool) -> Result<()> {
         ConfigKey::RunHttps => cfg.run.https_proxy = None,
         ConfigKey::RunAll => cfg.run.all_proxy = None,
         ConfigKey::RunNoProxy => cfg.run.no_proxy = None,
-        ConfigKey::ToolHttp => cfg.tool.http_proxy = None,
-        ConfigKey::ToolHttps => cfg.tool.https_proxy = None,
-        ConfigKey::ToolAll => cfg.tool.all_proxy = None,
-        ConfigKey::ToolNoProxy => cfg.tool.no_proxy = None,
+        ConfigKey::ToolHttp => cfg.tool.proxy.http_proxy = None,
+        ConfigKey::ToolHttps => cfg.tool.proxy.https_proxy = None,
+        ConfigKey::ToolAll => cfg.tool.proxy.all_proxy = None,
+        ConfigKey::ToolNoProxy => cfg.tool.proxy.no_proxy = None,
+        ConfigKey::ToolRetainVersions => cfg.tool.retain_versions = None,
+        ConfigKey::ToolMinReleaseAgeDays => cfg.tool.min_release_age_days = None,
+        ConfigKey::ToolCacheMaxSize => cfg.tool.cache_max_size = None,
         ConfigKey::UpdateHttp => cfg.update.http_proxy = None,
         ConfigKey::UpdateHttps => cfg.update.https_proxy = None,
         ConfigKey::UpdateAll => cfg.update.all_proxy = None,
@@ -505,6 +803,8 @@ fn unset_value_impl(key: ConfigKey, print_result: bool) -> Result<()> {
 fn key_label(key: ConfigKey) -> &'static str {
     match key {
         ConfigKey::GithubToken => "github-token",
+        ConfigKey::GithubApiBase => "github-api-base",
+        ConfigKey::GithubDownloadMirror => "github-download-mirror",
         ConfigKey::ProxyHttp => "proxy-http",
         ConfigKey::ProxyHttps => "proxy-https",
         ConfigKey::ProxyAll => "proxy-all",
@@ -517,6 +817,9 @@ fn key_label(key: ConfigKey) -> &'static str {
         ConfigKey::ToolHttps => "tool-https",
         ConfigKey::ToolAll => "tool-all",
         ConfigKey::ToolNoProxy => "tool-no-proxy",
+        ConfigKey::ToolRetainVersions => "tool-retain-versions",
+        ConfigKey::ToolMinReleaseAgeDays => "tool-min-release-age-days",
+        ConfigKey::ToolCacheMaxSize => "tool-cache-max-size",
         ConfigKey::UpdateHttp => "update-http",
         ConfigKey::UpdateHttps => "update-https",
         ConfigKey::UpdateAll => "update-all",
@@ -533,6 +836,7 @@ fn config_item_label(item: &ConfigItem) -> String {
 fn module_label(module: ConfigModule) -> &'static str {
     match module {
         ConfigModule::Auth => "auth",
+        ConfigModule::Github => "github",
         ConfigModule::Proxy => "proxy",
         ConfigModule::Run => "run",
         ConfigModule::Tool => "tool",
@@ -1077,6 +1381,8 @@ fn centered_rect(width_percent: u16, height_percent: u16, area: Rect) -> Rect {
 fn config_value_by_key(cfg: &ZaConfig, key: ConfigKey) -> Option<&str> {
     match key {
         ConfigKey::GithubToken => cfg.auth.github_token.as_deref(),
+        ConfigKey::GithubApiBase => cfg.github.api_base.as_deref(),
+        ConfigKey::GithubDownloadMirror => cfg.github.download_mirror.as_deref(),
         ConfigKey::ProxyHttp => cfg.proxy.http_proxy.as_deref(),
         ConfigKey::ProxyHttps => cfg.proxy.https_proxy.as_deref(),
         ConfigKey::ProxyAll => cfg.proxy.all_proxy.as_deref(),
@@ -1085,10 +1391,13 @@ fn config_value_by_key(cfg: &ZaConfig, key: ConfigKey) -> Option<&str> {
         ConfigKey::RunHttps => cfg.run.https_proxy.as_deref(),
         ConfigKey::RunAll => cfg.run.all_proxy.as_deref(),
         ConfigKey::RunNoProxy => cfg.run.no_proxy.as_deref(),
-        ConfigKey::ToolHttp => cfg.tool.http_proxy.as_deref(),
-        ConfigKey::ToolHttps => cfg.tool.https_proxy.as_deref(),
-        ConfigKey::ToolAll => cfg.tool.all_proxy.as_deref(),
-        ConfigKey::ToolNoProxy => cfg.tool.no_proxy.as_deref(),
+        ConfigKey::ToolHttp => cfg.tool.proxy.http_proxy.as_deref(),
+        ConfigKey::ToolHttps => cfg.tool.proxy.https_proxy.as_deref(),
+        ConfigKey::ToolAll => cfg.tool.proxy.all_proxy.as_deref(),
+        ConfigKey::ToolNoProxy => cfg.tool.proxy.no_proxy.as_deref(),
+        ConfigKey::ToolRetainVersions => cfg.tool.retain_versions.as_deref(),
+        ConfigKey::ToolMinReleaseAgeDays => cfg.tool.min_release_age_days.as_deref(),
+        ConfigKey::ToolCacheMaxSize => cfg.tool.cache_max_size.as_deref(),
         ConfigKey::UpdateHttp => cfg.update.http_proxy.as_deref(),
         ConfigKey::UpdateHttps => cfg.update.https_proxy.as_deref(),
         ConfigKey::UpdateAll => cfg.update.all_proxy.as_deref(),
@@ -1115,6 +1424,14 @@ fn parse_positive_usize_or_default(
     Ok(value)
 }
 
+fn parse_u64_or_default(raw: Option<&str>, default: u64, field: &str) -> Result<u64> {
+    let Some(raw) = raw.and_then(|v| normalize_value(v.to_string())) else {
+        return Ok(default);
+    };
+    raw.parse::<u64>()
+        .with_context(|| format!("parse `{field}` as non-negative integer"))
+}
+
 fn parse_positive_u64_or_default(raw: Option<&str>, default: u64, field: &str) -> Result<u64> {
     let Some(raw) = raw.and_then(|v| normalize_value(v.to_string())) else {
         return Ok(default);
@@ -1128,11 +1445,22 @@ fn parse_positive_u64_or_default(raw: Option<&str>, default: u64, field: &str) -
     Ok(value)
 }
 
+fn normalize_base_url(raw: &str, field: &str) -> Result<String> {
+    let trimmed = raw.trim().trim_end_matches('/');
+    let host = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"));
+    if host.is_none_or(|host| host.is_empty() || host.contains(['?', '#'])) {
+        bail!("`{field}` must be an http(s) base URL, got `{raw}`");
+    }
+    Ok(trimmed.to_string())
+}
+
 fn maybe_config_path() -> Option<PathBuf> {
     Some(config_base_dir()?.join(CONFIG_FILE_NAME))
 }
 
-fn config_base_dir() -> Option<PathBuf> {
+pub(crate) fn config_base_dir() -> Option<PathBuf> {
     if let Some(base) = env::var_os("XDG_CONFIG_HOME") {
         return Some(PathBuf::from(base).join(CONFIG_DIR_NAME));
     }
@@ -1235,7 +1563,11 @@ fn mask_secret(secret: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{ProxyOverrides, merge_proxy_overrides};
+    use super::{
+        ConfigKey, ProxyOverrides, ToolConfig, ZaConfig, ensure_per_tool_key,
+        merge_proxy_overrides, normalize_base_url, parse_tool_retain_versions,
+        rewrite_github_download_url, set_tool_min_release_age_override, tool_min_release_age_days,
+    };
 
     #[test]
     fn scoped_proxy_overrides_global_values() {
@@ -1258,4 +1590,103 @@ mod tests {
         assert_eq!(merged.all_proxy.as_deref(), Some("socks5://scoped-all"));
         assert_eq!(merged.no_proxy.as_deref(), Some("localhost"));
     }
+
+    #[test]
+    fn tool_section_keeps_proxy_keys_beside_retention() {
+        let cfg = toml::from_str::<ZaConfig>(
+            "[tool]\nhttps_proxy = \"http://tool-https\"\nretain_versions = \"3\"\ncache_max_size = \"512M\"\n",
+        )
+        .expect("parse config");
+        assert_eq!(
+            cfg.tool.proxy.https_proxy.as_deref(),
+            Some("http://tool-https")
+        );
+        assert_eq!(cfg.tool.retain_versions.as_deref(), Some("3"));
+
+        let rendered = toml::to_string_pretty(&cfg).expect("render config");
+        let reparsed = toml::from_str::<ZaConfig>(&rendered).expect("reparse config");
+        assert_eq!(
+            reparsed.tool.proxy.https_proxy.as_deref(),
+            Some("http://tool-https")
+        );
+        assert_eq!(reparsed.tool.retain_versions.as_deref(), Some("3"));
+        assert_eq!(reparsed.tool.cache_max_size.as_deref(), Some("512M"));
+    }
+
+    #[test]
+    fn tool_retain_versions_defaults_to_two_and_accepts_all() {
+        assert_eq!(parse_tool_retain_versions(None).expect("default"), Some(2));
+        assert_eq!(
+            parse_tool_retain_versions(Some("5")).expect("count"),
+            Some(5)
+        );
+        assert_eq!(parse_tool_retain_versions(Some("all")).expect("all"), None);
+        assert!(parse_tool_retain_versions(Some("0")).is_err());
+    }
+
+    #[test]
+    fn tool_min_release_age_prefers_per_tool_overrides() {
+        let cfg = toml::from_str::<ZaConfig>(
+            "[tool]\nmin_release_age_days = \"7\"\n\n[tool.min_release_age_overrides]\nrg = 0\n",
+        )
+        .expect("parse config");
+        assert_eq!(
+            tool_min_release_age_days(&cfg.tool, "codex").expect("global"),
+            7
+        );
+        assert_eq!(
+            tool_min_release_age_days(&cfg.tool, "rg").expect("override"),
+            0
+        );
+        assert_eq!(
+            tool_min_release_age_days(&ToolConfig::default(), "rg").expect("default"),
+            0
+        );
+
+        let rendered = toml::to_string_pretty(&cfg).expect("render config");
+        let reparsed = toml::from_str::<ZaConfig>(&rendered).expect("reparse config");
+        assert_eq!(
+            reparsed.tool.min_release_age_overrides.get("rg").copied(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn tool_min_release_age_overrides_are_set_per_tool() {
+        let mut cfg = ToolConfig::default();
+        set_tool_min_release_age_override(&mut cfg, "ripgrep", Some("3")).expect("set override");
+        assert_eq!(tool_min_release_age_days(&cfg, "rg").expect("override"), 3);
+        assert!(set_tool_min_release_age_override(&mut cfg, "rg", Some("soon")).is_err());
+        set_tool_min_release_age_override(&mut cfg, "rg", None).expect("remove override");
+        assert!(cfg.min_release_age_overrides.is_empty());
+        assert!(ensure_per_tool_key(ConfigKey::ToolRetainVersions).is_err());
+    }
+
+    #[test]
+    fn github_base_urls_are_normalized_and_validated() {
+        assert_eq!(
+            normalize_base_url(" https://ghe.example.com/api/v3/ ", "github.api_base")
+                .expect("valid base"),
+            "https://ghe.example.com/api/v3"
+        );
+        let err = normalize_base_url("ghe.example.com", "github.api_base").expect_err("no scheme");
+        assert!(err.to_string().contains("github.api_base"));
+    }
+
+    #[test]
+    fn github_download_mirror_only_rewrites_github_urls() {
+        let url = "https://github.com/lvillis/za/releases/download/0.1.94/za.tar.gz";
+        assert_eq!(
+            rewrite_github_download_url(url, Some("https://mirror.example.com/gh")),
+            "https://mirror.example.com/gh/lvillis/za/releases/download/0.1.94/za.tar.gz"
+        );
+        assert_eq!(rewrite_github_download_url(url, None), url);
+        assert_eq!(
+            rewrite_github_download_url(
+                "https://github.company.com/a/b/releases/download/v1/x",
+                Some("https://mirror.example.com")
+            ),
+            "https://github.company.com/a/b/releases/download/v1/x"
+        );
+    }
 }
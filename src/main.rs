@@ -105,9 +105,14 @@ fn main() -> Result<()> {
         },
         cli::Commands::Pin { cmd } => exit_with(command::pin::run(cmd)?),
         cli::Commands::Port { cmd } => exit_with(command::port::run(cmd)?),
-        cli::Commands::Tool { user, global, cmd } => exit_with(command::tool::run(
+        cli::Commands::Tool {
+            user,
+            global,
+            project,
             cmd,
-            command::tool::ToolScopeRequest::from_flags(user, global)?,
+        } => exit_with(command::tool::run(
+            cmd,
+            command::tool::ToolScopeRequest::from_tool_flags(user, global, project)?,
         )?),
         cli::Commands::Run { tool, args } => exit_with(command::run::run(&tool, &args)?),
         cli::Commands::Codex { cmd, args } => exit_with(command::codex::run(cmd, &args)?),